@@ -0,0 +1,47 @@
+use criterion::{BatchSize, Criterion, black_box, criterion_group, criterion_main};
+use probability_rs::Distribution;
+use probability_rs::dist::binomial::Binomial;
+use probability_rs::dist::sampler::Sampler;
+use probability_rs::rng::SplitMix64;
+
+fn bench_binomial(c: &mut Criterion) {
+    // np from below the BTPE threshold up to 3e8: the cost should flatten once BTPE
+    // takes over.
+    for n in [100u64, 10_000, 1_000_000, 1_000_000_000] {
+        let b = Binomial::new(n, 0.3).unwrap();
+        c.bench_function(&format!("binomial_sample_n_{n}"), |bench| {
+            bench.iter_batched(
+                || SplitMix64::seed_from_u64(123),
+                |mut rng| {
+                    let mut acc = 0i64;
+                    for _ in 0..1000 {
+                        acc ^= b.sample(&mut rng);
+                    }
+                    black_box(acc)
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+}
+
+fn bench_binomial_sampler(c: &mut Criterion) {
+    // Same draws as `binomial_sample_n_1000000`, with the BTPE setup done once.
+    let mut s = Binomial::new(1_000_000, 0.3).unwrap().sampler();
+    c.bench_function("binomial_sampler_n_1000000", |bench| {
+        bench.iter_batched(
+            || SplitMix64::seed_from_u64(123),
+            |mut rng| {
+                let mut acc = 0i64;
+                for _ in 0..1000 {
+                    acc ^= s.next(&mut rng);
+                }
+                black_box(acc)
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(benches, bench_binomial, bench_binomial_sampler);
+criterion_main!(benches);
@@ -0,0 +1,72 @@
+use criterion::{BatchSize, Criterion, black_box, criterion_group, criterion_main};
+use probability_rs::Distribution;
+use probability_rs::dist::normal::Normal;
+use probability_rs::rng::SplitMix64;
+
+fn bench_normal_ziggurat(c: &mut Criterion) {
+    let n = Normal::standard();
+    c.bench_function("normal_sample_ziggurat", |b| {
+        b.iter_batched(
+            || SplitMix64::seed_from_u64(123),
+            |mut rng| {
+                let mut acc = 0.0;
+                for _ in 0..1000 {
+                    acc += n.sample(&mut rng);
+                }
+                black_box(acc)
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn bench_normal_polar(c: &mut Criterion) {
+    let n = Normal::standard();
+    c.bench_function("normal_sample_polar", |b| {
+        b.iter_batched(
+            || SplitMix64::seed_from_u64(123),
+            |mut rng| {
+                let mut acc = 0.0;
+                for _ in 0..1000 {
+                    acc += n.sample_polar(&mut rng);
+                }
+                black_box(acc)
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn bench_normal_fill(c: &mut Criterion) {
+    let n = Normal::standard();
+    let mut buf = vec![0.0; 1000];
+    c.bench_function("normal_sample_fill", |b| {
+        b.iter_batched(
+            || SplitMix64::seed_from_u64(123),
+            |mut rng| {
+                n.sample_fill(&mut rng, &mut buf);
+                black_box(buf[999])
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    // The block path only wins when built for AVX2 (`-C target-cpu=native`).
+    c.bench_function("normal_sample_fill_wide", |b| {
+        b.iter_batched(
+            || SplitMix64::seed_from_u64(123),
+            |mut rng| {
+                n.sample_fill_wide(&mut rng, &mut buf);
+                black_box(buf[999])
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_normal_ziggurat,
+    bench_normal_polar,
+    bench_normal_fill
+);
+criterion_main!(benches);
@@ -0,0 +1,59 @@
+use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use probability_rs::rng::buffered::BufferedRng;
+use probability_rs::rng::sync::SharedRng;
+use probability_rs::rng::{Pcg32, RngCore, SplitMix64, WyRand};
+
+const DRAWS: u64 = 1000;
+
+fn draw_u64<R: RngCore>(rng: &mut R) -> u64 {
+    let mut acc = 0u64;
+    for _ in 0..DRAWS {
+        acc ^= rng.next_u64();
+    }
+    acc
+}
+
+fn draw_f64<R: RngCore>(rng: &mut R) -> f64 {
+    let mut acc = 0.0;
+    for _ in 0..DRAWS {
+        acc += rng.next_f64();
+    }
+    acc
+}
+
+fn bench_next_u64(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rng_next_u64");
+    group.throughput(Throughput::Elements(DRAWS));
+    let mut wy = WyRand::seed_from_u64(1);
+    group.bench_function("wyrand", |b| b.iter(|| black_box(draw_u64(&mut wy))));
+    let mut sm = SplitMix64::seed_from_u64(1);
+    group.bench_function("splitmix64", |b| b.iter(|| black_box(draw_u64(&mut sm))));
+    let mut pcg = Pcg32::seed_from_u64(1);
+    group.bench_function("pcg32", |b| b.iter(|| black_box(draw_u64(&mut pcg))));
+    // One lock per draw versus one lock per block.
+    let shared = SharedRng::new(SplitMix64::seed_from_u64(1));
+    let mut handle = &shared;
+    group.bench_function("shared_splitmix64", |b| {
+        b.iter(|| black_box(draw_u64(&mut handle)))
+    });
+    let mut buffered = BufferedRng::new(&shared);
+    group.bench_function("shared_splitmix64_buffered", |b| {
+        b.iter(|| black_box(draw_u64(&mut buffered)))
+    });
+    group.finish();
+}
+
+fn bench_next_f64(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rng_next_f64");
+    group.throughput(Throughput::Elements(DRAWS));
+    let mut wy = WyRand::seed_from_u64(2);
+    group.bench_function("wyrand", |b| b.iter(|| black_box(draw_f64(&mut wy))));
+    let mut sm = SplitMix64::seed_from_u64(2);
+    group.bench_function("splitmix64", |b| b.iter(|| black_box(draw_f64(&mut sm))));
+    let mut pcg = Pcg32::seed_from_u64(2);
+    group.bench_function("pcg32", |b| b.iter(|| black_box(draw_f64(&mut pcg))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_next_u64, bench_next_f64);
+criterion_main!(benches);
@@ -0,0 +1,555 @@
+//! Enums over the built-in distributions, for runtime selection without trait objects.
+//!
+//! [`AnyContinuous`] and [`AnyDiscrete`] implement the core and capability traits by
+//! forwarding to the wrapped distribution, and convert from each variant with `From`.
+//!
+//! ```
+//! use probability_rs::dist::any::AnyContinuous;
+//! use probability_rs::dist::{gamma::Gamma, normal::Normal};
+//! use probability_rs::{Continuous, Moments};
+//!
+//! let models: Vec<AnyContinuous> = vec![
+//!     Normal::standard().into(),
+//!     Gamma::new(2.0, 0.5).unwrap().into(),
+//! ];
+//! assert_eq!(models[1].mean(), 1.0);
+//! assert!(matches!(models[0], AnyContinuous::Normal(_)));
+//! assert_eq!(models[0].inv_cdf(0.5), 0.0);
+//! ```
+//!
+//! [`AnyDistribution`] covers both kinds and parses specs such as `"Gamma(2, 0.5)"` or
+//! `"Normal(mu=0, sigma=1)"`; its `Display` output parses back to the same distribution.
+//!
+//! ```
+//! use probability_rs::dist::any::AnyDistribution;
+//!
+//! let d: AnyDistribution = "Gamma(2, 0.5)".parse().unwrap();
+//! assert_eq!(d.to_string(), "Gamma(shape=2, scale=0.5)");
+//! assert_eq!(d.to_string().parse::<AnyDistribution>().unwrap().to_string(), d.to_string());
+//! assert!("Poisson(-1)".parse::<AnyDistribution>().is_err());
+//! ```
+
+use crate::dist::{
+    CharacteristicFunction, Continuous, Discrete, DistError, Distribution, HigherMoments, Mgf,
+    Moments,
+};
+use crate::dist::{
+    bernoulli::Bernoulli, beta::Beta, binomial::Binomial, chisquared::ChiSquared,
+    exponential::Exponential, gamma::Gamma, geometric::Geometric, lognormal::LogNormal,
+    negative_binomial::NegativeBinomial, normal::Normal, poisson::Poisson, uniform::Uniform,
+};
+use crate::rng::RngCore;
+use std::fmt;
+use std::str::FromStr;
+
+/// Declares an enum over distribution types with `From` impls and a `dispatch!` helper
+/// macro of the same name that forwards a method call to the active variant.
+macro_rules! any_enum {
+    ($(#[$meta:meta])* $name:ident, $dispatch:ident { $($var:ident),* $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[non_exhaustive]
+        pub enum $name {
+            $($var($var),)*
+        }
+
+        $(
+            impl From<$var> for $name {
+                #[inline]
+                fn from(d: $var) -> Self {
+                    $name::$var(d)
+                }
+            }
+        )*
+
+        macro_rules! $dispatch {
+            ($self_:expr, $d:ident => $body:expr) => {
+                match $self_ {
+                    $($name::$var($d) => $body,)*
+                }
+            };
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                $dispatch!(self, d => fmt::Display::fmt(d, f))
+            }
+        }
+
+        impl $name {
+            /// Name of the wrapped distribution, e.g. `"Normal"`.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $($name::$var(_) => stringify!($var),)*
+                }
+            }
+        }
+    };
+}
+
+any_enum! {
+    /// Any built-in continuous distribution.
+    AnyContinuous, dispatch_continuous {
+        Beta, ChiSquared, Exponential, Gamma, LogNormal, Normal, Uniform,
+    }
+}
+
+any_enum! {
+    /// Any built-in discrete distribution.
+    AnyDiscrete, dispatch_discrete {
+        Bernoulli, Binomial, Geometric, NegativeBinomial, Poisson,
+    }
+}
+
+impl Distribution for AnyContinuous {
+    type Value = f64;
+    fn cdf(&self, x: f64) -> f64 {
+        dispatch_continuous!(self, d => d.cdf(x))
+    }
+    fn sample<R: RngCore>(&self, rng: &mut R) -> f64 {
+        dispatch_continuous!(self, d => d.sample(rng))
+    }
+    fn in_support(&self, x: f64) -> bool {
+        dispatch_continuous!(self, d => d.in_support(x))
+    }
+    fn sample_fill<R: RngCore>(&self, rng: &mut R, out: &mut [f64]) {
+        dispatch_continuous!(self, d => d.sample_fill(rng, out))
+    }
+    fn sample_antithetic<R: RngCore>(&self, rng: &mut R) -> (f64, f64) {
+        dispatch_continuous!(self, d => d.sample_antithetic(rng))
+    }
+    fn cdf_slice(&self, xs: &[f64], out: &mut [f64]) {
+        dispatch_continuous!(self, d => d.cdf_slice(xs, out))
+    }
+}
+
+impl Continuous for AnyContinuous {
+    fn pdf(&self, x: f64) -> f64 {
+        dispatch_continuous!(self, d => d.pdf(x))
+    }
+    fn pdf_slice(&self, xs: &[f64], out: &mut [f64]) {
+        dispatch_continuous!(self, d => d.pdf_slice(xs, out))
+    }
+    fn inv_cdf_slice(&self, ps: &[f64], out: &mut [f64]) {
+        dispatch_continuous!(self, d => d.inv_cdf_slice(ps, out))
+    }
+    fn support(&self) -> (f64, f64) {
+        dispatch_continuous!(self, d => d.support())
+    }
+    fn try_inv_cdf(&self, p: f64) -> Result<f64, DistError> {
+        dispatch_continuous!(self, d => d.try_inv_cdf(p))
+    }
+    fn try_isf(&self, q: f64) -> Result<f64, DistError> {
+        dispatch_continuous!(self, d => d.try_isf(q))
+    }
+}
+
+impl Distribution for AnyDiscrete {
+    type Value = i64;
+    fn cdf(&self, x: i64) -> f64 {
+        dispatch_discrete!(self, d => d.cdf(x))
+    }
+    fn sample<R: RngCore>(&self, rng: &mut R) -> i64 {
+        dispatch_discrete!(self, d => d.sample(rng))
+    }
+    fn in_support(&self, x: i64) -> bool {
+        dispatch_discrete!(self, d => d.in_support(x))
+    }
+    fn sample_fill<R: RngCore>(&self, rng: &mut R, out: &mut [i64]) {
+        dispatch_discrete!(self, d => d.sample_fill(rng, out))
+    }
+    fn sample_antithetic<R: RngCore>(&self, rng: &mut R) -> (i64, i64) {
+        dispatch_discrete!(self, d => d.sample_antithetic(rng))
+    }
+    fn cdf_slice(&self, xs: &[i64], out: &mut [f64]) {
+        dispatch_discrete!(self, d => d.cdf_slice(xs, out))
+    }
+}
+
+impl Discrete for AnyDiscrete {
+    fn pmf(&self, x: i64) -> f64 {
+        dispatch_discrete!(self, d => d.pmf(x))
+    }
+    fn pmf_slice(&self, ks: &[i64], out: &mut [f64]) {
+        dispatch_discrete!(self, d => d.pmf_slice(ks, out))
+    }
+    fn ln_pmf(&self, x: i64) -> f64 {
+        dispatch_discrete!(self, d => d.ln_pmf(x))
+    }
+    fn try_inv_cdf(&self, p: f64) -> Result<i64, DistError> {
+        dispatch_discrete!(self, d => d.try_inv_cdf(p))
+    }
+    fn try_isf(&self, q: f64) -> Result<i64, DistError> {
+        dispatch_discrete!(self, d => d.try_isf(q))
+    }
+    fn sf(&self, k: i64) -> f64 {
+        dispatch_discrete!(self, d => d.sf(k))
+    }
+    fn ln_sf(&self, k: i64) -> f64 {
+        dispatch_discrete!(self, d => d.ln_sf(k))
+    }
+    fn pgf(&self, s: f64) -> f64 {
+        dispatch_discrete!(self, d => d.pgf(s))
+    }
+    fn ln_pgf(&self, s: f64) -> f64 {
+        dispatch_discrete!(self, d => d.ln_pgf(s))
+    }
+}
+
+macro_rules! forward_capabilities {
+    ($name:ident, $dispatch:ident) => {
+        impl Moments for $name {
+            fn mean(&self) -> f64 {
+                $dispatch!(self, d => d.mean())
+            }
+            fn variance(&self) -> f64 {
+                $dispatch!(self, d => d.variance())
+            }
+            fn std_dev(&self) -> f64 {
+                $dispatch!(self, d => d.std_dev())
+            }
+            fn skewness(&self) -> f64 {
+                $dispatch!(self, d => d.skewness())
+            }
+            fn kurtosis(&self) -> f64 {
+                $dispatch!(self, d => d.kurtosis())
+            }
+            fn kurtosis_full(&self) -> f64 {
+                $dispatch!(self, d => d.kurtosis_full())
+            }
+            fn entropy(&self) -> f64 {
+                $dispatch!(self, d => d.entropy())
+            }
+        }
+
+        impl HigherMoments for $name {
+            fn raw_moment(&self, n: u32) -> f64 {
+                $dispatch!(self, d => d.raw_moment(n))
+            }
+            fn central_moment(&self, n: u32) -> f64 {
+                $dispatch!(self, d => d.central_moment(n))
+            }
+        }
+
+        impl CharacteristicFunction for $name {
+            fn cf(&self, t: f64) -> (f64, f64) {
+                $dispatch!(self, d => d.cf(t))
+            }
+        }
+    };
+}
+
+forward_capabilities!(AnyContinuous, dispatch_continuous);
+forward_capabilities!(AnyDiscrete, dispatch_discrete);
+
+// Beta and LogNormal have no closed-form MGF, so only the discrete enum forwards `Mgf`.
+impl Mgf for AnyDiscrete {
+    fn mgf(&self, t: f64) -> f64 {
+        dispatch_discrete!(self, d => d.mgf(t))
+    }
+    fn cgf(&self, t: f64) -> f64 {
+        dispatch_discrete!(self, d => d.cgf(t))
+    }
+}
+
+/// Any built-in distribution, continuous or discrete.
+///
+/// With the `serde` feature it (de)serializes untagged, i.e. exactly like the inner
+/// [`AnyContinuous`] or [`AnyDiscrete`] (`{"Gamma":{"shape":2.0,"scale":0.5}}`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(untagged)
+)]
+pub enum AnyDistribution {
+    Continuous(AnyContinuous),
+    Discrete(AnyDiscrete),
+}
+
+impl AnyDistribution {
+    /// Name of the wrapped distribution, e.g. `"Poisson"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AnyDistribution::Continuous(d) => d.name(),
+            AnyDistribution::Discrete(d) => d.name(),
+        }
+    }
+}
+
+impl From<AnyContinuous> for AnyDistribution {
+    #[inline]
+    fn from(d: AnyContinuous) -> Self {
+        AnyDistribution::Continuous(d)
+    }
+}
+
+impl From<AnyDiscrete> for AnyDistribution {
+    #[inline]
+    fn from(d: AnyDiscrete) -> Self {
+        AnyDistribution::Discrete(d)
+    }
+}
+
+impl fmt::Display for AnyDistribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnyDistribution::Continuous(d) => fmt::Display::fmt(d, f),
+            AnyDistribution::Discrete(d) => fmt::Display::fmt(d, f),
+        }
+    }
+}
+
+/// Error returned when parsing a distribution spec.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ParseDistError {
+    /// The input is not of the form `Name(arg, ...)`.
+    Syntax,
+    /// No built-in distribution has this name.
+    UnknownName(String),
+    /// Wrong number of arguments for the named distribution.
+    Arity {
+        name: &'static str,
+        expected: usize,
+        got: usize,
+    },
+    /// An argument is not a number, names an unknown parameter, or repeats one.
+    BadArgument(String),
+    /// The constructor rejected the parsed parameters.
+    Invalid(DistError),
+    /// The spec names a distribution of the other kind (e.g. `Poisson` for `AnyContinuous`).
+    WrongKind(&'static str),
+}
+
+impl fmt::Display for ParseDistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseDistError::Syntax => f.write_str("expected `Name(arg, ...)`"),
+            ParseDistError::UnknownName(n) => write!(f, "unknown distribution `{n}`"),
+            ParseDistError::Arity {
+                name,
+                expected,
+                got,
+            } => write!(f, "{name} takes {expected} argument(s), got {got}"),
+            ParseDistError::BadArgument(a) => write!(f, "bad argument `{a}`"),
+            ParseDistError::Invalid(e) => write!(f, "{e}"),
+            ParseDistError::WrongKind(n) => write!(f, "`{n}` is the wrong kind of distribution"),
+        }
+    }
+}
+
+impl std::error::Error for ParseDistError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseDistError::Invalid(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<DistError> for ParseDistError {
+    fn from(e: DistError) -> Self {
+        ParseDistError::Invalid(e)
+    }
+}
+
+/// Names and parameter names, in constructor order, as printed by `Display`.
+const SPECS: &[(&str, &[&str])] = &[
+    ("Bernoulli", &["p"]),
+    ("Beta", &["a", "b"]),
+    ("Binomial", &["n", "p"]),
+    ("ChiSquared", &["v"]),
+    ("Exponential", &["lambda"]),
+    ("Gamma", &["shape", "scale"]),
+    ("Geometric", &["p"]),
+    ("LogNormal", &["mu", "sigma"]),
+    ("NegativeBinomial", &["r", "p"]),
+    ("Normal", &["mu", "sigma"]),
+    ("Poisson", &["lambda"]),
+    ("Uniform", &["a", "b"]),
+];
+
+/// Splits `Name(args)` and orders the arguments by parameter; positional arguments
+/// must precede `key=value` ones. Names match case-insensitively.
+fn parse_spec(s: &str) -> Result<(&'static str, Vec<f64>), ParseDistError> {
+    let s = s.trim();
+    let open = s.find('(').ok_or(ParseDistError::Syntax)?;
+    let inner = s[open + 1..]
+        .strip_suffix(')')
+        .ok_or(ParseDistError::Syntax)?;
+    let raw_name = s[..open].trim();
+    let &(name, params) = SPECS
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(raw_name))
+        .ok_or_else(|| ParseDistError::UnknownName(raw_name.to_string()))?;
+
+    let args: Vec<&str> = if inner.trim().is_empty() {
+        Vec::new()
+    } else {
+        inner.split(',').map(str::trim).collect()
+    };
+    if args.len() != params.len() {
+        return Err(ParseDistError::Arity {
+            name,
+            expected: params.len(),
+            got: args.len(),
+        });
+    }
+    let mut values: Vec<Option<f64>> = vec![None; params.len()];
+    let mut seen_keyed = false;
+    for (i, arg) in args.iter().enumerate() {
+        let bad = || ParseDistError::BadArgument(arg.to_string());
+        let (slot, value) = match arg.split_once('=') {
+            Some((key, value)) => {
+                seen_keyed = true;
+                let key = key.trim();
+                let slot = params.iter().position(|p| *p == key).ok_or_else(bad)?;
+                (slot, value.trim())
+            }
+            None if !seen_keyed => (i, *arg),
+            None => return Err(bad()),
+        };
+        if values[slot].is_some() {
+            return Err(bad());
+        }
+        values[slot] = Some(value.parse::<f64>().map_err(|_| bad())?);
+    }
+    // Arity matched and no slot repeated, so every slot is filled.
+    Ok((name, values.into_iter().map(Option::unwrap).collect()))
+}
+
+fn build(name: &'static str, v: &[f64]) -> Result<AnyDistribution, ParseDistError> {
+    let continuous = |d: AnyContinuous| Ok(AnyDistribution::Continuous(d));
+    let discrete = |d: AnyDiscrete| Ok(AnyDistribution::Discrete(d));
+    match name {
+        "Bernoulli" => discrete(Bernoulli::new(v[0])?.into()),
+        "Beta" => continuous(Beta::new(v[0], v[1])?.into()),
+        "Binomial" => {
+            let n = v[0];
+            if !(n >= 0.0 && n.fract() == 0.0 && n <= u64::MAX as f64) {
+                return Err(ParseDistError::BadArgument(format!("n={n}")));
+            }
+            discrete(Binomial::new(n as u64, v[1])?.into())
+        }
+        "ChiSquared" => continuous(ChiSquared::new(v[0])?.into()),
+        "Exponential" => continuous(Exponential::new(v[0])?.into()),
+        "Gamma" => continuous(Gamma::new(v[0], v[1])?.into()),
+        "Geometric" => discrete(Geometric::new(v[0])?.into()),
+        "LogNormal" => continuous(LogNormal::new(v[0], v[1])?.into()),
+        "NegativeBinomial" => discrete(NegativeBinomial::new(v[0], v[1])?.into()),
+        "Normal" => continuous(Normal::new(v[0], v[1])?.into()),
+        "Poisson" => discrete(Poisson::new(v[0])?.into()),
+        "Uniform" => continuous(Uniform::new(v[0], v[1])?.into()),
+        _ => unreachable!("every SPECS entry has a constructor"),
+    }
+}
+
+impl FromStr for AnyDistribution {
+    type Err = ParseDistError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, values) = parse_spec(s)?;
+        build(name, &values)
+    }
+}
+
+impl FromStr for AnyContinuous {
+    type Err = ParseDistError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse::<AnyDistribution>()? {
+            AnyDistribution::Continuous(d) => Ok(d),
+            AnyDistribution::Discrete(d) => Err(ParseDistError::WrongKind(d.name())),
+        }
+    }
+}
+
+impl FromStr for AnyDiscrete {
+    type Err = ParseDistError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse::<AnyDistribution>()? {
+            AnyDistribution::Discrete(d) => Ok(d),
+            AnyDistribution::Continuous(d) => Err(ParseDistError::WrongKind(d.name())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+
+    #[test]
+    fn forwards_to_variant() {
+        let g = Gamma::new(2.5, 1.5).unwrap();
+        let any = AnyContinuous::from(g);
+        let mut a = SplitMix64::seed_from_u64(6);
+        let mut b = SplitMix64::seed_from_u64(6);
+        assert_eq!(any.sample(&mut a), g.sample(&mut b));
+        assert_eq!(any.pdf(1.0), g.pdf(1.0));
+        assert_eq!(any.raw_moment(3), g.raw_moment(3));
+        assert_eq!(any.name(), "Gamma");
+    }
+
+    #[test]
+    fn discrete_variants() {
+        let any: AnyDiscrete = Poisson::new(3.0).unwrap().into();
+        assert_eq!(any.mean(), 3.0);
+        assert_eq!(any.inv_cdf(0.0), 0);
+        let nb = AnyDiscrete::from(NegativeBinomial::new(2.0, 0.5).unwrap());
+        assert!((nb.mgf(0.0) - 1.0).abs() < 1e-15);
+        assert_eq!(nb.name(), "NegativeBinomial");
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for spec in [
+            "Normal(mu=-1.5, sigma=0.1)",
+            "Binomial(n=12, p=0.25)",
+            "NegativeBinomial(r=2.5, p=0.3)",
+            "Uniform(a=0, b=1e-300)",
+        ] {
+            let d: AnyDistribution = spec.parse().unwrap();
+            assert_eq!(d.to_string(), spec);
+        }
+        let keyed: AnyContinuous = "gamma(2, scale = 0.5)".parse().unwrap();
+        assert_eq!(keyed.to_string(), "Gamma(shape=2, scale=0.5)");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = |s: &str| s.parse::<AnyDistribution>().unwrap_err();
+        assert_eq!(err("Normal 0 1"), ParseDistError::Syntax);
+        assert_eq!(
+            err("Cauchy(0, 1)"),
+            ParseDistError::UnknownName("Cauchy".into())
+        );
+        assert!(matches!(
+            err("Poisson(1, 2)"),
+            ParseDistError::Arity {
+                expected: 1,
+                got: 2,
+                ..
+            }
+        ));
+        assert!(matches!(
+            err("Normal(sigma=1, 0)"),
+            ParseDistError::BadArgument(_)
+        ));
+        assert!(matches!(
+            err("Binomial(2.5, 0.5)"),
+            ParseDistError::BadArgument(_)
+        ));
+        assert_eq!(
+            err("Exponential(0)"),
+            ParseDistError::Invalid(DistError::NonPositive {
+                name: "lambda",
+                value: 0.0
+            })
+        );
+        assert!(matches!(
+            "Poisson(3)".parse::<AnyContinuous>(),
+            Err(ParseDistError::WrongKind("Poisson"))
+        ));
+    }
+}
@@ -1,23 +1,40 @@
-use crate::dist::{Discrete, DistError, Distribution, Moments};
+use crate::dist::{
+    CharacteristicFunction, Discrete, DistError, Distribution, HigherMoments, Mgf, Moments, Param,
+    check_probability, check_unit,
+};
 use crate::rng::RngCore;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Bernoulli {
     p: f64,
 }
 
 impl Bernoulli {
-    pub fn new(p: f64) -> Result<Self, DistError> {
-        if !(0.0..=1.0).contains(&p) || !p.is_finite() {
-            return Err(DistError::InvalidParameter);
-        }
+    pub const fn new(p: f64) -> Result<Self, DistError> {
+        const_try!(check_unit("p", p, true));
         Ok(Self { p })
     }
-    pub fn p(&self) -> f64 {
+    /// p = num/den, e.g. `from_ratio(1, 3)`.
+    pub const fn from_ratio(num: u64, den: u64) -> Result<Self, DistError> {
+        if den == 0 {
+            return Err(DistError::NonPositive {
+                name: "den",
+                value: 0.0,
+            });
+        }
+        Self::new(num as f64 / den as f64)
+    }
+    pub const fn p(&self) -> f64 {
         self.p
     }
 }
 
+impl std::fmt::Display for Bernoulli {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Bernoulli(p={})", Param(self.p))
+    }
+}
+
 impl Distribution for Bernoulli {
     type Value = i64;
     fn cdf(&self, x: Self::Value) -> f64 {
@@ -44,9 +61,20 @@ impl Discrete for Bernoulli {
             0.0
         }
     }
-    fn inv_cdf(&self, p: f64) -> Self::Value {
-        debug_assert!((0.0..=1.0).contains(&p));
-        if p < 1.0 - self.p { 0 } else { 1 }
+    fn try_inv_cdf(&self, p: f64) -> Result<i64, DistError> {
+        let p = check_probability(p)?;
+        Ok(if p < 1.0 - self.p { 0 } else { 1 })
+    }
+    fn sf(&self, x: i64) -> f64 {
+        match x {
+            x if x < 0 => 1.0,
+            0 => self.p,
+            _ => 0.0,
+        }
+    }
+
+    fn pgf(&self, s: f64) -> f64 {
+        1.0 - self.p + self.p * s
     }
 }
 
@@ -83,10 +111,56 @@ impl Moments for Bernoulli {
     }
 }
 
+impl Mgf for Bernoulli {
+    fn mgf(&self, t: f64) -> f64 {
+        if self.p == 0.0 {
+            return 1.0;
+        }
+        1.0 + self.p * t.exp_m1()
+    }
+    fn cgf(&self, t: f64) -> f64 {
+        if self.p == 0.0 {
+            return 0.0;
+        }
+        if t > 1.0 {
+            // t + ln(p + (1 − p) e^{−t}), finite after e^t overflows
+            return t + (self.p + (1.0 - self.p) * (-t).exp()).ln();
+        }
+        (self.p * t.exp_m1()).ln_1p()
+    }
+}
+
+impl CharacteristicFunction for Bernoulli {
+    fn cf(&self, t: f64) -> (f64, f64) {
+        (1.0 - self.p + self.p * t.cos(), self.p * t.sin())
+    }
+}
+
+impl HigherMoments for Bernoulli {
+    fn raw_moment(&self, n: u32) -> f64 {
+        if n == 0 { 1.0 } else { self.p }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
+    fn mgf_and_cgf() {
+        let b = Bernoulli::new(0.3).unwrap();
+        assert_eq!((b.mgf(0.0), b.cgf(0.0)), (1.0, 0.0));
+        let e = std::f64::consts::E;
+        assert!((b.mgf(1.0) - (0.7 + 0.3 * e)).abs() < 1e-15);
+        assert!((b.cgf(1.0) - (0.7 + 0.3 * e).ln()).abs() < 1e-15);
+        // Near 0 the cgf is p t without cancellation.
+        assert!((b.cgf(1e-10) / 3e-11 - 1.0).abs() < 1e-9);
+        assert!((b.cgf(-1000.0) - 0.7f64.ln()).abs() < 1e-15);
+        assert_eq!(b.mgf(1000.0), f64::INFINITY);
+        assert!((b.cgf(1000.0) - (1000.0 + 0.3f64.ln())).abs() < 1e-12);
+        let zero = Bernoulli::new(0.0).unwrap();
+        assert_eq!((zero.mgf(1000.0), zero.cgf(1000.0)), (1.0, 0.0));
+    }
+    #[test]
     fn pmf() {
         let b = Bernoulli::new(0.3).unwrap();
         assert!((b.pmf(1) - 0.3).abs() < 1e-15);
@@ -111,4 +185,17 @@ mod tests {
         let b1 = Bernoulli::new(1.0).unwrap();
         assert_eq!(b1.entropy(), 0.0);
     }
+
+    #[test]
+    fn from_ratio() {
+        assert_eq!(Bernoulli::from_ratio(1, 4).unwrap().p(), 0.25);
+        assert!(Bernoulli::from_ratio(1, 0).is_err());
+        assert_eq!(
+            Bernoulli::from_ratio(5, 4).unwrap_err(),
+            DistError::ProbabilityOutOfRange {
+                name: "p",
+                value: 1.25
+            }
+        );
+    }
 }
@@ -1,8 +1,12 @@
 use super::gamma::Gamma;
-use crate::dist::{Continuous, DistError, Distribution, Moments};
+use crate::dist::{
+    CharacteristicFunction, Continuous, DistError, Distribution, HigherMoments, Moments, Param,
+    check_positive, check_probability,
+};
+use crate::num;
 use crate::rng::RngCore;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Beta {
     a: f64,
     b: f64,
@@ -11,12 +15,14 @@ pub struct Beta {
 
 impl Beta {
     pub fn new(a: f64, b: f64) -> Result<Self, DistError> {
-        if !(a > 0.0 && b > 0.0 && a.is_finite() && b.is_finite()) {
-            return Err(DistError::InvalidParameter);
-        }
-        let ln_beta = ln_gamma(a) + ln_gamma(b) - ln_gamma(a + b);
+        check_positive("a", a)?;
+        check_positive("b", b)?;
+        let ln_beta = num::ln_beta(a, b);
         Ok(Self { a, b, ln_beta })
     }
+    pub fn builder() -> BetaBuilder {
+        BetaBuilder::default()
+    }
     #[inline]
     pub fn a(&self) -> f64 {
         self.a
@@ -27,6 +33,42 @@ impl Beta {
     }
 }
 
+/// Builder for [`Beta`]; give both shapes.
+///
+/// ```
+/// use probability_rs::dist::beta::Beta;
+/// let b = Beta::builder().a(2.0).b(5.0).build()?;
+/// assert_eq!((b.a(), b.b()), (2.0, 5.0));
+/// # Ok::<(), probability_rs::dist::DistError>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BetaBuilder {
+    a: Option<f64>,
+    b: Option<f64>,
+}
+
+impl BetaBuilder {
+    pub fn a(mut self, a: f64) -> Self {
+        self.a = Some(a);
+        self
+    }
+    pub fn b(mut self, b: f64) -> Self {
+        self.b = Some(b);
+        self
+    }
+    pub fn build(self) -> Result<Beta, DistError> {
+        let a = self.a.ok_or(DistError::MissingParameter { name: "a" })?;
+        let b = self.b.ok_or(DistError::MissingParameter { name: "b" })?;
+        Beta::new(a, b)
+    }
+}
+
+impl std::fmt::Display for Beta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Beta(a={}, b={})", Param(self.a), Param(self.b))
+    }
+}
+
 impl Distribution for Beta {
     type Value = f64;
     fn cdf(&self, x: f64) -> f64 {
@@ -36,7 +78,7 @@ impl Distribution for Beta {
         if x >= 1.0 {
             return 1.0;
         }
-        reg_inc_beta(self.a, self.b, x)
+        num::betainc(self.a, self.b, x)
     }
     fn in_support(&self, x: f64) -> bool {
         (0.0..=1.0).contains(&x) && x.is_finite()
@@ -55,30 +97,23 @@ impl Continuous for Beta {
         }
         ((self.a - 1.0) * x.ln() + (self.b - 1.0) * (1.0 - x).ln() - self.ln_beta).exp()
     }
-    fn inv_cdf(&self, p: f64) -> f64 {
-        debug_assert!(p > 0.0 && p < 1.0);
-        // Simple Newton with bracketing in [0,1]
-        let mut lo = 0.0;
-        let mut hi = 1.0;
-        let mut x = p; // initial guess
-        for _ in 0..60 {
-            let fx = self.cdf(x) - p;
-            if fx.abs() < 1e-10 {
-                break;
-            }
-            if fx < 0.0 {
-                lo = x;
-            } else {
-                hi = x;
-            }
-            let dfx = self.pdf(x).max(1e-300);
-            let mut x_new = x - fx / dfx;
-            if !(0.0..=1.0).contains(&x_new) {
-                x_new = 0.5 * (lo + hi);
-            }
-            x = x_new;
+    fn support(&self) -> (f64, f64) {
+        (0.0, 1.0)
+    }
+    fn try_inv_cdf(&self, p: f64) -> Result<f64, DistError> {
+        let p = check_probability(p)?;
+        if p == 0.0 || p == 1.0 {
+            return Ok(p);
+        }
+        Ok(num::betainc_inv(self.a, self.b, p))
+    }
+    fn try_isf(&self, q: f64) -> Result<f64, DistError> {
+        // 1 − X ~ Beta(b, a), whose lower tail keeps tiny q.
+        let q = check_probability(q)?;
+        if q == 0.0 || q == 1.0 {
+            return Ok(1.0 - q);
         }
-        x
+        Ok(1.0 - num::betainc_inv(self.b, self.a, q))
     }
 }
 
@@ -107,70 +142,58 @@ impl Moments for Beta {
         // H = ln B(a,b) - (a-1)ψ(a) - (b-1)ψ(b) + (a+b-2)ψ(a+b)
         let a = self.a;
         let b = self.b;
-        let ln_beta =
-            super::gamma::ln_gamma(a) + super::gamma::ln_gamma(b) - super::gamma::ln_gamma(a + b);
-        ln_beta - (a - 1.0) * crate::num::digamma(a) - (b - 1.0) * crate::num::digamma(b)
-            + (a + b - 2.0) * crate::num::digamma(a + b)
+        self.ln_beta - (a - 1.0) * num::digamma(a) - (b - 1.0) * num::digamma(b)
+            + (a + b - 2.0) * num::digamma(a + b)
     }
 }
 
-// Helpers: ln_gamma and regularized incomplete beta (continued fractions)
-fn ln_gamma(z: f64) -> f64 {
-    super::gamma::ln_gamma(z)
-}
-
-fn reg_inc_beta(a: f64, b: f64, x: f64) -> f64 {
-    // Use symmetry to ensure x <= (a+1)/(a+b+2)
-    if x <= 0.0 {
-        return 0.0;
-    }
-    if x >= 1.0 {
-        return 1.0;
-    }
-    let bt = ((a + b).ln() - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
-    if x < (a + 1.0) / (a + b + 2.0) {
-        bt * beta_cf(a, b, x) / a
-    } else {
-        1.0 - bt * beta_cf(b, a, 1.0 - x) / b
+impl CharacteristicFunction for Beta {
+    fn cf(&self, t: f64) -> (f64, f64) {
+        // φ(t) = ₁F₁(a; a+b; it) has no elementary form; integrate numerically.
+        super::numerical_cf(self, t)
     }
 }
 
-fn beta_cf(a: f64, b: f64, x: f64) -> f64 {
-    // Continued fraction for incomplete beta (Numerical Recipes style)
-    let mut am = 1.0;
-    let mut bm = 1.0;
-    let mut az = 1.0;
-    let qab = a + b;
-    let qap = a + 1.0;
-    let qam = a - 1.0;
-    let mut bz = 1.0 - qab * x / qap;
-    let eps = 3e-14;
-    let fpmin = 1e-300;
-    for m in 1..=200 {
-        let m2 = 2 * m;
-        // even step
-        let d = m as f64 * (b - m as f64) * x / ((qam + m2 as f64) * (a + m2 as f64));
-        let ap = az + d * am;
-        let bp = bz + d * bm;
-        // odd step
-        let d = -(a + m as f64) * (qab + m as f64) * x / ((a + m2 as f64) * (qap + m2 as f64));
-        let app = ap + d * az;
-        let bpp = bp + d * bz;
-        am = ap / bpp.max(fpmin);
-        bm = bp / bpp.max(fpmin);
-        az = app / bpp.max(fpmin);
-        bz = 1.0;
-        if (app - ap).abs() < eps * app.abs() {
-            break;
-        }
+impl HigherMoments for Beta {
+    fn raw_moment(&self, n: u32) -> f64 {
+        // Π_{r<n} (a + r) / (a + b + r)
+        (0..n).fold(1.0, |acc, r| {
+            let r = r as f64;
+            acc * (self.a + r) / (self.a + self.b + r)
+        })
     }
-    az
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
+    fn quantiles_for_skewed_shapes() {
+        // mpmath bisection on betainc(a, b, 0, x, regularized=True) = p
+        for (a, b, p, want) in [
+            (0.5, 0.5, 0.25, 0.14644660940672624),
+            (2.0, 3.0, 0.4, 0.32916650337840788),
+            (0.01, 50.0, 0.5, 9.0202183843672219e-33),
+            (200.0, 0.3, 1e-9, 0.91569657397150735),
+            (1e-3, 1e-3, 0.3, 1.4142853421408587e-222),
+            (5.0, 5.0, 1e-100, 3.8012452545008435e-21),
+            (30.0, 2.0, 0.999999, 0.99995360530870799),
+            // Tiny quantiles above the median: x = p^(1/a) for b = 1.
+            (0.01, 1.0, 0.6, 6.5331862350007091e-23),
+            (0.001, 1.0, 0.9, 1.7478712517226516e-46),
+        ] {
+            let x = Beta::new(a, b).unwrap().inv_cdf(p);
+            // x ~ exp(ln I / a): ulp errors in the ln Γ terms are amplified by 1/a.
+            assert!((x / want - 1.0).abs() < 5e-12, "a={a} b={b} p={p}: {x}");
+        }
+    }
+    #[test]
+    fn isf_keeps_tiny_upper_tails() {
+        // mpmath: 1 − x with betainc(5, 2, 0, 1 − x, regularized=True) = 1e-12
+        let x = Beta::new(2.0, 5.0).unwrap().isf(1e-12);
+        assert!((x / 0.99721662673631517 - 1.0).abs() < 1e-14, "{x}");
+    }
+    #[test]
     fn moments() {
         let b = Beta::new(2.0, 5.0).unwrap();
         assert!((b.mean() - (2.0 / 7.0)).abs() < 1e-12);
@@ -181,4 +204,13 @@ mod tests {
         assert!(b.skewness().abs() < 1e-15);
         assert!((b.kurtosis() - (-6.0 / 7.0)).abs() < 1e-12);
     }
+
+    #[test]
+    fn cdf_reference_values() {
+        // mpmath.betainc(a, b, 0, x, regularized=True)
+        let b = Beta::new(2.0, 5.0).unwrap();
+        assert!((b.cdf(0.3) - 0.579825).abs() < 1e-12);
+        let b = Beta::new(10.0, 3.0).unwrap();
+        assert!((b.cdf(0.7) - 0.252815347855).abs() < 1e-11);
+    }
 }
@@ -1,103 +1,443 @@
-use crate::dist::{Discrete, DistError, Distribution, Moments};
+use crate::dist::sampler::Sampler;
+use crate::dist::{
+    CharacteristicFunction, Discrete, DistError, Distribution, HigherMoments, Mgf, Moments, Param,
+    check_probability, check_unit,
+};
+use crate::num::sum::Kahan;
+use crate::num::{self, bd0, betainc, betainc_xy, ln_betainc, stirlerr};
 use crate::rng::RngCore;
+use crate::telemetry::{self, Counter};
 
 /// Binomial(n, p) over k=0..n.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Binomial {
     n: u64,
     p: f64,
 }
 
 impl Binomial {
-    pub fn new(n: u64, p: f64) -> Result<Self, DistError> {
-        if !(0.0..=1.0).contains(&p) || !p.is_finite() {
-            return Err(DistError::InvalidParameter);
-        }
+    /// `n·min(p, 1−p)` above which [`Distribution::sample`] switches from inversion to BTPE.
+    pub const BTPE_THRESHOLD: f64 = 30.0;
+
+    pub const fn new(n: u64, p: f64) -> Result<Self, DistError> {
+        const_try!(check_unit("p", p, true));
         Ok(Self { n, p })
     }
+    pub fn builder() -> BinomialBuilder {
+        BinomialBuilder::default()
+    }
     #[inline]
-    pub fn n(&self) -> u64 {
+    pub const fn n(&self) -> u64 {
         self.n
     }
     #[inline]
-    pub fn p(&self) -> f64 {
+    pub const fn p(&self) -> f64 {
         self.p
     }
 
-    fn pmf_recurrence(&self, k: u64) -> f64 {
-        if k > self.n {
-            return 0.0;
-        }
-        // Start at k=0: (1-p)^n
-        let mut p0 = (1.0 - self.p).powi(self.n as i32);
-        if k == 0 {
-            return p0;
-        }
-        // p(k) = p(k-1) * (n-k+1)/k * (p/(1-p))
-        let odds = self.p / (1.0 - self.p);
-        for i in 1..=k {
-            p0 *= (self.n - i + 1) as f64 / (i as f64) * odds;
-        }
-        p0
-    }
-
-    fn cdf_sum(&self, k: u64) -> f64 {
+    /// P(X <= k) = I_{1−p}(n − k, k + 1), with `p` passed as the complement so `1 − p` is
+    /// never rounded.
+    fn cdf_beta(&self, k: u64) -> f64 {
         if k >= self.n {
             return 1.0;
         }
-        let mut sum = 0.0;
-        for i in 0..=k {
-            sum += self.pmf_recurrence(i);
-        }
-        sum
+        betainc_xy((self.n - k) as f64, k as f64 + 1.0, 1.0 - self.p, self.p)
+    }
+}
+
+/// Builder for [`Binomial`]; give the number of trials `n` and the success probability `p`.
+///
+/// ```
+/// use probability_rs::dist::binomial::Binomial;
+/// let b = Binomial::builder().n(20).p(0.3).build()?;
+/// assert_eq!((b.n(), b.p()), (20, 0.3));
+/// # Ok::<(), probability_rs::dist::DistError>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BinomialBuilder {
+    n: Option<u64>,
+    p: Option<f64>,
+}
+
+impl BinomialBuilder {
+    pub fn n(mut self, n: u64) -> Self {
+        self.n = Some(n);
+        self
+    }
+    pub fn p(mut self, p: f64) -> Self {
+        self.p = Some(p);
+        self
+    }
+    pub fn build(self) -> Result<Binomial, DistError> {
+        let n = self.n.ok_or(DistError::MissingParameter { name: "n" })?;
+        let p = self.p.ok_or(DistError::MissingParameter { name: "p" })?;
+        Binomial::new(n, p)
+    }
+}
+
+impl std::fmt::Display for Binomial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Binomial(n={}, p={})", self.n, Param(self.p))
     }
 }
 
 impl Distribution for Binomial {
     type Value = i64;
     fn cdf(&self, x: i64) -> f64 {
-        if x < 0 { 0.0 } else { self.cdf_sum(x as u64) }
+        if x < 0 { 0.0 } else { self.cdf_beta(x as u64) }
     }
     fn in_support(&self, x: i64) -> bool {
         x >= 0 && (x as u64) <= self.n
     }
+    /// Sequential inversion for `n·min(p, 1−p) <` [`Binomial::BTPE_THRESHOLD`] (O(np)
+    /// steps), BTPE rejection above it (O(1) expected).
     fn sample<R: RngCore>(&self, rng: &mut R) -> i64 {
-        // Inversion by summing pmf from 0.. until exceeds u
-        let u = rng.next_f64();
-        let mut acc = 0.0;
-        for k in 0..=self.n {
-            acc += self.pmf_recurrence(k);
-            if u <= acc {
-                return k as i64;
+        self.sampler().next(rng)
+    }
+}
+
+/// [`Binomial`] sampler keeping the inversion or BTPE setup between draws; see
+/// [`Binomial::sampler`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BinomialSampler {
+    n: u64,
+    // Draws use min(p, 1 − p) and are reflected when p > 1/2.
+    reflect: bool,
+    method: Method,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Method {
+    Inversion(Inversion),
+    Btpe(Btpe),
+}
+
+impl Binomial {
+    // δ(n) − δ(k) − δ(n−k) − bd0(k, np) − bd0(n−k, nq), for 0 < k < n and 0 < p < 1.
+    fn ln_pmf_exponent(&self, n: f64, k: f64) -> f64 {
+        stirlerr(n)
+            - stirlerr(k)
+            - stirlerr(n - k)
+            - bd0(k, n * self.p)
+            - bd0(n - k, n * (1.0 - self.p))
+    }
+
+    /// A sampler with the per-distribution setup done once.
+    pub fn sampler(&self) -> BinomialSampler {
+        let r = self.p.min(1.0 - self.p);
+        let method = if self.n as f64 * r < Self::BTPE_THRESHOLD {
+            Method::Inversion(Inversion::new(self.n, r))
+        } else {
+            Method::Btpe(Btpe::new(self.n, r))
+        };
+        BinomialSampler {
+            n: self.n,
+            reflect: self.p > 0.5,
+            method,
+        }
+    }
+}
+
+impl Sampler for BinomialSampler {
+    type Value = i64;
+    #[inline]
+    fn next<R: RngCore>(&mut self, rng: &mut R) -> i64 {
+        let y = match &self.method {
+            Method::Inversion(inv) => inv.sample(rng),
+            Method::Btpe(btpe) => btpe.sample(rng),
+        };
+        let y = if self.reflect { self.n - y } else { y };
+        y as i64
+    }
+}
+
+/// Inversion walking the pmf recurrence from 0; restarts if it runs past a 10σ bound.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Inversion {
+    n: u64,
+    p: f64,
+    q: f64,
+    qn: f64,
+    bound: u64,
+}
+
+impl Inversion {
+    fn new(n: u64, p: f64) -> Self {
+        let q = 1.0 - p;
+        let np = n as f64 * p;
+        Self {
+            n,
+            p,
+            q,
+            qn: (n as f64 * (-p).ln_1p()).exp(),
+            bound: (np + 10.0 * (np * q + 1.0).sqrt()).min(n as f64) as u64,
+        }
+    }
+
+    fn sample<R: RngCore>(&self, rng: &mut R) -> u64 {
+        let Inversion { n, p, q, qn, bound } = *self;
+        'restart: loop {
+            let mut u = rng.next_f64();
+            let mut px = qn;
+            let mut x = 0;
+            while u > px {
+                x += 1;
+                if x > bound {
+                    continue 'restart;
+                }
+                u -= px;
+                px *= (n - x + 1) as f64 * p / (x as f64 * q);
+            }
+            telemetry::record(Counter::BinomialSearchSteps, x);
+            return x;
+        }
+    }
+}
+
+/// BTPE (Kachitvichyanukul & Schmeiser 1988) for `p <= 1/2` and `np >= 30`: a triangle,
+/// two parallelograms and two exponential tails majorize the pmf around the mode.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Btpe {
+    n: f64,
+    p: f64,
+    q: f64,
+    npq: f64,
+    m: f64,
+    p1: f64,
+    xm: f64,
+    xl: f64,
+    xr: f64,
+    c: f64,
+    lambda_l: f64,
+    lambda_r: f64,
+    p2: f64,
+    p3: f64,
+    p4: f64,
+}
+
+impl Btpe {
+    fn new(n: u64, p: f64) -> Self {
+        let nf = n as f64;
+        let q = 1.0 - p;
+        let npq = nf * p * q;
+        let fm = nf * p + p;
+        let m = fm.floor();
+        let p1 = (2.195 * npq.sqrt() - 4.6 * q).floor() + 0.5;
+        let xm = m + 0.5;
+        let xl = xm - p1;
+        let xr = xm + p1;
+        let c = 0.134 + 20.5 / (15.3 + m);
+        let a = (fm - xl) / (fm - xl * p);
+        let lambda_l = a * (1.0 + 0.5 * a);
+        let a = (xr - fm) / (xr * q);
+        let lambda_r = a * (1.0 + 0.5 * a);
+        let p2 = p1 * (1.0 + 2.0 * c);
+        let p3 = p2 + c / lambda_l;
+        let p4 = p3 + c / lambda_r;
+        Self {
+            n: nf,
+            p,
+            q,
+            npq,
+            m,
+            p1,
+            xm,
+            xl,
+            xr,
+            c,
+            lambda_l,
+            lambda_r,
+            p2,
+            p3,
+            p4,
+        }
+    }
+
+    fn sample<R: RngCore>(&self, rng: &mut R) -> u64 {
+        let Btpe {
+            n: nf,
+            p,
+            q,
+            npq,
+            m,
+            p1,
+            xm,
+            xl,
+            xr,
+            c,
+            lambda_l,
+            lambda_r,
+            p2,
+            p3,
+            p4,
+        } = *self;
+        loop {
+            let u = rng.next_f64() * p4;
+            let mut v = rng.next_f64();
+            // Triangle: accepted outright.
+            if u <= p1 {
+                return (xm - p1 * v + u).floor() as u64;
+            }
+            let y = if u <= p2 {
+                // Parallelograms.
+                let x = xl + (u - p1) / c;
+                v = v * c + 1.0 - (m - x + 0.5).abs() / p1;
+                if v > 1.0 {
+                    telemetry::record(Counter::BinomialBtpeRejections, 1);
+                    continue;
+                }
+                x.floor()
+            } else if u <= p3 {
+                // Left exponential tail.
+                let y = (xl + v.ln() / lambda_l).floor();
+                if y < 0.0 || v == 0.0 {
+                    telemetry::record(Counter::BinomialBtpeRejections, 1);
+                    continue;
+                }
+                v *= (u - p2) * lambda_l;
+                y
+            } else {
+                // Right exponential tail.
+                let y = (xr - v.ln() / lambda_r).floor();
+                if y > nf || v == 0.0 {
+                    telemetry::record(Counter::BinomialBtpeRejections, 1);
+                    continue;
+                }
+                v *= (u - p3) * lambda_r;
+                y
+            };
+
+            let k = (y - m).abs();
+            let accept = if k <= 20.0 || k >= 0.5 * npq - 1.0 {
+                // Explicit ratio f(y)/f(m) by the pmf recurrence.
+                let s = p / q;
+                let a = s * (nf + 1.0);
+                let mut f = 1.0;
+                if m < y {
+                    let mut i = m + 1.0;
+                    while i <= y {
+                        f *= a / i - s;
+                        i += 1.0;
+                    }
+                } else {
+                    let mut i = y + 1.0;
+                    while i <= m {
+                        f /= a / i - s;
+                        i += 1.0;
+                    }
+                }
+                v <= f
+            } else {
+                // Squeeze on ln v, then the Stirling-corrected log ratio.
+                let rho = (k / npq) * ((k * (k / 3.0 + 0.625) + 1.0 / 6.0) / npq + 0.5);
+                let t = -k * k / (2.0 * npq);
+                let ln_v = v.ln();
+                if ln_v < t - rho {
+                    true
+                } else if ln_v > t + rho {
+                    false
+                } else {
+                    let x1 = y + 1.0;
+                    let f1 = m + 1.0;
+                    let z = nf + 1.0 - m;
+                    let w = nf - y + 1.0;
+                    let corr = |t: f64| {
+                        let t2 = t * t;
+                        (13680.0 - (462.0 - (132.0 - (99.0 - 140.0 / t2) / t2) / t2) / t2)
+                            / t
+                            / 166320.0
+                    };
+                    ln_v <= xm * (f1 / x1).ln()
+                        + (nf - m + 0.5) * (z / w).ln()
+                        + (y - m) * (w * p / (x1 * q)).ln()
+                        + corr(f1)
+                        + corr(z)
+                        + corr(x1)
+                        + corr(w)
+                }
+            };
+            if accept {
+                return y as u64;
             }
+            telemetry::record(Counter::BinomialBtpeRejections, 1);
         }
-        self.n as i64
     }
 }
 
 impl Discrete for Binomial {
+    /// `e^(−deviance) √(n / 2πk(n−k))`, as R's `dbinom`: the same terms as
+    /// [`ln_pmf`](Self::ln_pmf), with the prefactor applied outside the exponential so its
+    /// logarithm adds no rounding to it.
     fn pmf(&self, x: i64) -> f64 {
-        if x < 0 {
-            return 0.0;
+        let (n, k) = (self.n as f64, x as f64);
+        if x <= 0 || x as u64 >= self.n || self.p == 0.0 || self.p == 1.0 {
+            return self.ln_pmf(x).exp();
         }
-        self.pmf_recurrence(x as u64)
+        self.ln_pmf_exponent(n, k).exp() * (n / (2.0 * std::f64::consts::PI * k * (n - k))).sqrt()
     }
-    fn inv_cdf(&self, p: f64) -> i64 {
-        debug_assert!((0.0..=1.0).contains(&p));
-        if p <= 0.0 {
-            return 0;
+
+    /// Loader's saddle-point form `δ(n) − δ(k) − δ(n−k) − bd0(k, np) − bd0(n−k, nq)
+    /// − ½ ln(2πk(n−k)/n)`, free of the cancellation in `ln C(n, k) + k ln p + …`,
+    /// so it is O(1) and accurate for any `n`.
+    fn ln_pmf(&self, x: i64) -> f64 {
+        if x < 0 || x as u64 > self.n {
+            return f64::NEG_INFINITY;
+        }
+        let (n, k) = (self.n as f64, x as f64);
+        if self.p == 0.0 || self.p == 1.0 {
+            let at = if self.p == 0.0 { 0.0 } else { n };
+            return if k == at { 0.0 } else { f64::NEG_INFINITY };
         }
-        if p >= 1.0 {
-            return self.n as i64;
+        if k == 0.0 {
+            return n * (-self.p).ln_1p();
         }
-        let mut acc = 0.0;
-        for k in 0..=self.n {
-            acc += self.pmf_recurrence(k);
-            if p <= acc {
-                return k as i64;
+        if k == n {
+            return n * self.p.ln();
+        }
+        self.ln_pmf_exponent(n, k)
+            - 0.5 * ((2.0 * std::f64::consts::PI * k).ln() + (-k / n).ln_1p())
+    }
+    fn try_inv_cdf(&self, p: f64) -> Result<i64, DistError> {
+        let p = check_probability(p)?;
+        if p == 0.0 {
+            return Ok(0);
+        }
+        if p == 1.0 {
+            return Ok(self.n as i64);
+        }
+        // Smallest k with cdf(k) >= p, by bisection on the incomplete-beta CDF.
+        let (mut lo, mut hi) = (0, self.n);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.cdf_beta(mid) >= p {
+                hi = mid;
+            } else {
+                lo = mid + 1;
             }
         }
-        self.n as i64
+        Ok(lo as i64)
+    }
+    fn sf(&self, k: i64) -> f64 {
+        // P(X > k) = I_p(k + 1, n − k)
+        match k {
+            k if k < 0 => 1.0,
+            k if k as u64 >= self.n => 0.0,
+            k => betainc(k as f64 + 1.0, (self.n - k as u64) as f64, self.p),
+        }
+    }
+    fn ln_sf(&self, k: i64) -> f64 {
+        match k {
+            k if k < 0 => 0.0,
+            k if k as u64 >= self.n => f64::NEG_INFINITY,
+            k => ln_betainc(k as f64 + 1.0, (self.n - k as u64) as f64, self.p),
+        }
+    }
+
+    fn pgf(&self, s: f64) -> f64 {
+        (1.0 - self.p + self.p * s).powf(self.n as f64)
+    }
+    fn ln_pgf(&self, s: f64) -> f64 {
+        (self.n as f64) * (self.p * (s - 1.0)).ln_1p()
     }
 }
 
@@ -119,21 +459,88 @@ impl Moments for Binomial {
         (1.0 - 6.0 * p * (1.0 - p)) / (n * p * (1.0 - p))
     }
     fn entropy(&self) -> f64 {
-        // Exact sum: H = - sum_{k=0}^n pmf(k) ln pmf(k)
-        let mut h = 0.0;
-        for k in 0..=self.n {
-            let pk = self.pmf_recurrence(k);
-            if pk > 0.0 {
-                h -= pk * pk.ln();
+        // H = −Σ pmf(k) ln pmf(k), summed outwards from the mode over unnormalized weights
+        // w_k = pmf(k)/pmf(mode) until they are negligible: with W = Σ w_k,
+        // H = ln W − Σ w_k ln w_k / W. O(σ) terms and no underflow for large n.
+        if self.n == 0 || self.p == 0.0 || self.p == 1.0 {
+            return 0.0;
+        }
+        let n = self.n as f64;
+        let ln_odds = num::logit(self.p);
+        let mode = ((n + 1.0) * self.p).floor().min(n);
+        let (mut total, mut weighted) = (Kahan::new(), Kahan::new());
+        total += 1.0;
+        let mut add = |ln_w: f64| {
+            let w = ln_w.exp();
+            total += w;
+            weighted += w * ln_w;
+            w > 1e-17 * total.sum()
+        };
+        let (mut k, mut ln_w) = (mode, 0.0);
+        while k < n {
+            ln_w += ((n - k) / (k + 1.0)).ln() + ln_odds;
+            k += 1.0;
+            if !add(ln_w) {
+                break;
             }
         }
-        h
+        let (mut k, mut ln_w) = (mode, 0.0);
+        while k > 0.0 {
+            ln_w += (k / (n - k + 1.0)).ln() - ln_odds;
+            k -= 1.0;
+            if !add(ln_w) {
+                break;
+            }
+        }
+        let total = total.sum();
+        total.ln() - weighted.sum() / total
+    }
+}
+
+impl Mgf for Binomial {
+    fn mgf(&self, t: f64) -> f64 {
+        self.cgf(t).exp()
+    }
+    fn cgf(&self, t: f64) -> f64 {
+        // K(t) = n ln(1 - p + p e^t)
+        (self.n as f64) * (self.p * t.exp_m1()).ln_1p()
+    }
+}
+
+impl CharacteristicFunction for Binomial {
+    fn cf(&self, t: f64) -> (f64, f64) {
+        // (1 − p + p e^{it})^n in polar form
+        let re = 1.0 - self.p + self.p * t.cos();
+        let im = self.p * t.sin();
+        let n = self.n as f64;
+        let m = re.hypot(im).powf(n);
+        let arg = n * im.atan2(re);
+        (m * arg.cos(), m * arg.sin())
+    }
+}
+
+impl HigherMoments for Binomial {
+    fn raw_moment(&self, n: u32) -> f64 {
+        super::summed_moment(self, n, 0.0)
+    }
+    fn central_moment(&self, n: u32) -> f64 {
+        super::summed_moment(self, n, self.mean())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[test]
+    fn pmf_at_large_n() {
+        // mpmath binomial(n, k) p^k (1-p)^(n-k)
+        let b = Binomial::new(10_000_000, 0.3).unwrap();
+        assert!((b.pmf(3_000_000) / 0.0002752963192402077 - 1.0).abs() < 1e-12);
+        assert!((b.pmf(2_995_000) / 7.146605354364074e-07 - 1.0).abs() < 1e-12);
+        assert_eq!(Binomial::new(4, 0.0).unwrap().pmf(0), 1.0);
+        assert_eq!(Binomial::new(4, 1.0).unwrap().ln_pmf(3), f64::NEG_INFINITY);
+    }
+
     #[test]
     fn pmf_values() {
         let b = Binomial::new(5, 0.4).unwrap();
@@ -141,6 +548,9 @@ mod tests {
         assert!((b.pmf(0) - 0.6f64.powi(5)).abs() < 1e-15);
         // pmf(5) = p^n
         assert!((b.pmf(5) - 0.4f64.powi(5)).abs() < 1e-15);
+        // mpmath binomial(30, 7) 0.2^7 0.8^23
+        let b = Binomial::new(30, 0.2).unwrap();
+        assert!((b.pmf(7) / 0.15382069897321638 - 1.0).abs() < 1e-15);
     }
     #[test]
     fn cdf_monotone() {
@@ -159,4 +569,142 @@ mod tests {
         assert!((b.skewness() - skew).abs() < 1e-12);
         assert!((b.kurtosis() - kurt).abs() < 1e-12);
     }
+    #[test]
+    fn mgf_values() {
+        let b = Binomial::new(4, 0.5).unwrap();
+        let t = 2f64.ln();
+        // (0.5 + 0.5 * 2)^4 = 1.5^4
+        assert!((b.mgf(t) - 1.5f64.powi(4)).abs() < 1e-12);
+        assert_eq!(b.cgf(0.0), 0.0);
+    }
+
+    #[test]
+    fn pgf_values() {
+        let b = Binomial::new(3, 0.2).unwrap();
+        assert!((b.pgf(0.0) - b.pmf(0)).abs() < 1e-15);
+        assert!((b.pgf(2.0) - 1.2f64.powi(3)).abs() < 1e-14);
+        assert!((b.ln_pgf(2.0) - 3.0 * 1.2f64.ln()).abs() < 1e-14);
+    }
+
+    #[test]
+    fn try_inv_cdf_bounds() {
+        let b = Binomial::new(7, 0.4).unwrap();
+        assert_eq!(b.try_inv_cdf(0.0), Ok(0));
+        assert_eq!(b.try_inv_cdf(1.0), Ok(7));
+        assert_eq!(b.try_inv_cdf(-1e-9), Err(DistError::DomainError));
+    }
+
+    #[test]
+    fn sf_upper_tail() {
+        // mpmath.betainc(k + 1, n - k, 0, p, regularized=True)
+        let b = Binomial::new(100, 0.01).unwrap();
+        assert!((b.sf(20) / 9.576655593219771e-22 - 1.0).abs() < 1e-10);
+        assert_eq!(b.sf(100), 0.0);
+        let b = Binomial::new(1000, 0.01).unwrap();
+        assert!((b.ln_sf(900) + 3830.537259214461).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sampling_matches_pmf_on_both_paths() {
+        // Inversion (np = 4), BTPE (np = 40), BTPE reflected through p > 1/2, and BTPE
+        // with npq = 210 so the squeeze path is exercised.
+        let mut rng = crate::rng::SplitMix64::seed_from_u64(3);
+        for (n, p) in [(20, 0.2), (100, 0.4), (100, 0.6), (1000, 0.3)] {
+            let b = Binomial::new(n, p).unwrap();
+            let draws = 200_000;
+            let mut counts = vec![0u32; n as usize + 1];
+            for _ in 0..draws {
+                counts[b.sample(&mut rng) as usize] += 1;
+            }
+            // Pearson chi-squared over cells with expected count >= 5.
+            let (mut chi2, mut cells) = (0.0, 0);
+            for (k, &c) in counts.iter().enumerate() {
+                let e = b.pmf(k as i64) * draws as f64;
+                if e >= 5.0 {
+                    chi2 += (c as f64 - e).powi(2) / e;
+                    cells += 1;
+                }
+            }
+            // Mean + 5 sd of chi-squared(cells - 1).
+            let df = (cells - 1) as f64;
+            assert!(
+                chi2 < df + 5.0 * (2.0 * df).sqrt(),
+                "n={n} p={p}: {chi2} on {df}"
+            );
+        }
+    }
+
+    #[test]
+    fn sampler_replays_sample() {
+        for b in [
+            Binomial::new(20, 0.2).unwrap(),
+            Binomial::new(500, 0.9).unwrap(),
+        ] {
+            let mut a = crate::rng::SplitMix64::seed_from_u64(4);
+            let mut r = a.clone();
+            let mut s = b.sampler();
+            assert!((0..100).all(|_| s.next(&mut a) == b.sample(&mut r)));
+        }
+    }
+
+    #[test]
+    fn huge_n_samples_in_constant_time() {
+        let b = Binomial::new(1_000_000_000_000, 0.3).unwrap();
+        let mut rng = crate::rng::SplitMix64::seed_from_u64(5);
+        let xs: Vec<f64> = (0..10_000).map(|_| b.sample(&mut rng) as f64).collect();
+        let m = xs.iter().sum::<f64>() / xs.len() as f64;
+        let sd = (b.variance() / xs.len() as f64).sqrt();
+        assert!((m - b.mean()).abs() < 5.0 * sd, "{m}");
+        assert_eq!(
+            Binomial::new(1 << 40, 1.0).unwrap().sample(&mut rng),
+            1 << 40
+        );
+        assert_eq!(Binomial::new(0, 0.5).unwrap().sample(&mut rng), 0);
+    }
+
+    #[test]
+    fn cdf_and_quantile_at_scale() {
+        // Exact sums of the pmf in 40-digit arithmetic (mpmath).
+        for (n, p, k, want) in [
+            (10, 0.3, 3, 0.6496107184),
+            (1_000_000, 0.3, 300_000, 0.50049331906667927),
+            (1_000_000, 0.3, 299_000, 0.014568219219937685),
+            (1_000_000, 0.3, 301_500, 0.99946784275514584),
+            (10_000_000, 0.001, 10_100, 0.84266922001083967),
+            (1_000_000_000, 0.5, 499_990_000, 0.26355495737733487),
+        ] {
+            let b = Binomial::new(n, p).unwrap();
+            let got = b.cdf(k);
+            assert!(
+                (got / want - 1.0).abs() < 1e-9,
+                "n={n} k={k}: {got} vs {want}"
+            );
+            assert!((b.sf(k) - (1.0 - want)).abs() < 1e-9, "n={n} k={k}");
+            let q = b.inv_cdf(want - 1e-9);
+            assert_eq!(q, k, "n={n}");
+        }
+        let b = Binomial::new(20, 0.0).unwrap();
+        assert_eq!((b.cdf(0), b.inv_cdf(0.5)), (1.0, 0));
+        let b = Binomial::new(20, 1.0).unwrap();
+        assert_eq!((b.cdf(19), b.cdf(20), b.inv_cdf(0.5)), (0.0, 1.0, 20));
+    }
+
+    #[test]
+    fn entropy_exact_and_large_n() {
+        let b = Binomial::new(10, 0.3).unwrap();
+        let exact: f64 = (0..=10).map(|k| -b.pmf(k) * b.pmf(k).ln()).sum();
+        assert!((b.entropy() - exact).abs() < 1e-14);
+        // Large n approaches ½ ln(2πe npq) (+ O(1/npq)), where (1−p)^n underflows.
+        let b = Binomial::new(1_000_000_000, 0.3).unwrap();
+        assert!((b.entropy() - 11.000247577545544).abs() < 1e-7);
+        assert_eq!(Binomial::new(5, 1.0).unwrap().entropy(), 0.0);
+    }
+
+    #[test]
+    fn cdf_keeps_tiny_p() {
+        // Rounding 1 − p would move this by about 1e-8.
+        // mpmath: betainc(n − 9, 10, 0, 1 − p, regularized=True)
+        let b = Binomial::new(1_000_000_000, 1e-8).unwrap();
+        assert!((b.cdf(9) / 0.45792971384630203 - 1.0).abs() < 1e-12);
+    }
 }
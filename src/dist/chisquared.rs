@@ -1,10 +1,12 @@
 use super::gamma::Gamma;
-use crate::dist::{Continuous, DistError, Distribution, Moments};
+use crate::dist::{
+    CharacteristicFunction, Continuous, DistError, Distribution, HigherMoments, Mgf, Moments,
+    Param, check_positive,
+};
 use crate::rng::RngCore;
-use std::cmp::Ordering;
 
 /// Chi-squared with v degrees of freedom: equivalent to Gamma(k=v/2, theta=2).
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ChiSquared {
     v: f64,
     gamma: Gamma,
@@ -12,9 +14,7 @@ pub struct ChiSquared {
 
 impl ChiSquared {
     pub fn new(v: f64) -> Result<Self, DistError> {
-        if !v.is_finite() || v.partial_cmp(&0.0) != Some(Ordering::Greater) {
-            return Err(DistError::InvalidParameter);
-        }
+        check_positive("v", v)?;
         let gamma = Gamma::new(v / 2.0, 2.0)?;
         Ok(Self { v, gamma })
     }
@@ -24,6 +24,12 @@ impl ChiSquared {
     }
 }
 
+impl std::fmt::Display for ChiSquared {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ChiSquared(v={})", Param(self.v))
+    }
+}
+
 impl Distribution for ChiSquared {
     type Value = f64;
     fn cdf(&self, x: f64) -> f64 {
@@ -41,8 +47,14 @@ impl Continuous for ChiSquared {
     fn pdf(&self, x: f64) -> f64 {
         self.gamma.pdf(x)
     }
-    fn inv_cdf(&self, p: f64) -> f64 {
-        self.gamma.inv_cdf(p)
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+    fn try_inv_cdf(&self, p: f64) -> Result<f64, DistError> {
+        self.gamma.try_inv_cdf(p)
+    }
+    fn try_isf(&self, q: f64) -> Result<f64, DistError> {
+        self.gamma.try_isf(q)
     }
 }
 
@@ -64,6 +76,30 @@ impl Moments for ChiSquared {
     }
 }
 
+impl Mgf for ChiSquared {
+    fn mgf(&self, t: f64) -> f64 {
+        self.gamma.mgf(t)
+    }
+    fn cgf(&self, t: f64) -> f64 {
+        self.gamma.cgf(t)
+    }
+}
+
+impl CharacteristicFunction for ChiSquared {
+    fn cf(&self, t: f64) -> (f64, f64) {
+        self.gamma.cf(t)
+    }
+}
+
+impl HigherMoments for ChiSquared {
+    fn raw_moment(&self, n: u32) -> f64 {
+        self.gamma.raw_moment(n)
+    }
+    fn central_moment(&self, n: u32) -> f64 {
+        self.gamma.central_moment(n)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,4 +115,11 @@ mod tests {
         assert!((x2.skewness() - 2f64.sqrt()).abs() < 1e-12);
         assert!((x2.kurtosis() - 3.0).abs() < 1e-12);
     }
+    #[test]
+    fn mgf() {
+        // (1 - 2t)^{-v/2}
+        let x2 = ChiSquared::new(4.0).unwrap();
+        assert!((x2.mgf(0.25) - 4.0).abs() < 1e-12);
+        assert_eq!(x2.mgf(0.5), f64::INFINITY);
+    }
 }
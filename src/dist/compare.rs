@@ -0,0 +1,188 @@
+//! Approximate comparison of distributions, by parameters or by CDF.
+
+use crate::dist::any::{AnyContinuous, AnyDiscrete, AnyDistribution};
+use crate::dist::{
+    Continuous, Discrete, bernoulli::Bernoulli, beta::Beta, binomial::Binomial,
+    chisquared::ChiSquared, exponential::Exponential, gamma::Gamma, geometric::Geometric,
+    lognormal::LogNormal, negative_binomial::NegativeBinomial, normal::Normal, poisson::Poisson,
+    uniform::Uniform,
+};
+
+/// Parameter-wise approximate equality.
+///
+/// Each pair of real parameters must satisfy `|a − b| <= tol · max(1, |a|, |b|)`: an
+/// absolute tolerance near zero and a relative one for large values. Integer parameters
+/// must match exactly.
+pub trait ApproxEq {
+    fn approx_eq(&self, other: &Self, tol: f64) -> bool;
+}
+
+#[inline]
+fn close(a: f64, b: f64, tol: f64) -> bool {
+    (a - b).abs() <= tol * a.abs().max(b.abs()).max(1.0)
+}
+
+macro_rules! approx_by_getters {
+    ($($ty:ident: $($getter:ident),+;)*) => {$(
+        impl ApproxEq for $ty {
+            fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+                $(close(self.$getter(), other.$getter(), tol))&&+
+            }
+        }
+    )*};
+}
+
+approx_by_getters! {
+    Bernoulli: p;
+    Beta: a, b;
+    ChiSquared: dof;
+    Exponential: lambda;
+    Gamma: shape, scale;
+    Geometric: p;
+    LogNormal: mu, sigma;
+    NegativeBinomial: r, p;
+    Normal: mean_param, sigma;
+    Poisson: lambda;
+    Uniform: a, b;
+}
+
+impl ApproxEq for Binomial {
+    fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+        self.n() == other.n() && close(self.p(), other.p(), tol)
+    }
+}
+
+macro_rules! approx_any {
+    ($name:ident { $($var:ident),* $(,)? }) => {
+        impl ApproxEq for $name {
+            /// Different variants are never approximately equal.
+            fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+                match (self, other) {
+                    $(($name::$var(a), $name::$var(b)) => a.approx_eq(b, tol),)*
+                    _ => false,
+                }
+            }
+        }
+    };
+}
+
+approx_any!(AnyContinuous {
+    Beta,
+    ChiSquared,
+    Exponential,
+    Gamma,
+    LogNormal,
+    Normal,
+    Uniform
+});
+approx_any!(AnyDiscrete {
+    Bernoulli,
+    Binomial,
+    Geometric,
+    NegativeBinomial,
+    Poisson
+});
+
+impl ApproxEq for AnyDistribution {
+    fn approx_eq(&self, other: &Self, tol: f64) -> bool {
+        match (self, other) {
+            (AnyDistribution::Continuous(a), AnyDistribution::Continuous(b)) => a.approx_eq(b, tol),
+            (AnyDistribution::Discrete(a), AnyDistribution::Discrete(b)) => a.approx_eq(b, tol),
+            _ => false,
+        }
+    }
+}
+
+/// Approximate sup_x |F_a(x) − F_b(x)| (the Kolmogorov distance).
+///
+/// Evaluates both CDFs at the quantiles of both distributions on a grid of 999 interior
+/// probabilities; differences confined to the outer 1e-3 of either tail are missed.
+pub fn max_cdf_diff<A, B>(a: &A, b: &B) -> f64
+where
+    A: Continuous + ?Sized,
+    B: Continuous + ?Sized,
+{
+    const N: usize = 1000;
+    let mut max = 0.0_f64;
+    for i in 1..N {
+        let u = i as f64 / N as f64;
+        for x in [a.inv_cdf(u), b.inv_cdf(u)] {
+            max = max.max((a.cdf(x) - b.cdf(x)).abs());
+        }
+    }
+    max
+}
+
+/// sup_k |F_a(k) − F_b(k)| over the integers between the 1e-12 and 1 − 1e-12 quantiles
+/// of either distribution.
+///
+/// Ranges wider than a million integers (a far-tailed Geometric, a Poisson with a huge
+/// mean) are not walked: both CDFs are then evaluated at the quantiles of both
+/// distributions on a grid of that many probabilities, as in [`max_cdf_diff`].
+pub fn max_cdf_diff_discrete<A, B>(a: &A, b: &B) -> f64
+where
+    A: Discrete + ?Sized,
+    B: Discrete + ?Sized,
+{
+    const MAX_POINTS: i64 = 1_000_000;
+    let diff = |k: i64| (a.cdf(k) - b.cdf(k)).abs();
+    let lo = a.inv_cdf(1e-12).min(b.inv_cdf(1e-12));
+    let hi = a.inv_cdf(1.0 - 1e-12).max(b.inv_cdf(1.0 - 1e-12));
+    if hi.saturating_sub(lo) < MAX_POINTS {
+        return (lo..=hi).map(diff).fold(0.0, f64::max);
+    }
+    let mut max = 0.0_f64;
+    for i in 1..MAX_POINTS {
+        let u = i as f64 / MAX_POINTS as f64;
+        for k in [a.inv_cdf(u), b.inv_cdf(u)] {
+            max = max.max(diff(k));
+        }
+    }
+    max
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parameter_tolerance() {
+        let a = Normal::new(1.0, 2.0).unwrap();
+        let b = Normal::new(1.0 + 1e-10, 2.0).unwrap();
+        assert_ne!(a, b);
+        assert!(a.approx_eq(&b, 1e-9));
+        assert!(!a.approx_eq(&b, 1e-11));
+        let n1 = Binomial::new(10, 0.5).unwrap();
+        let n2 = Binomial::new(11, 0.5).unwrap();
+        assert!(!n1.approx_eq(&n2, 1.0));
+        let g: AnyContinuous = Gamma::new(2.0, 1.0).unwrap().into();
+        let c: AnyContinuous = ChiSquared::new(4.0).unwrap().into();
+        assert!(!g.approx_eq(&c, 1.0));
+    }
+
+    #[test]
+    fn cdf_distance() {
+        // Gamma(v/2, 2) is the chi-squared distribution.
+        let g = Gamma::new(1.5, 2.0).unwrap();
+        let c = ChiSquared::new(3.0).unwrap();
+        assert!(max_cdf_diff(&g, &c) < 1e-12);
+        let n0 = Normal::new(0.0, 1.0).unwrap();
+        let n1 = Normal::new(0.1, 1.0).unwrap();
+        // 2Φ(0.05) − 1
+        assert!((max_cdf_diff(&n0, &n1) - 0.0398776).abs() < 1e-4);
+        let p = Poisson::new(3.0).unwrap();
+        let b = Binomial::new(3000, 0.001).unwrap();
+        assert!(max_cdf_diff_discrete(&p, &b) < 1e-3);
+    }
+
+    #[test]
+    fn discrete_distance_over_a_wide_support() {
+        // The 1 − 1e-12 quantile of Geometric(1e-6) is near 2.8e7, so this goes through
+        // the quantile grid instead of walking the support.
+        let a = Geometric::new(1e-6).unwrap();
+        let b = Geometric::new(1.1e-6).unwrap();
+        let d = max_cdf_diff_discrete(&a, &b);
+        // sup over t of e^{−t} − e^{−1.1t}, at t = 10 ln 1.1: 0.0350493...
+        assert!((d - 0.0350493).abs() < 1e-5, "{d}");
+    }
+}
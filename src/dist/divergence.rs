@@ -0,0 +1,364 @@
+//! Distances between two distributions on the same support.
+//!
+//! - [`hellinger`]: H = sqrt(1 − BC), where BC = ∫ sqrt(f g) is the Bhattacharyya coefficient; in `[0, 1]`.
+//! - [`total_variation`]: ½ ∫ |f − g|; in `[0, 1]`.
+//! - [`jensen_shannon`]: ½ KL(f‖m) + ½ KL(g‖m) with m = (f + g)/2, in nats; in `[0, ln 2]`.
+//! - [`wasserstein_1`]: ∫ |F − G| = ∫₀¹ |F⁻¹(u) − G⁻¹(u)| du, in units of the variable;
+//!   also against a sample ([`wasserstein_1_sample`]) and between two samples
+//!   ([`wasserstein_1_samples`]).
+//!
+//! Closed forms are used for matching pairs of known families (e.g. two normals); other
+//! pairs are integrated adaptively in probability space, or summed for the `_discrete`
+//! variants.
+
+use crate::dist::numeric::{adaptive_quantile_expectation, probability_edges};
+use crate::dist::{
+    Continuous, Discrete, exponential::Exponential, gamma::Gamma, normal::Normal, poisson::Poisson,
+};
+use crate::num::ln_gamma;
+use crate::num::quadrature::integrate_adaptive;
+use crate::num::sum::Kahan;
+use std::any::Any;
+
+/// Absolute tolerance of the adaptive quadrature.
+const TOL: f64 = 1e-10;
+
+/// Both arguments as `&T`, when they are.
+fn pair<'a, T: Any, A: Any, B: Any>(a: &'a A, b: &'a B) -> Option<(&'a T, &'a T)> {
+    Some((
+        (a as &dyn Any).downcast_ref::<T>()?,
+        (b as &dyn Any).downcast_ref::<T>()?,
+    ))
+}
+
+fn bhattacharyya_closed_form<A: Any, B: Any>(a: &A, b: &B) -> Option<f64> {
+    if let Some((x, y)) = pair::<Normal, _, _>(a, b) {
+        let (s1, s2) = (x.sigma() * x.sigma(), y.sigma() * y.sigma());
+        let dm = x.mean_param() - y.mean_param();
+        return Some(
+            (2.0 * x.sigma() * y.sigma() / (s1 + s2)).sqrt() * (-dm * dm / (4.0 * (s1 + s2))).exp(),
+        );
+    }
+    if let Some((x, y)) = pair::<Exponential, _, _>(a, b) {
+        let (l1, l2) = (x.lambda(), y.lambda());
+        return Some(2.0 * (l1 * l2).sqrt() / (l1 + l2));
+    }
+    if let Some((x, y)) = pair::<Gamma, _, _>(a, b) {
+        let (k1, k2) = (x.shape(), y.shape());
+        let (b1, b2) = (1.0 / x.scale(), 1.0 / y.scale());
+        let k = 0.5 * (k1 + k2);
+        let ln_bc = 0.5 * (k1 * b1.ln() + k2 * b2.ln()) + ln_gamma(k)
+            - 0.5 * (ln_gamma(k1) + ln_gamma(k2))
+            - k * (0.5 * (b1 + b2)).ln();
+        return Some(ln_bc.exp());
+    }
+    if let Some((x, y)) = pair::<Poisson, _, _>(a, b) {
+        let d = x.lambda().sqrt() - y.lambda().sqrt();
+        return Some((-0.5 * d * d).exp());
+    }
+    None
+}
+
+#[inline]
+fn hellinger_from_bc(bc: f64) -> f64 {
+    (1.0 - bc.min(1.0)).max(0.0).sqrt()
+}
+
+/// Hellinger distance between two continuous distributions.
+pub fn hellinger<A, B>(a: &A, b: &B) -> f64
+where
+    A: Continuous + Any,
+    B: Continuous + Any,
+{
+    let bc = bhattacharyya_closed_form(a, b).unwrap_or_else(|| {
+        // BC = E_a[sqrt(g/f)]
+        adaptive_quantile_expectation(a, |x| ratio(b.pdf(x), a.pdf(x)).sqrt(), TOL)
+    });
+    hellinger_from_bc(bc)
+}
+
+/// Total variation distance between two continuous distributions.
+pub fn total_variation<A, B>(a: &A, b: &B) -> f64
+where
+    A: Continuous + Any,
+    B: Continuous + Any,
+{
+    if let Some((x, y)) = pair::<Exponential, _, _>(a, b) {
+        let (l1, l2) = (x.lambda(), y.lambda());
+        if l1 == l2 {
+            return 0.0;
+        }
+        // The densities cross once, at x*; TV = |F_1(x*) − F_2(x*)|.
+        let xs = (l1 / l2).ln() / (l1 - l2);
+        return ((-l2 * xs).exp() - (-l1 * xs).exp()).abs();
+    }
+    // TV = 1 − ∫ min(f, g) = 1 − E_a[min(1, g/f)]
+    let overlap = adaptive_quantile_expectation(a, |x| ratio(b.pdf(x), a.pdf(x)).min(1.0), TOL);
+    (1.0 - overlap).clamp(0.0, 1.0)
+}
+
+/// Jensen–Shannon divergence (nats) between two continuous distributions.
+pub fn jensen_shannon<A, B>(a: &A, b: &B) -> f64
+where
+    A: Continuous + ?Sized,
+    B: Continuous + ?Sized,
+{
+    // ½ E_a[ln(2f/(f+g))] + ½ E_b[ln(2g/(f+g))]
+    let half_kl = |p: f64, q: f64| {
+        if p > 0.0 {
+            (2.0 * p / (p + q)).ln()
+        } else {
+            0.0
+        }
+    };
+    let ka = adaptive_quantile_expectation(a, |x| half_kl(a.pdf(x), b.pdf(x)), TOL);
+    let kb = adaptive_quantile_expectation(b, |x| half_kl(b.pdf(x), a.pdf(x)), TOL);
+    (0.5 * (ka + kb)).clamp(0.0, std::f64::consts::LN_2)
+}
+
+/// `num / den`, treating `0/0` as 0 and `x/0` as +inf.
+#[inline]
+fn ratio(num: f64, den: f64) -> f64 {
+    if den > 0.0 {
+        num / den
+    } else if num > 0.0 {
+        f64::INFINITY
+    } else {
+        0.0
+    }
+}
+
+/// Sums `term(pa(k), pb(k))` over the union of both supports (up to 1 − 1e-15 of mass).
+fn sum_pair<A, B, F>(a: &A, b: &B, mut term: F) -> f64
+where
+    A: Discrete + ?Sized,
+    B: Discrete + ?Sized,
+    F: FnMut(f64, f64) -> f64,
+{
+    let lo = a.inv_cdf(0.0).min(b.inv_cdf(0.0));
+    let hi = a.inv_cdf(1.0 - 1e-15).max(b.inv_cdf(1.0 - 1e-15));
+    (lo..=hi)
+        .map(|k| term(a.pmf(k), b.pmf(k)))
+        .collect::<Kahan>()
+        .sum()
+}
+
+/// Hellinger distance between two discrete distributions.
+pub fn hellinger_discrete<A, B>(a: &A, b: &B) -> f64
+where
+    A: Discrete + Any,
+    B: Discrete + Any,
+{
+    let bc =
+        bhattacharyya_closed_form(a, b).unwrap_or_else(|| sum_pair(a, b, |p, q| (p * q).sqrt()));
+    hellinger_from_bc(bc)
+}
+
+/// Total variation distance between two discrete distributions.
+pub fn total_variation_discrete<A, B>(a: &A, b: &B) -> f64
+where
+    A: Discrete + ?Sized,
+    B: Discrete + ?Sized,
+{
+    (0.5 * sum_pair(a, b, |p, q| (p - q).abs())).clamp(0.0, 1.0)
+}
+
+/// Jensen–Shannon divergence (nats) between two discrete distributions.
+pub fn jensen_shannon_discrete<A, B>(a: &A, b: &B) -> f64
+where
+    A: Discrete + ?Sized,
+    B: Discrete + ?Sized,
+{
+    let xlog = |p: f64, m: f64| if p > 0.0 { p * (p / m).ln() } else { 0.0 };
+    let js = sum_pair(a, b, |p, q| {
+        let m = 0.5 * (p + q);
+        0.5 * (xlog(p, m) + xlog(q, m))
+    });
+    js.clamp(0.0, std::f64::consts::LN_2)
+}
+
+/// Wasserstein-1 (earth mover's) distance between two continuous distributions.
+pub fn wasserstein_1<A, B>(a: &A, b: &B) -> f64
+where
+    A: Continuous + ?Sized,
+    B: Continuous + ?Sized,
+{
+    integrate_adaptive(
+        |u| (a.inv_cdf(u) - b.inv_cdf(u)).abs(),
+        &probability_edges(),
+        TOL,
+    )
+}
+
+/// Wasserstein-1 distance between two discrete distributions: Σ |F(k) − G(k)|.
+pub fn wasserstein_1_discrete<A, B>(a: &A, b: &B) -> f64
+where
+    A: Discrete + ?Sized,
+    B: Discrete + ?Sized,
+{
+    let lo = a.inv_cdf(0.0).min(b.inv_cdf(0.0));
+    let hi = a.inv_cdf(1.0 - 1e-15).max(b.inv_cdf(1.0 - 1e-15));
+    (lo..hi).map(|k| (a.cdf(k) - b.cdf(k)).abs()).sum()
+}
+
+fn sorted(data: &[f64]) -> Vec<f64> {
+    let mut v = data.to_vec();
+    v.sort_unstable_by(f64::total_cmp);
+    v
+}
+
+/// Wasserstein-1 distance between the empirical distribution of `data` and `d`.
+///
+/// Returns `NaN` if `data` is empty or contains a NaN.
+pub fn wasserstein_1_sample<D: Continuous + ?Sized>(data: &[f64], d: &D) -> f64 {
+    if data.is_empty() || data.iter().any(|x| x.is_nan()) {
+        return f64::NAN;
+    }
+    let xs = sorted(data);
+    let n = xs.len() as f64;
+    // The empirical quantile is a step function: break panels at its jumps (i/n) and
+    // where it crosses the model quantile (F(x_i)).
+    let mut edges = probability_edges();
+    edges.extend((1..xs.len()).map(|i| i as f64 / n));
+    edges.extend(
+        xs.iter()
+            .map(|&x| d.cdf(x))
+            .filter(|&u| u > 1e-15 && u < 1.0 - 1e-15),
+    );
+    edges.sort_unstable_by(f64::total_cmp);
+    edges.dedup();
+    let step = |u: f64| xs[((u * n) as usize).min(xs.len() - 1)];
+    integrate_adaptive(|u| (step(u) - d.inv_cdf(u)).abs(), &edges, TOL)
+}
+
+/// Wasserstein-1 distance between the empirical distributions of two samples.
+///
+/// Returns `NaN` if either sample is empty or contains a NaN (which has no place in the
+/// merged order the sweep walks).
+pub fn wasserstein_1_samples(a: &[f64], b: &[f64]) -> f64 {
+    if a.is_empty() || b.is_empty() || a.iter().chain(b).any(|x| x.is_nan()) {
+        return f64::NAN;
+    }
+    let (xa, xb) = (sorted(a), sorted(b));
+    let (na, nb) = (xa.len() as f64, xb.len() as f64);
+    // Sweep the merged order statistics, integrating |F_a − F_b| between them.
+    let (mut i, mut j, mut total) = (0, 0, 0.0);
+    let mut prev = xa[0].min(xb[0]);
+    while i < xa.len() || j < xb.len() {
+        let x = match (xa.get(i), xb.get(j)) {
+            (Some(&p), Some(&q)) => p.min(q),
+            (Some(&p), None) => p,
+            (None, Some(&q)) => q,
+            (None, None) => unreachable!(),
+        };
+        total += (i as f64 / na - j as f64 / nb).abs() * (x - prev);
+        while xa.get(i) == Some(&x) {
+            i += 1;
+        }
+        while xb.get(j) == Some(&x) {
+            j += 1;
+        }
+        prev = x;
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dist::{binomial::Binomial, extension::Extension, lognormal::LogNormal};
+
+    /// Hides the concrete type so the numerical paths are exercised.
+    struct Opaque<D>(D);
+    impl<D: Continuous> crate::dist::extension::ExtendedDistribution for Opaque<D> {
+        type Value = f64;
+        fn cdf(&self, x: f64) -> f64 {
+            self.0.cdf(x)
+        }
+        fn sample<R: crate::rng::RngCore>(&self, rng: &mut R) -> f64 {
+            self.0.sample(rng)
+        }
+        fn in_support(&self, x: f64) -> bool {
+            self.0.in_support(x)
+        }
+    }
+    impl<D: Continuous> crate::dist::extension::ExtendedContinuous for Opaque<D> {
+        fn pdf(&self, x: f64) -> f64 {
+            self.0.pdf(x)
+        }
+        fn inv_cdf(&self, p: f64) -> f64 {
+            self.0.inv_cdf(p)
+        }
+    }
+
+    #[test]
+    fn closed_forms_match_quadrature() {
+        let g1 = Gamma::new(2.0, 1.0).unwrap();
+        let g2 = Gamma::new(3.5, 0.7).unwrap();
+        let closed = hellinger(&g1, &g2);
+        let numeric = hellinger(&Extension(Opaque(g1)), &Extension(Opaque(g2)));
+        assert!((closed - numeric).abs() < 1e-8, "{closed} vs {numeric}");
+
+        let e1 = Exponential::new(1.0).unwrap();
+        let e2 = Exponential::new(3.0).unwrap();
+        let closed = total_variation(&e1, &e2);
+        let numeric = total_variation(&Extension(Opaque(e1)), &Extension(Opaque(e2)));
+        assert!((closed - numeric).abs() < 1e-8, "{closed} vs {numeric}");
+    }
+
+    #[test]
+    fn bounds_and_symmetry() {
+        let a = Normal::new(0.0, 1.0).unwrap();
+        let b = LogNormal::new(0.0, 0.5).unwrap();
+        assert!(hellinger(&a, &a) < 1e-12);
+        let (jab, jba) = (jensen_shannon(&a, &b), jensen_shannon(&b, &a));
+        assert!((jab - jba).abs() < 1e-8);
+        assert!(jab > 0.0 && jab < std::f64::consts::LN_2);
+        let far = Normal::new(100.0, 1.0).unwrap();
+        assert!((total_variation(&a, &far) - 1.0).abs() < 1e-9);
+        assert!((jensen_shannon(&a, &far) - std::f64::consts::LN_2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn discrete_distances() {
+        let p = Poisson::new(5.0).unwrap();
+        let b = Binomial::new(50, 0.1).unwrap();
+        let tv = total_variation_discrete(&p, &b);
+        assert!(tv > 0.0 && tv < 0.05);
+        let h_closed = hellinger_discrete(&p, &Poisson::new(7.0).unwrap());
+        let bc: f64 = (0..200)
+            .map(|k| (p.pmf(k) * Poisson::new(7.0).unwrap().pmf(k)).sqrt())
+            .sum();
+        assert!((h_closed - (1.0 - bc).sqrt()).abs() < 1e-10);
+        assert!(jensen_shannon_discrete(&p, &p) < 1e-15);
+    }
+
+    #[test]
+    fn wasserstein_shift_and_samples() {
+        // Location families: W1 is the shift.
+        let a = Normal::new(0.0, 2.0).unwrap();
+        let b = Normal::new(1.5, 2.0).unwrap();
+        assert!((wasserstein_1(&a, &b) - 1.5).abs() < 1e-8);
+        assert!(
+            (wasserstein_1_discrete(&Poisson::new(3.0).unwrap(), &Poisson::new(3.0).unwrap()))
+                .abs()
+                < 1e-15
+        );
+
+        assert_eq!(
+            wasserstein_1_samples(&[0.0, 1.0, 3.0], &[3.0, 2.0, 0.0]),
+            1.0 / 3.0
+        );
+        assert!(wasserstein_1_samples(&[], &[1.0]).is_nan());
+        assert!(wasserstein_1_samples(&[0.0, f64::NAN], &[1.0]).is_nan());
+        assert!(wasserstein_1_samples(&[0.0], &[f64::NAN, 1.0]).is_nan());
+        assert!(wasserstein_1_sample(&[f64::NAN], &a).is_nan());
+
+        // A single point at the median: E|X − m| = σ sqrt(2/π).
+        let w = wasserstein_1_sample(&[0.0], &a);
+        assert!(
+            (w - 2.0 * (2.0 / std::f64::consts::PI).sqrt()).abs() < 1e-8,
+            "{w}"
+        );
+    }
+}
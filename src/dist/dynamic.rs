@@ -0,0 +1,256 @@
+//! Object-safe companions of [`Continuous`] and [`Discrete`].
+//!
+//! The generic `sample<R: RngCore>` keeps the core traits from being used as trait objects.
+//! [`ContinuousDyn`] and [`DiscreteDyn`] take `&mut dyn RngCore` instead and are implemented
+//! for every distribution. The trait objects in turn implement the core traits, so a
+//! `Box<dyn ContinuousDyn>` works with any generic helper:
+//!
+//! ```
+//! use probability_rs::dist::{ContinuousDyn, exponential::Exponential, normal::Normal};
+//! use probability_rs::{Continuous, Distribution, rng::SplitMix64};
+//!
+//! let models: Vec<Box<dyn ContinuousDyn>> = vec![
+//!     Box::new(Normal::standard()),
+//!     Box::new(Exponential::new(2.0).unwrap()),
+//! ];
+//! let mut rng = SplitMix64::seed_from_u64(3);
+//! for m in &models {
+//!     let _x = m.sample(&mut rng);
+//!     assert!(m.inv_cdf(0.5).is_finite());
+//! }
+//! ```
+
+use crate::dist::{Continuous, Discrete, DistError, Distribution};
+use crate::rng::RngCore;
+
+/// Object-safe view of a [`Continuous`] distribution.
+pub trait ContinuousDyn {
+    fn cdf_dyn(&self, x: f64) -> f64;
+    fn pdf_dyn(&self, x: f64) -> f64;
+    fn support_dyn(&self) -> (f64, f64);
+    fn try_inv_cdf_dyn(&self, p: f64) -> Result<f64, DistError>;
+    fn try_isf_dyn(&self, q: f64) -> Result<f64, DistError>;
+    fn in_support_dyn(&self, x: f64) -> bool;
+    fn sample_dyn(&self, rng: &mut dyn RngCore) -> f64;
+}
+
+/// Object-safe view of a [`Discrete`] distribution.
+pub trait DiscreteDyn {
+    fn cdf_dyn(&self, x: i64) -> f64;
+    fn pmf_dyn(&self, x: i64) -> f64;
+    fn ln_pmf_dyn(&self, x: i64) -> f64;
+    fn try_inv_cdf_dyn(&self, p: f64) -> Result<i64, DistError>;
+    fn try_isf_dyn(&self, q: f64) -> Result<i64, DistError>;
+    fn sf_dyn(&self, k: i64) -> f64;
+    fn ln_sf_dyn(&self, k: i64) -> f64;
+    fn pgf_dyn(&self, s: f64) -> f64;
+    fn in_support_dyn(&self, x: i64) -> bool;
+    fn sample_dyn(&self, rng: &mut dyn RngCore) -> i64;
+}
+
+impl<T: Continuous> ContinuousDyn for T {
+    #[inline]
+    fn cdf_dyn(&self, x: f64) -> f64 {
+        self.cdf(x)
+    }
+    #[inline]
+    fn pdf_dyn(&self, x: f64) -> f64 {
+        self.pdf(x)
+    }
+    #[inline]
+    fn support_dyn(&self) -> (f64, f64) {
+        self.support()
+    }
+    #[inline]
+    fn try_inv_cdf_dyn(&self, p: f64) -> Result<f64, DistError> {
+        self.try_inv_cdf(p)
+    }
+    #[inline]
+    fn try_isf_dyn(&self, q: f64) -> Result<f64, DistError> {
+        self.try_isf(q)
+    }
+    #[inline]
+    fn in_support_dyn(&self, x: f64) -> bool {
+        self.in_support(x)
+    }
+    #[inline]
+    fn sample_dyn(&self, mut rng: &mut dyn RngCore) -> f64 {
+        self.sample(&mut rng)
+    }
+}
+
+impl<T: Discrete> DiscreteDyn for T {
+    #[inline]
+    fn cdf_dyn(&self, x: i64) -> f64 {
+        self.cdf(x)
+    }
+    #[inline]
+    fn pmf_dyn(&self, x: i64) -> f64 {
+        self.pmf(x)
+    }
+    #[inline]
+    fn ln_pmf_dyn(&self, x: i64) -> f64 {
+        self.ln_pmf(x)
+    }
+    #[inline]
+    fn try_inv_cdf_dyn(&self, p: f64) -> Result<i64, DistError> {
+        self.try_inv_cdf(p)
+    }
+    #[inline]
+    fn try_isf_dyn(&self, q: f64) -> Result<i64, DistError> {
+        self.try_isf(q)
+    }
+    #[inline]
+    fn sf_dyn(&self, k: i64) -> f64 {
+        self.sf(k)
+    }
+    #[inline]
+    fn ln_sf_dyn(&self, k: i64) -> f64 {
+        self.ln_sf(k)
+    }
+    #[inline]
+    fn pgf_dyn(&self, s: f64) -> f64 {
+        self.pgf(s)
+    }
+    #[inline]
+    fn in_support_dyn(&self, x: i64) -> bool {
+        self.in_support(x)
+    }
+    #[inline]
+    fn sample_dyn(&self, mut rng: &mut dyn RngCore) -> i64 {
+        self.sample(&mut rng)
+    }
+}
+
+// The trait objects implement the core traits, with and without `Send + Sync`.
+macro_rules! impl_core_for_dyn {
+    ($($obj:ty),*) => {$(
+        impl super::private::Sealed for $obj {}
+
+        impl Distribution for $obj {
+            type Value = f64;
+            #[inline]
+            fn cdf(&self, x: f64) -> f64 {
+                self.cdf_dyn(x)
+            }
+            #[inline]
+            fn sample<R: RngCore>(&self, rng: &mut R) -> f64 {
+                self.sample_dyn(rng)
+            }
+            #[inline]
+            fn in_support(&self, x: f64) -> bool {
+                self.in_support_dyn(x)
+            }
+        }
+
+        impl Continuous for $obj {
+            #[inline]
+            fn pdf(&self, x: f64) -> f64 {
+                self.pdf_dyn(x)
+            }
+            #[inline]
+            fn support(&self) -> (f64, f64) {
+                self.support_dyn()
+            }
+            #[inline]
+            fn try_inv_cdf(&self, p: f64) -> Result<f64, DistError> {
+                self.try_inv_cdf_dyn(p)
+            }
+            #[inline]
+            fn try_isf(&self, q: f64) -> Result<f64, DistError> {
+                self.try_isf_dyn(q)
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_core_for_dyn_discrete {
+    ($($obj:ty),*) => {$(
+        impl super::private::Sealed for $obj {}
+
+        impl Distribution for $obj {
+            type Value = i64;
+            #[inline]
+            fn cdf(&self, x: i64) -> f64 {
+                self.cdf_dyn(x)
+            }
+            #[inline]
+            fn sample<R: RngCore>(&self, rng: &mut R) -> i64 {
+                self.sample_dyn(rng)
+            }
+            #[inline]
+            fn in_support(&self, x: i64) -> bool {
+                self.in_support_dyn(x)
+            }
+        }
+
+        impl Discrete for $obj {
+            #[inline]
+            fn pmf(&self, x: i64) -> f64 {
+                self.pmf_dyn(x)
+            }
+            #[inline]
+            fn ln_pmf(&self, x: i64) -> f64 {
+                self.ln_pmf_dyn(x)
+            }
+            #[inline]
+            fn try_inv_cdf(&self, p: f64) -> Result<i64, DistError> {
+                self.try_inv_cdf_dyn(p)
+            }
+            #[inline]
+            fn try_isf(&self, q: f64) -> Result<i64, DistError> {
+                self.try_isf_dyn(q)
+            }
+            #[inline]
+            fn sf(&self, k: i64) -> f64 {
+                self.sf_dyn(k)
+            }
+            #[inline]
+            fn ln_sf(&self, k: i64) -> f64 {
+                self.ln_sf_dyn(k)
+            }
+            #[inline]
+            fn pgf(&self, s: f64) -> f64 {
+                self.pgf_dyn(s)
+            }
+        }
+    )*};
+}
+
+impl_core_for_dyn!(dyn ContinuousDyn + '_, dyn ContinuousDyn + Send + Sync + '_);
+impl_core_for_dyn_discrete!(dyn DiscreteDyn + '_, dyn DiscreteDyn + Send + Sync + '_);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dist::{binomial::Binomial, gamma::Gamma, normal::Normal, poisson::Poisson};
+    use crate::rng::SplitMix64;
+
+    #[test]
+    fn boxed_continuous_matches_concrete() {
+        let g = Gamma::new(3.0, 0.5).unwrap();
+        let models: Vec<Box<dyn ContinuousDyn + Send + Sync>> =
+            vec![Box::new(Normal::new(1.0, 2.0).unwrap()), Box::new(g)];
+        let mut a = SplitMix64::seed_from_u64(8);
+        let mut b = SplitMix64::seed_from_u64(8);
+        assert_eq!(models[1].sample(&mut a), g.sample(&mut b));
+        assert_eq!(models[1].cdf(1.2), g.cdf(1.2));
+        // Generic helpers accept the trait object.
+        let mean = crate::dist::numerical_moment(&*models[0], 1, 0.0);
+        assert!((mean - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn boxed_discrete() {
+        let models: Vec<Box<dyn DiscreteDyn>> = vec![
+            Box::new(Binomial::new(10, 0.3).unwrap()),
+            Box::new(Poisson::new(4.0).unwrap()),
+        ];
+        let mut rng = SplitMix64::seed_from_u64(2);
+        for m in &models {
+            assert!(m.in_support(m.sample(&mut rng)));
+            assert_eq!(m.pgf(1.0), 1.0);
+            assert_eq!(m.inv_cdf(0.0), 0);
+        }
+    }
+}
@@ -1,22 +1,96 @@
-use crate::dist::{Continuous, DistError, Distribution, Moments};
+use crate::dist::ziggurat;
+use crate::dist::{
+    CharacteristicFunction, Continuous, DistError, Distribution, HigherMoments, Mgf, Moments,
+    Param, SampleFloat, check_positive, check_probability, const_unwrap,
+};
+use crate::num::Float;
 use crate::rng::RngCore;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Exponential {
     lambda: f64,
 }
 
 impl Exponential {
-    pub fn new(lambda: f64) -> Result<Self, DistError> {
-        if !(lambda > 0.0 && lambda.is_finite()) {
-            return Err(DistError::InvalidParameter);
-        }
+    /// Rate λ = 1.
+    pub const fn standard() -> Self {
+        const_unwrap(Self::new(1.0))
+    }
+    pub const fn new(lambda: f64) -> Result<Self, DistError> {
+        const_try!(check_positive("lambda", lambda));
         Ok(Self { lambda })
     }
+    /// From the mean 1/λ (SciPy's `scale`).
+    pub const fn from_mean(mean: f64) -> Result<Self, DistError> {
+        const_try!(check_positive("mean", mean));
+        Self::new(1.0 / mean)
+    }
+    pub fn builder() -> ExponentialBuilder {
+        ExponentialBuilder::default()
+    }
     #[inline]
-    pub fn lambda(&self) -> f64 {
+    pub const fn lambda(&self) -> f64 {
         self.lambda
     }
+    /// The block ziggurat of [`Normal::sample_fill_wide`](crate::dist::normal::Normal::sample_fill_wide)
+    /// for the exponential, with the same AVX2-only payoff and the same fallback.
+    pub fn sample_fill_wide<R: RngCore>(&self, rng: &mut R, out: &mut [f64]) {
+        if rng.uniform_bits() {
+            ziggurat::fill_exponential(rng, 1.0 / self.lambda, out);
+        } else {
+            self.sample_fill(rng, out);
+        }
+    }
+}
+
+/// Builder for [`Exponential`]; give either the rate or the mean (the last one set wins).
+///
+/// ```
+/// use probability_rs::dist::exponential::Exponential;
+/// let e = Exponential::builder().mean(4.0).build()?;
+/// assert_eq!(e.lambda(), 0.25);
+/// # Ok::<(), probability_rs::dist::DistError>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExponentialBuilder {
+    rate_or_mean: Option<RateOrMean>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum RateOrMean {
+    Rate(f64),
+    Mean(f64),
+}
+
+impl ExponentialBuilder {
+    pub fn rate(mut self, lambda: f64) -> Self {
+        self.rate_or_mean = Some(RateOrMean::Rate(lambda));
+        self
+    }
+    pub fn mean(mut self, mean: f64) -> Self {
+        self.rate_or_mean = Some(RateOrMean::Mean(mean));
+        self
+    }
+    pub fn build(self) -> Result<Exponential, DistError> {
+        match self.rate_or_mean {
+            None => Err(DistError::MissingParameter { name: "rate" }),
+            Some(RateOrMean::Rate(lambda)) => Exponential::new(lambda),
+            Some(RateOrMean::Mean(mean)) => Exponential::from_mean(mean),
+        }
+    }
+}
+
+impl Default for Exponential {
+    /// [`Exponential::standard`].
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl std::fmt::Display for Exponential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Exponential(lambda={})", Param(self.lambda))
+    }
 }
 
 impl Distribution for Exponential {
@@ -28,12 +102,33 @@ impl Distribution for Exponential {
             1.0 - (-self.lambda * x).exp()
         }
     }
+    fn cdf_slice(&self, xs: &[f64], out: &mut [f64]) {
+        assert_eq!(xs.len(), out.len(), "cdf_slice: length mismatch");
+        let lambda = self.lambda;
+        for (o, &x) in out.iter_mut().zip(xs) {
+            let v = 1.0 - (-lambda * x).exp();
+            *o = if x <= 0.0 { 0.0 } else { v };
+        }
+    }
     fn in_support(&self, x: f64) -> bool {
         x >= 0.0 && x.is_finite()
     }
+    /// Ziggurat method (256 layers).
     fn sample<R: RngCore>(&self, rng: &mut R) -> f64 {
-        let u = rng.next_f64();
-        -u.ln() / self.lambda
+        ziggurat::standard_exponential(rng) / self.lambda
+    }
+    /// Inverse transform of `u` and `1 - u`, which keeps the pair perfectly
+    /// anti-correlated in the uniform.
+    fn sample_antithetic<R: RngCore>(&self, rng: &mut R) -> (f64, f64) {
+        let u = rng.next_f64_open_open();
+        (-u.ln() / self.lambda, -(-u).ln_1p() / self.lambda)
+    }
+}
+
+impl SampleFloat for Exponential {
+    /// Inverse transform `−ln(1 − u) / λ`; `1 − u` is in `(0, 1]`, so the draw is finite.
+    fn sample_float<F: Float, R: RngCore>(&self, rng: &mut R) -> F {
+        -(F::ONE - rng.next_float::<F>()).ln() / F::from_f64(self.lambda)
     }
 }
 
@@ -45,9 +140,26 @@ impl Continuous for Exponential {
             0.0
         }
     }
-    fn inv_cdf(&self, p: f64) -> f64 {
-        debug_assert!(p > 0.0 && p < 1.0);
-        -(1.0 - p).ln() / self.lambda
+    fn pdf_slice(&self, xs: &[f64], out: &mut [f64]) {
+        assert_eq!(xs.len(), out.len(), "pdf_slice: length mismatch");
+        let lambda = self.lambda;
+        for (o, &x) in out.iter_mut().zip(xs) {
+            let v = lambda * (-lambda * x).exp();
+            *o = if x >= 0.0 && x.is_finite() { v } else { 0.0 };
+        }
+    }
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+    fn try_inv_cdf(&self, p: f64) -> Result<f64, DistError> {
+        // p = 1 gives +inf through ln(0).
+        let p = check_probability(p)?;
+        Ok(-(1.0 - p).ln() / self.lambda)
+    }
+
+    fn try_isf(&self, q: f64) -> Result<f64, DistError> {
+        // S(x) = e^{−λx}; q = 0 gives +inf.
+        Ok(-check_probability(q)?.ln() / self.lambda)
     }
 }
 
@@ -69,10 +181,63 @@ impl Moments for Exponential {
     }
 }
 
+impl Mgf for Exponential {
+    fn mgf(&self, t: f64) -> f64 {
+        if t < self.lambda {
+            self.lambda / (self.lambda - t)
+        } else if t.is_nan() {
+            f64::NAN
+        } else {
+            f64::INFINITY
+        }
+    }
+    fn cgf(&self, t: f64) -> f64 {
+        if t < self.lambda {
+            -(-t / self.lambda).ln_1p()
+        } else {
+            self.mgf(t)
+        }
+    }
+}
+
+impl CharacteristicFunction for Exponential {
+    fn cf(&self, t: f64) -> (f64, f64) {
+        // λ / (λ − it)
+        let l = self.lambda;
+        let den = l * l + t * t;
+        (l * l / den, l * t / den)
+    }
+}
+
+impl HigherMoments for Exponential {
+    fn raw_moment(&self, n: u32) -> f64 {
+        // n! / λ^n
+        (1..=n).fold(1.0, |acc, k| acc * k as f64 / self.lambda)
+    }
+    fn central_moment(&self, n: u32) -> f64 {
+        // !n / λ^n, where the subfactorial satisfies !n = n·!(n−1) + (−1)^n
+        let mut d = 1.0;
+        for k in 1..=n {
+            d = k as f64 * d + if k % 2 == 0 { 1.0 } else { -1.0 };
+        }
+        d / self.lambda.powi(n as i32)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
+    fn builder_takes_rate_or_mean() {
+        let e = Exponential::builder().mean(2.0).rate(3.0).build().unwrap();
+        assert_eq!(e, Exponential::new(3.0).unwrap());
+        assert_eq!(
+            Exponential::builder().build().unwrap_err(),
+            DistError::MissingParameter { name: "rate" }
+        );
+        assert!(Exponential::builder().mean(-1.0).build().is_err());
+    }
+    #[test]
     fn basic_values() {
         let e = Exponential::new(2.0).unwrap();
         assert!((e.mean() - 0.5).abs() < 1e-15);
@@ -82,4 +247,98 @@ mod tests {
         assert!((e.skewness() - 2.0).abs() < 1e-15);
         assert!((e.kurtosis() - 6.0).abs() < 1e-15);
     }
+
+    #[test]
+    fn mgf_domain() {
+        let e = Exponential::new(2.0).unwrap();
+        assert!((e.mgf(1.0) - 2.0).abs() < 1e-15);
+        assert!((e.cgf(1.0) - 2f64.ln()).abs() < 1e-15);
+        assert_eq!(e.mgf(2.0), f64::INFINITY);
+        assert_eq!(e.cgf(3.0), f64::INFINITY);
+        assert!(e.mgf(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn cf_closed_form_matches_numerical() {
+        let e = Exponential::new(1.5).unwrap();
+        for &t in &[0.0, 0.7, 3.0] {
+            let (re, im) = e.cf(t);
+            let (nre, nim) = crate::dist::numerical_cf(&e, t);
+            assert!((re - nre).abs() < 1e-9 && (im - nim).abs() < 1e-9);
+        }
+        assert_eq!(e.cf(0.0), (1.0, 0.0));
+    }
+
+    #[test]
+    fn sample_float_runs_in_f32() {
+        let e = Exponential::new(2.0).unwrap();
+        let mut rng = crate::rng::SplitMix64::seed_from_u64(5);
+        let mut xs = vec![0.0f32; 20_000];
+        e.sample_fill_float(&mut rng, &mut xs);
+        assert!(xs.iter().all(|x| x.is_finite() && *x >= 0.0));
+        let m = xs.iter().sum::<f32>() / xs.len() as f32;
+        assert!((m - 0.5).abs() < 0.02, "{m}");
+        let u = crate::dist::uniform::Uniform::new(-1.0, 3.0).unwrap();
+        assert!((0..1000).all(|_| (-1.0..3.0).contains(&u.sample_float::<f32, _>(&mut rng))));
+    }
+
+    #[test]
+    fn antithetic_pairs_mirror_the_uniform() {
+        let e = Exponential::new(2.0).unwrap();
+        let mut rng = crate::rng::SplitMix64::seed_from_u64(8);
+        for _ in 0..100 {
+            let (x, y) = e.sample_antithetic(&mut rng);
+            // u = exp(-2x) and 1 - u = exp(-2y).
+            assert!(((-2.0 * x).exp() + (-2.0 * y).exp() - 1.0).abs() < 1e-12);
+        }
+        let n = crate::dist::normal::Normal::new(1.0, 2.0).unwrap();
+        let (a, b) = n.sample_antithetic(&mut rng);
+        assert!((a + b - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn stratified_draws_hit_every_quantile_bin() {
+        let e = Exponential::new(1.0).unwrap();
+        let xs = e.sample_stratified(10, &mut crate::rng::SplitMix64::seed_from_u64(2));
+        for (i, &x) in xs.iter().enumerate() {
+            assert_eq!((e.cdf(x) * 10.0) as usize, i);
+        }
+    }
+
+    #[test]
+    fn sample_finite_at_rng_extremes() {
+        let e = Exponential::new(1.0).unwrap();
+        // u = 0 used to give +inf under inversion; the ziggurat maps it to 0.
+        let mut rng = crate::rng::mock::ScriptedRng::new(&[0]);
+        assert_eq!(e.sample(&mut rng), 0.0);
+        // Top of the base strip falls into the tail, then a zero word lands at its edge.
+        let mut rng = crate::rng::mock::ScriptedRng::new(&[u64::MAX << 8, 0]);
+        assert_eq!(e.sample(&mut rng), 7.69711747013105);
+    }
+
+    #[test]
+    fn higher_moments() {
+        let e = Exponential::new(2.0).unwrap();
+        assert!((e.raw_moment(3) - 6.0 / 8.0).abs() < 1e-15);
+        // μ4 = 9/λ^4; central moments agree with the numerical fallback
+        assert!((e.central_moment(4) - 9.0 / 16.0).abs() < 1e-15);
+        let num = crate::dist::numerical_moment(&e, 4, e.mean());
+        assert!((num - 9.0 / 16.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn isf_log_space() {
+        let e = Exponential::new(0.5).unwrap();
+        assert!((e.isf(1e-30) - 2.0 * 30.0 * std::f64::consts::LN_10).abs() < 1e-12);
+        assert!((e.isf(0.25) - e.inv_cdf(0.75)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn sample_from_a_halton_sequence() {
+        let mut h = crate::rng::halton::Halton::new(1);
+        let e = Exponential::new(2.0).unwrap();
+        let xs: Vec<f64> = (0..100_000).map(|_| e.sample(&mut h)).collect();
+        let m = xs.iter().sum::<f64>() / xs.len() as f64;
+        assert!((m - 0.5).abs() < 1e-3, "{m}");
+    }
 }
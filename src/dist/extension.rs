@@ -0,0 +1,459 @@
+//! Extension point for distributions defined outside this crate.
+//!
+//! [`Distribution`], [`Continuous`] and [`Discrete`] are sealed so that methods can be
+//! added to them without breaking downstream code. Third-party crates implement the
+//! unsealed mirrors below instead and wrap their type in [`Extension`], which implements
+//! the core traits and can be passed to anything generic over them.
+//!
+//! ```
+//! use probability_rs::dist::extension::{Extension, ExtendedContinuous, ExtendedDistribution};
+//! use probability_rs::{Continuous, Distribution, rng::RngCore, rng::SplitMix64};
+//!
+//! /// Standard logistic distribution.
+//! struct Logistic;
+//!
+//! impl ExtendedDistribution for Logistic {
+//!     type Value = f64;
+//!     fn cdf(&self, x: f64) -> f64 {
+//!         1.0 / (1.0 + (-x).exp())
+//!     }
+//!     fn sample<R: RngCore>(&self, rng: &mut R) -> f64 {
+//!         self.inv_cdf(rng.next_f64_open_open())
+//!     }
+//!     fn in_support(&self, x: f64) -> bool {
+//!         x.is_finite()
+//!     }
+//! }
+//!
+//! impl ExtendedContinuous for Logistic {
+//!     fn pdf(&self, x: f64) -> f64 {
+//!         let e = (-x).exp();
+//!         e / ((1.0 + e) * (1.0 + e))
+//!     }
+//!     fn inv_cdf(&self, p: f64) -> f64 {
+//!         (p / (1.0 - p)).ln()
+//!     }
+//! }
+//!
+//! let d = Extension(Logistic);
+//! let mut rng = SplitMix64::seed_from_u64(1);
+//! assert_eq!(d.cdf(0.0), 0.5);
+//! assert!(Continuous::inv_cdf(&d, 0.5).abs() < 1e-15);
+//! let _x = d.sample(&mut rng);
+//! ```
+//!
+//! `inv_cdf` may also be left out: the default solves `cdf(x) = p` numerically inside
+//! [`ExtendedContinuous::support`], so a new continuous distribution only needs its pdf and cdf.
+//!
+//! Methods added to the core traits later appear here as provided methods, so existing
+//! extensions keep compiling.
+
+use crate::dist::{
+    CharacteristicFunction, Continuous, Discrete, DistError, Distribution, HigherMoments, Mgf,
+    Moments, check_probability,
+};
+use crate::rng::RngCore;
+
+/// Unsealed mirror of [`Distribution`].
+pub trait ExtendedDistribution {
+    type Value;
+    fn cdf(&self, x: Self::Value) -> f64;
+    fn sample<R: RngCore>(&self, rng: &mut R) -> Self::Value;
+    fn in_support(&self, x: Self::Value) -> bool;
+    fn sample_fill<R: RngCore>(&self, rng: &mut R, out: &mut [Self::Value]) {
+        for x in out {
+            *x = self.sample(rng);
+        }
+    }
+}
+
+/// Unsealed mirror of [`Continuous`].
+pub trait ExtendedContinuous: ExtendedDistribution<Value = f64> {
+    fn pdf(&self, x: f64) -> f64;
+    /// Support bounds `(lo, hi)`, possibly infinite. Defaults to the whole real line.
+    fn support(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+    /// Defaults to solving `cdf(x) = p` inside [`support`](Self::support), as
+    /// [`Continuous::try_inv_cdf`] does. Panics unless p is in `[0,1]`.
+    fn inv_cdf(&self, p: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&p), "p must be in [0,1]");
+        super::numeric::bracketed_quantile(|x| self.cdf(x), self.support(), p)
+    }
+    /// Defaults to rejecting p outside `[0,1]` and calling [`inv_cdf`](Self::inv_cdf).
+    fn try_inv_cdf(&self, p: f64) -> Result<f64, DistError> {
+        Ok(self.inv_cdf(check_probability(p)?))
+    }
+    /// Defaults to `try_inv_cdf(1 − q)`, as [`Continuous::try_isf`] does.
+    fn try_isf(&self, q: f64) -> Result<f64, DistError> {
+        self.try_inv_cdf(1.0 - check_probability(q)?)
+    }
+}
+
+/// Unsealed mirror of [`Discrete`].
+pub trait ExtendedDiscrete: ExtendedDistribution<Value = i64> {
+    fn pmf(&self, x: i64) -> f64;
+    fn ln_pmf(&self, x: i64) -> f64 {
+        self.pmf(x).ln()
+    }
+    fn inv_cdf(&self, p: f64) -> i64;
+    /// Defaults to rejecting p outside `[0,1]` and calling [`inv_cdf`](Self::inv_cdf).
+    fn try_inv_cdf(&self, p: f64) -> Result<i64, DistError> {
+        Ok(self.inv_cdf(check_probability(p)?))
+    }
+    fn pgf(&self, s: f64) -> f64;
+    fn ln_pgf(&self, s: f64) -> f64 {
+        self.pgf(s).ln()
+    }
+    /// Defaults to `1 − cdf(k)` up to the median and the summed pmf above it, as
+    /// [`Discrete::sf`] does.
+    fn sf(&self, k: i64) -> f64 {
+        let c = self.cdf(k);
+        if c <= 0.5 {
+            1.0 - c
+        } else {
+            super::numeric::upper_tail_sum(|j| self.pmf(j), k)
+        }
+    }
+    fn ln_sf(&self, k: i64) -> f64 {
+        self.sf(k).ln()
+    }
+    /// Defaults to a search against [`sf`](Self::sf), as [`Discrete::try_isf`] does.
+    fn try_isf(&self, q: f64) -> Result<i64, DistError> {
+        super::numeric::galloping_isf(|p| self.try_inv_cdf(p), |k| self.sf(k), q)
+    }
+}
+
+/// Adapts an [`ExtendedDistribution`] to the core traits.
+///
+/// The open capability traits ([`Moments`], [`Mgf`], [`CharacteristicFunction`],
+/// [`HigherMoments`]) are forwarded when the inner type implements them.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Extension<T>(pub T);
+
+impl<T> Extension<T> {
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: ExtendedDistribution> super::private::Sealed for Extension<T> {}
+
+impl<T: ExtendedDistribution> Distribution for Extension<T> {
+    type Value = T::Value;
+    #[inline]
+    fn cdf(&self, x: T::Value) -> f64 {
+        self.0.cdf(x)
+    }
+    #[inline]
+    fn sample<R: RngCore>(&self, rng: &mut R) -> T::Value {
+        self.0.sample(rng)
+    }
+    #[inline]
+    fn in_support(&self, x: T::Value) -> bool {
+        self.0.in_support(x)
+    }
+    #[inline]
+    fn sample_fill<R: RngCore>(&self, rng: &mut R, out: &mut [T::Value]) {
+        self.0.sample_fill(rng, out)
+    }
+}
+
+impl<T: ExtendedContinuous> Continuous for Extension<T> {
+    #[inline]
+    fn pdf(&self, x: f64) -> f64 {
+        self.0.pdf(x)
+    }
+    #[inline]
+    fn support(&self) -> (f64, f64) {
+        self.0.support()
+    }
+    #[inline]
+    fn try_inv_cdf(&self, p: f64) -> Result<f64, DistError> {
+        self.0.try_inv_cdf(p)
+    }
+    #[inline]
+    fn try_isf(&self, q: f64) -> Result<f64, DistError> {
+        self.0.try_isf(q)
+    }
+}
+
+impl<T: ExtendedDiscrete> Discrete for Extension<T> {
+    #[inline]
+    fn pmf(&self, x: i64) -> f64 {
+        self.0.pmf(x)
+    }
+    #[inline]
+    fn ln_pmf(&self, x: i64) -> f64 {
+        self.0.ln_pmf(x)
+    }
+    #[inline]
+    fn try_inv_cdf(&self, p: f64) -> Result<i64, DistError> {
+        self.0.try_inv_cdf(p)
+    }
+    #[inline]
+    fn pgf(&self, s: f64) -> f64 {
+        self.0.pgf(s)
+    }
+    #[inline]
+    fn ln_pgf(&self, s: f64) -> f64 {
+        self.0.ln_pgf(s)
+    }
+    #[inline]
+    fn sf(&self, k: i64) -> f64 {
+        self.0.sf(k)
+    }
+    #[inline]
+    fn ln_sf(&self, k: i64) -> f64 {
+        self.0.ln_sf(k)
+    }
+    #[inline]
+    fn try_isf(&self, q: f64) -> Result<i64, DistError> {
+        self.0.try_isf(q)
+    }
+}
+
+impl<T: Moments> Moments for Extension<T> {
+    fn mean(&self) -> f64 {
+        self.0.mean()
+    }
+    fn variance(&self) -> f64 {
+        self.0.variance()
+    }
+    fn std_dev(&self) -> f64 {
+        self.0.std_dev()
+    }
+    fn skewness(&self) -> f64 {
+        self.0.skewness()
+    }
+    fn kurtosis(&self) -> f64 {
+        self.0.kurtosis()
+    }
+    fn kurtosis_full(&self) -> f64 {
+        self.0.kurtosis_full()
+    }
+    fn entropy(&self) -> f64 {
+        self.0.entropy()
+    }
+}
+
+impl<T: HigherMoments> HigherMoments for Extension<T> {
+    fn raw_moment(&self, n: u32) -> f64 {
+        self.0.raw_moment(n)
+    }
+    fn central_moment(&self, n: u32) -> f64 {
+        self.0.central_moment(n)
+    }
+}
+
+impl<T: Mgf> Mgf for Extension<T> {
+    fn mgf(&self, t: f64) -> f64 {
+        self.0.mgf(t)
+    }
+    fn cgf(&self, t: f64) -> f64 {
+        self.0.cgf(t)
+    }
+}
+
+impl<T: CharacteristicFunction> CharacteristicFunction for Extension<T> {
+    fn cf(&self, t: f64) -> (f64, f64) {
+        self.0.cf(t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Discrete uniform on {0, .., n-1}.
+    struct DiscreteUniform(i64);
+
+    impl ExtendedDistribution for DiscreteUniform {
+        type Value = i64;
+        fn cdf(&self, x: i64) -> f64 {
+            ((x + 1).clamp(0, self.0)) as f64 / self.0 as f64
+        }
+        fn sample<R: RngCore>(&self, rng: &mut R) -> i64 {
+            (rng.next_f64() * self.0 as f64) as i64
+        }
+        fn in_support(&self, x: i64) -> bool {
+            (0..self.0).contains(&x)
+        }
+    }
+
+    impl ExtendedDiscrete for DiscreteUniform {
+        fn pmf(&self, x: i64) -> f64 {
+            if self.in_support(x) {
+                1.0 / self.0 as f64
+            } else {
+                0.0
+            }
+        }
+        fn inv_cdf(&self, p: f64) -> i64 {
+            ((p * self.0 as f64).ceil() as i64 - 1).max(0)
+        }
+        fn pgf(&self, s: f64) -> f64 {
+            (0..self.0).map(|k| s.powi(k as i32)).sum::<f64>() / self.0 as f64
+        }
+    }
+
+    #[test]
+    fn generic_helpers_accept_extensions() {
+        let d = Extension(DiscreteUniform(4));
+        // E[X^2] = (0 + 1 + 4 + 9) / 4
+        assert!((super::super::summed_moment(&d, 2, 0.0) - 3.5).abs() < 1e-15);
+        assert_eq!(Discrete::inv_cdf(&d, 0.5), 1);
+        assert!((d.ln_pgf(1.0)).abs() < 1e-15);
+        assert_eq!((d.sf(1), d.sf(2)), (0.5, 0.25));
+    }
+
+    /// Geometric with success probability ½ on {0, 1, ..}, with tail-accurate `ln_pmf` and
+    /// `ln_sf`.
+    struct Halving;
+
+    impl ExtendedDistribution for Halving {
+        type Value = i64;
+        fn cdf(&self, k: i64) -> f64 {
+            1.0 - self.sf(k)
+        }
+        fn sample<R: RngCore>(&self, rng: &mut R) -> i64 {
+            rng.next_u64().trailing_ones() as i64
+        }
+        fn in_support(&self, k: i64) -> bool {
+            k >= 0
+        }
+    }
+
+    impl ExtendedDiscrete for Halving {
+        fn pmf(&self, k: i64) -> f64 {
+            if k < 0 {
+                0.0
+            } else {
+                (-(k as f64 + 1.0)).exp2()
+            }
+        }
+        fn inv_cdf(&self, p: f64) -> i64 {
+            ((-(1.0 - p).log2()).ceil() as i64 - 1).max(0)
+        }
+        fn pgf(&self, s: f64) -> f64 {
+            0.5 / (1.0 - 0.5 * s)
+        }
+        fn sf(&self, k: i64) -> f64 {
+            if k < 0 {
+                1.0
+            } else {
+                (-(k as f64 + 1.0)).exp2()
+            }
+        }
+        fn ln_sf(&self, k: i64) -> f64 {
+            if k < 0 {
+                0.0
+            } else {
+                -(k as f64 + 1.0) * std::f64::consts::LN_2
+            }
+        }
+        fn ln_pmf(&self, k: i64) -> f64 {
+            if k < 0 {
+                f64::NEG_INFINITY
+            } else {
+                -(k as f64 + 1.0) * std::f64::consts::LN_2
+            }
+        }
+    }
+
+    #[test]
+    fn tail_overrides_are_forwarded() {
+        let h = Extension(Halving);
+        assert_eq!(h.sf(1021), 2f64.powi(-1022));
+        assert_eq!(h.ln_sf(1999), -2000.0 * std::f64::consts::LN_2);
+        assert_eq!(h.ln_pmf(1999), -2000.0 * std::f64::consts::LN_2);
+        // 2^-997 <= 1e-300 < 2^-996
+        assert_eq!(Discrete::isf(&h, 1e-300), 996);
+        let l = Extension(Logistic);
+        assert!((Continuous::isf(&l, 1e-300) - (1e3 + 6.907755278982137)).abs() < 1e-12);
+    }
+
+    /// Triangular on [0, 2] with its mode at 1; only pdf, cdf and support are given.
+    struct Triangular;
+
+    impl ExtendedDistribution for Triangular {
+        type Value = f64;
+        fn cdf(&self, x: f64) -> f64 {
+            let x = x.clamp(0.0, 2.0);
+            if x <= 1.0 {
+                0.5 * x * x
+            } else {
+                1.0 - 0.5 * (2.0 - x) * (2.0 - x)
+            }
+        }
+        fn sample<R: RngCore>(&self, rng: &mut R) -> f64 {
+            self.inv_cdf(rng.next_f64())
+        }
+        fn in_support(&self, x: f64) -> bool {
+            (0.0..=2.0).contains(&x)
+        }
+    }
+
+    impl ExtendedContinuous for Triangular {
+        fn pdf(&self, x: f64) -> f64 {
+            if self.in_support(x) {
+                1.0 - (x - 1.0).abs()
+            } else {
+                0.0
+            }
+        }
+        fn support(&self) -> (f64, f64) {
+            (0.0, 2.0)
+        }
+    }
+
+    /// Logistic with location 1e3 and scale 1e-2, on the whole real line.
+    struct Logistic;
+
+    impl ExtendedDistribution for Logistic {
+        type Value = f64;
+        fn cdf(&self, x: f64) -> f64 {
+            1.0 / (1.0 + (-(x - 1e3) / 1e-2).exp())
+        }
+        fn sample<R: RngCore>(&self, rng: &mut R) -> f64 {
+            self.inv_cdf(rng.next_f64())
+        }
+        fn in_support(&self, x: f64) -> bool {
+            x.is_finite()
+        }
+    }
+
+    impl ExtendedContinuous for Logistic {
+        fn pdf(&self, x: f64) -> f64 {
+            let e = (-(x - 1e3) / 1e-2).exp();
+            e / (1e-2 * (1.0 + e) * (1.0 + e))
+        }
+        fn try_isf(&self, q: f64) -> Result<f64, DistError> {
+            let q = check_probability(q)?;
+            Ok(1e3 + 1e-2 * ((-q).ln_1p() - q.ln()))
+        }
+    }
+
+    #[test]
+    fn default_inv_cdf_inverts_the_cdf() {
+        let t = Extension(Triangular);
+        assert_eq!(Continuous::inv_cdf(&t, 0.0), 0.0);
+        assert_eq!(Continuous::inv_cdf(&t, 1.0), 2.0);
+        for &p in &[1e-12f64, 0.1, 0.5, 0.875, 1.0 - 1e-9] {
+            let x = if p <= 0.5 {
+                (2.0 * p).sqrt()
+            } else {
+                2.0 - (2.0 * (1.0 - p)).sqrt()
+            };
+            assert!((Continuous::inv_cdf(&t, p) - x).abs() < 1e-12, "p = {p}");
+        }
+        let l = Extension(Logistic);
+        assert_eq!(Continuous::inv_cdf(&l, 0.0), f64::NEG_INFINITY);
+        for &p in &[1e-6f64, 0.3, 0.5, 0.99] {
+            let x = 1e3 + 1e-2 * (p / (1.0 - p)).ln();
+            assert!((Continuous::inv_cdf(&l, p) - x).abs() < 1e-10, "p = {p}");
+        }
+        assert!(matches!(l.try_inv_cdf(1.5), Err(DistError::DomainError)));
+    }
+}
@@ -1,23 +1,57 @@
-use crate::dist::{Discrete, DistError, Distribution, Moments};
+use crate::dist::{
+    CharacteristicFunction, Discrete, DistError, Distribution, HigherMoments, Mgf, Moments, Param,
+    check_probability, check_unit,
+};
+use crate::num;
 use crate::rng::RngCore;
 
 /// Geometric(p) over k=1,2,... counts trials until first success.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Geometric {
     p: f64,
 }
 
 impl Geometric {
-    pub fn new(p: f64) -> Result<Self, DistError> {
-        if !(0.0..=1.0).contains(&p) || p == 0.0 || !p.is_finite() {
-            return Err(DistError::InvalidParameter);
-        }
+    pub const fn new(p: f64) -> Result<Self, DistError> {
+        const_try!(check_unit("p", p, false));
         Ok(Self { p })
     }
     #[inline]
-    pub fn p(&self) -> f64 {
+    pub const fn p(&self) -> f64 {
         self.p
     }
+
+    /// ln(1 − p), exact for tiny p where `1 - p` rounds.
+    #[inline]
+    fn ln_q(&self) -> f64 {
+        (-self.p).ln_1p()
+    }
+
+    /// Smallest k >= 1 with `done(k)`, from the closed-form estimate `x`. Steps by one only
+    /// while k is exact as an f64; from 2^63 on the quantile saturates at `i64::MAX`.
+    fn refine(x: f64, done: impl Fn(i64) -> bool) -> i64 {
+        let x = x.ceil().max(1.0);
+        if x >= 9.223_372_036_854_775_808e18 {
+            return i64::MAX;
+        }
+        let mut k = x as i64;
+        if k > 1 << 53 {
+            return k;
+        }
+        while k > 1 && done(k - 1) {
+            k -= 1;
+        }
+        while !done(k) {
+            k += 1;
+        }
+        k
+    }
+}
+
+impl std::fmt::Display for Geometric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Geometric(p={})", Param(self.p))
+    }
 }
 
 impl Distribution for Geometric {
@@ -26,37 +60,69 @@ impl Distribution for Geometric {
         if k < 1 {
             return 0.0;
         }
-        1.0 - (1.0 - self.p).powi(k as i32)
+        -(k as f64 * self.ln_q()).exp_m1()
     }
     fn in_support(&self, k: i64) -> bool {
         k >= 1
     }
     fn sample<R: RngCore>(&self, rng: &mut R) -> i64 {
-        // Inverse CDF: k = ceil(log(1-u)/log(1-p))
-        let u = rng.next_f64();
-        let q = 1.0 - self.p;
-        let k = ((1.0 - u).ln() / q.ln()).ceil() as i64;
+        // Inverse CDF: k = ceil(log(u)/log(1-p)), with u in (0,1) so the log stays finite
+        let u = rng.next_f64_open_open();
+        let k = (u.ln() / self.ln_q()).ceil() as i64;
         if k < 1 { 1 } else { k }
     }
 }
 
 impl Discrete for Geometric {
     fn pmf(&self, k: i64) -> f64 {
-        if !self.in_support(k) {
-            return 0.0;
+        self.ln_pmf(k).exp()
+    }
+    fn ln_pmf(&self, k: i64) -> f64 {
+        match k {
+            ..1 => f64::NEG_INFINITY,
+            1 => self.p.ln(),
+            _ => self.p.ln() + (k - 1) as f64 * self.ln_q(),
         }
-        self.p * (1.0 - self.p).powi((k - 1) as i32)
     }
-    fn inv_cdf(&self, p: f64) -> i64 {
-        debug_assert!((0.0..=1.0).contains(&p));
-        if p <= 0.0 {
-            return 1;
+    fn try_inv_cdf(&self, p: f64) -> Result<i64, DistError> {
+        let p = check_probability(p)?;
+        if p == 0.0 {
+            return Ok(1);
         }
-        if p >= 1.0 {
-            return i64::MAX;
+        if p == 1.0 {
+            return Ok(i64::MAX);
+        }
+        // The closed form can land one off after rounding; step to the exact answer.
+        Ok(Self::refine((-p).ln_1p() / self.ln_q(), |k| {
+            self.cdf(k) >= p
+        }))
+    }
+    fn try_isf(&self, q: f64) -> Result<i64, DistError> {
+        let q = check_probability(q)?;
+        if q == 0.0 {
+            return Ok(i64::MAX);
+        }
+        // (1 − p)^k <= q from k = ⌈ln q / ln(1 − p)⌉.
+        Ok(Self::refine(q.ln() / self.ln_q(), |k| self.sf(k) <= q))
+    }
+    fn sf(&self, k: i64) -> f64 {
+        self.ln_sf(k).exp()
+    }
+    fn ln_sf(&self, k: i64) -> f64 {
+        // P(X > k) = (1 − p)^k
+        if k < 1 { 0.0 } else { k as f64 * self.ln_q() }
+    }
+
+    fn pgf(&self, s: f64) -> f64 {
+        // p s / (1 - (1-p) s) for |s| < 1/(1-p)
+        let den = 1.0 - (1.0 - self.p) * s;
+        if den > 0.0 && den < 2.0 {
+            self.p * s / den
+        } else if den <= 0.0 {
+            f64::INFINITY
+        } else {
+            f64::NAN
         }
-        let q = 1.0 - self.p;
-        ((1.0 - p).ln() / q.ln()).ceil() as i64
     }
 }
 
@@ -84,6 +150,50 @@ impl Moments for Geometric {
     }
 }
 
+impl Mgf for Geometric {
+    fn mgf(&self, t: f64) -> f64 {
+        self.cgf(t).exp()
+    }
+    fn cgf(&self, t: f64) -> f64 {
+        // M(t) = p e^t / (1 - (1-p) e^t) for t < -ln(1-p)
+        let q = 1.0 - self.p;
+        if q == 0.0 {
+            return t;
+        }
+        let s = t + q.ln();
+        if s < 0.0 {
+            self.p.ln() + t - num::log1mexp(s)
+        } else if t.is_nan() {
+            f64::NAN
+        } else {
+            f64::INFINITY
+        }
+    }
+}
+
+impl CharacteristicFunction for Geometric {
+    fn cf(&self, t: f64) -> (f64, f64) {
+        // p e^{it} / (1 − (1 − p) e^{it})
+        let q = 1.0 - self.p;
+        let (num_re, num_im) = (self.p * t.cos(), self.p * t.sin());
+        let (den_re, den_im) = (1.0 - q * t.cos(), -q * t.sin());
+        let den = den_re * den_re + den_im * den_im;
+        (
+            (num_re * den_re + num_im * den_im) / den,
+            (num_im * den_re - num_re * den_im) / den,
+        )
+    }
+}
+
+impl HigherMoments for Geometric {
+    fn raw_moment(&self, n: u32) -> f64 {
+        super::summed_moment(self, n, 0.0)
+    }
+    fn central_moment(&self, n: u32) -> f64 {
+        super::summed_moment(self, n, self.mean())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,6 +209,38 @@ mod tests {
         assert!((g.cdf(1) - 0.5).abs() < 1e-15);
         assert!((g.cdf(2) - 0.75).abs() < 1e-15);
     }
+    #[test]
+    fn rare_events_at_large_k() {
+        // mpmath: -expm1(k·log1p(-p)) and log(p) + (k - 1)·log1p(-p)
+        let g = Geometric::new(1e-9).unwrap();
+        let k = 1_000_000_000;
+        assert!((g.cdf(k) / 0.6321205590124974 - 1.0).abs() < 1e-14);
+        assert!((g.ln_pmf(k) + 21.723265836446411).abs() < 1e-12);
+        assert!((g.ln_sf(k) + 1.0000000005).abs() < 1e-12);
+        assert!((g.pmf(3 * k) / (g.ln_pmf(3 * k)).exp() - 1.0).abs() < 1e-15);
+        for k in [1, 2, 17, 123_456_789, 5_000_000_000] {
+            assert_eq!(g.inv_cdf(g.cdf(k)), k);
+        }
+        let g = Geometric::new(0.3).unwrap();
+        assert!((1..80).all(|k| g.inv_cdf(g.cdf(k)) == k));
+        assert_eq!(Geometric::new(1.0).unwrap().pmf(1), 1.0);
+    }
+
+    #[test]
+    fn quantiles_saturate_and_isf_is_closed_form() {
+        let g = Geometric::new(1e-20).unwrap();
+        assert_eq!(g.inv_cdf(0.5), i64::MAX);
+        assert_eq!(g.isf(0.5), i64::MAX);
+        // ⌈−ln(1 − 1e-3) / 1e-20⌉, beyond 2^53 so no stepping
+        assert!((g.inv_cdf(1e-3) as f64 / 1.0005003335835335e17 - 1.0).abs() < 1e-15);
+        let g = Geometric::new(0.5).unwrap();
+        // 2^-997 <= 1e-300 < 2^-996
+        assert_eq!(g.isf(1e-300), 997);
+        assert_eq!((g.isf(1.0), g.isf(0.0)), (1, i64::MAX));
+        let g = Geometric::new(0.3).unwrap();
+        assert!((1..80).all(|k| g.isf(g.sf(k)) == k));
+    }
+
     #[test]
     fn moments_higher() {
         let g = Geometric::new(0.25).unwrap();
@@ -109,6 +251,16 @@ mod tests {
         assert!((g.kurtosis() - kurt).abs() < 1e-12);
     }
 
+    #[test]
+    fn mgf_domain() {
+        let g = Geometric::new(0.5).unwrap();
+        let t = 0.5f64.ln();
+        // 0.5 * 0.5 / (1 - 0.25) = 1/3
+        assert!((g.mgf(t) - 1.0 / 3.0).abs() < 1e-15);
+        assert_eq!(g.mgf(2f64.ln()), f64::INFINITY);
+        assert!((g.cgf(0.0)).abs() < 1e-15);
+    }
+
     #[test]
     fn entropy_geometric() {
         let p = 0.3;
@@ -117,4 +269,46 @@ mod tests {
         let expected = -(p * p.ln() + (q / p) * q.ln());
         assert!((g.entropy() - expected).abs() < 1e-12);
     }
+
+    #[test]
+    fn sample_at_rng_extremes() {
+        let g = Geometric::new(0.3).unwrap();
+        let mut rng = crate::rng::mock::ScriptedRng::new(&[0, u64::MAX]);
+        let lo = g.sample(&mut rng);
+        let hi = g.sample(&mut rng);
+        assert!((1..i64::MAX).contains(&lo));
+        assert_eq!(hi, 1);
+    }
+
+    #[test]
+    fn pgf_domain() {
+        let g = Geometric::new(0.5).unwrap();
+        assert!((g.pgf(1.0) - 1.0).abs() < 1e-15);
+        assert!((g.pgf(0.5) - 1.0 / 3.0).abs() < 1e-15);
+        assert_eq!(g.pgf(2.0), f64::INFINITY);
+        assert!(g.pgf(-3.0).is_nan());
+    }
+
+    #[test]
+    fn summed_higher_moments() {
+        let g = Geometric::new(0.5).unwrap();
+        assert!((g.raw_moment(1) - g.mean()).abs() < 1e-12);
+        assert!((g.central_moment(2) - g.variance()).abs() < 1e-12);
+        // E[X^2] = (2 - p) / p^2
+        assert!((g.raw_moment(2) - 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn zero_probability_rejected() {
+        let e = Geometric::new(0.0).unwrap_err();
+        assert_eq!(
+            e,
+            DistError::ProbabilityOutOfRange {
+                name: "p",
+                value: 0.0
+            }
+        );
+        let boxed: Box<dyn std::error::Error> = Box::new(e);
+        assert!(boxed.to_string().contains("`p`"));
+    }
 }
@@ -1,9 +1,16 @@
 use crate::dist::normal::Normal;
-use crate::dist::{Continuous, DistError, Distribution, Moments};
+use crate::dist::{
+    CharacteristicFunction, Continuous, DistError, Distribution, HigherMoments, Moments, Param,
+    SampleFloat, check_finite, check_positive, const_unwrap,
+};
+use crate::num::Float;
 use crate::rng::RngCore;
 
 /// Lognormal with parameters (mu, sigma) where ln(X) ~ Normal(mu, sigma).
-#[derive(Debug, Clone, Copy)]
+///
+/// `cdf` and the quantiles go through [`Normal`], so they follow its
+/// [`Accuracy`](crate::num::Accuracy); see [`with_accuracy`](Self::with_accuracy).
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct LogNormal {
     mu: f64,
     sigma: f64,
@@ -11,23 +18,114 @@ pub struct LogNormal {
 }
 
 impl LogNormal {
-    pub fn new(mu: f64, sigma: f64) -> Result<Self, DistError> {
-        if !(sigma > 0.0 && sigma.is_finite() && mu.is_finite()) {
-            return Err(DistError::InvalidParameter);
-        }
-        let normal = Normal::new(mu, sigma)?;
+    /// exp of N(0, 1).
+    pub const fn standard() -> Self {
+        const_unwrap(Self::new(0.0, 1.0))
+    }
+    pub const fn new(mu: f64, sigma: f64) -> Result<Self, DistError> {
+        const_try!(check_finite("mu", mu));
+        const_try!(check_positive("sigma", sigma));
+        let normal = const_try!(Normal::new(mu, sigma));
         Ok(Self { mu, sigma, normal })
     }
+    /// From the mean m and variance v of the log-normal variable itself:
+    /// σ² = ln(1 + v/m²), μ = ln m − σ²/2.
+    pub fn from_mean_variance(mean: f64, variance: f64) -> Result<Self, DistError> {
+        check_positive("mean", mean)?;
+        check_positive("variance", variance)?;
+        let s2 = (variance / (mean * mean)).ln_1p();
+        Self::new(mean.ln() - 0.5 * s2, s2.sqrt())
+    }
+    pub fn builder() -> LogNormalBuilder {
+        LogNormalBuilder::default()
+    }
+    /// The same distribution evaluating `cdf` and quantiles at `accuracy`, as
+    /// [`Normal::with_accuracy`].
+    pub const fn with_accuracy(self, accuracy: crate::num::Accuracy) -> Self {
+        Self {
+            normal: self.normal.with_accuracy(accuracy),
+            ..self
+        }
+    }
     #[inline]
-    pub fn mu(&self) -> f64 {
+    pub const fn accuracy(&self) -> crate::num::Accuracy {
+        self.normal.accuracy()
+    }
+    #[inline]
+    pub const fn mu(&self) -> f64 {
         self.mu
     }
     #[inline]
-    pub fn sigma(&self) -> f64 {
+    pub const fn sigma(&self) -> f64 {
         self.sigma
     }
 }
 
+/// Builder for [`LogNormal`]; give `mu` and `sigma` of ln X, or the `mean` and `variance`
+/// of X itself (setting either of the latter selects that form).
+///
+/// ```
+/// use probability_rs::dist::lognormal::LogNormal;
+/// let d = LogNormal::builder().mu(0.5).sigma(2.0).build()?;
+/// assert_eq!(d, LogNormal::new(0.5, 2.0)?);
+/// # Ok::<(), probability_rs::dist::DistError>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogNormalBuilder {
+    mu: Option<f64>,
+    sigma: Option<f64>,
+    mean: Option<f64>,
+    variance: Option<f64>,
+}
+
+impl LogNormalBuilder {
+    pub fn mu(mut self, mu: f64) -> Self {
+        self.mu = Some(mu);
+        self
+    }
+    pub fn sigma(mut self, sigma: f64) -> Self {
+        self.sigma = Some(sigma);
+        self
+    }
+    pub fn mean(mut self, mean: f64) -> Self {
+        self.mean = Some(mean);
+        self
+    }
+    pub fn variance(mut self, variance: f64) -> Self {
+        self.variance = Some(variance);
+        self
+    }
+    pub fn build(self) -> Result<LogNormal, DistError> {
+        let missing = |name| DistError::MissingParameter { name };
+        if self.mean.is_some() || self.variance.is_some() {
+            let mean = self.mean.ok_or(missing("mean"))?;
+            let variance = self.variance.ok_or(missing("variance"))?;
+            return LogNormal::from_mean_variance(mean, variance);
+        }
+        let mu = self.mu.ok_or(missing("mu"))?;
+        let sigma = self.sigma.ok_or(missing("sigma"))?;
+        LogNormal::new(mu, sigma)
+    }
+}
+
+impl Default for LogNormal {
+    /// [`LogNormal::standard`].
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl std::fmt::Display for LogNormal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "LogNormal(mu={}, sigma={})",
+            Param(self.mu),
+            Param(self.sigma)
+        )
+    }
+}
+
 impl Distribution for LogNormal {
     type Value = f64;
     fn cdf(&self, x: f64) -> f64 {
@@ -42,6 +140,23 @@ impl Distribution for LogNormal {
     fn sample<R: RngCore>(&self, rng: &mut R) -> f64 {
         (self.normal.sample(rng)).exp()
     }
+    /// Reflects the underlying normal draw.
+    fn sample_antithetic<R: RngCore>(&self, rng: &mut R) -> (f64, f64) {
+        let (a, b) = self.normal.sample_antithetic(rng);
+        (a.exp(), b.exp())
+    }
+}
+
+impl SampleFloat for LogNormal {
+    fn sample_float<F: Float, R: RngCore>(&self, rng: &mut R) -> F {
+        self.normal.sample_float::<F, R>(rng).exp()
+    }
+    fn sample_fill_float<F: Float, R: RngCore>(&self, rng: &mut R, out: &mut [F]) {
+        self.normal.sample_fill_float(rng, out);
+        for x in out {
+            *x = x.exp();
+        }
+    }
 }
 
 impl Continuous for LogNormal {
@@ -52,9 +167,16 @@ impl Continuous for LogNormal {
         let z = x.ln();
         self.normal.pdf(z) / x
     }
-    fn inv_cdf(&self, p: f64) -> f64 {
-        debug_assert!((0.0..=1.0).contains(&p));
-        self.normal.inv_cdf(p).exp()
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+    fn try_inv_cdf(&self, p: f64) -> Result<f64, DistError> {
+        // exp maps the normal's ±inf at p = 0, 1 onto the support bounds 0 and +inf.
+        Ok(self.normal.try_inv_cdf(p)?.exp())
+    }
+
+    fn try_isf(&self, q: f64) -> Result<f64, DistError> {
+        Ok(self.normal.try_isf(q)?.exp())
     }
 }
 
@@ -64,17 +186,17 @@ impl Moments for LogNormal {
     }
     fn variance(&self) -> f64 {
         let s2 = self.sigma * self.sigma;
-        ((2.0 * s2).exp() - (s2).exp()) * (self.mu + s2).exp()
+        s2.exp_m1() * (2.0 * self.mu + s2).exp()
     }
     fn skewness(&self) -> f64 {
-        let s2 = self.sigma * self.sigma;
-        let es2 = s2.exp();
-        (es2 + 2.0) * (es2 - 1.0).sqrt()
+        // (e^{σ²} + 2) sqrt(e^{σ²} − 1), in m = e^{σ²} − 1 to keep small σ accurate
+        let m = (self.sigma * self.sigma).exp_m1();
+        (m + 3.0) * m.sqrt()
     }
     fn kurtosis(&self) -> f64 {
-        let s2 = self.sigma * self.sigma;
-        let es2 = s2.exp();
-        es2.powi(4) + 2.0 * es2.powi(3) + 3.0 * es2.powi(2) - 6.0
+        // e^{4σ²} + 2e^{3σ²} + 3e^{2σ²} − 6 = 16m + 15m² + 6m³ + m⁴
+        let m = (self.sigma * self.sigma).exp_m1();
+        m * (16.0 + m * (15.0 + m * (6.0 + m)))
     }
     fn entropy(&self) -> f64 {
         // H = mu + 0.5 * ln(2π e σ^2)
@@ -84,10 +206,41 @@ impl Moments for LogNormal {
     }
 }
 
+impl CharacteristicFunction for LogNormal {
+    fn cf(&self, t: f64) -> (f64, f64) {
+        // No closed form exists; integrate numerically.
+        super::numerical_cf(self, t)
+    }
+}
+
+impl HigherMoments for LogNormal {
+    fn raw_moment(&self, n: u32) -> f64 {
+        let n = n as f64;
+        (n * self.mu + 0.5 * n * n * self.sigma * self.sigma).exp()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
+    fn builder_forms() {
+        let d = LogNormal::builder()
+            .mean(2.0)
+            .variance(1.0)
+            .build()
+            .unwrap();
+        assert_eq!(d, LogNormal::from_mean_variance(2.0, 1.0).unwrap());
+        assert_eq!(
+            LogNormal::builder().mu(0.0).mean(2.0).build().unwrap_err(),
+            DistError::MissingParameter { name: "variance" }
+        );
+        assert_eq!(
+            LogNormal::builder().mu(0.0).build().unwrap_err(),
+            DistError::MissingParameter { name: "sigma" }
+        );
+    }
+    #[test]
     fn pdf_cdf_basic() {
         let ln = LogNormal::new(0.0, 1.0).unwrap();
         assert!(ln.pdf(1.0) > 0.0);
@@ -103,4 +256,29 @@ mod tests {
         assert!((ln.skewness() - skew).abs() < 1e-12);
         assert!((ln.kurtosis() - kurt).abs() < 1e-12);
     }
+
+    #[test]
+    fn cf_numerical_is_normalised() {
+        let ln = LogNormal::new(0.0, 0.25).unwrap();
+        let (re, im) = ln.cf(0.0);
+        assert!((re - 1.0).abs() < 1e-12 && im.abs() < 1e-12);
+        // |φ(t)| <= 1
+        let (re, im) = ln.cf(2.0);
+        assert!(re.hypot(im) <= 1.0 + 1e-12);
+    }
+
+    #[test]
+    fn from_mean_variance_recovers_moments() {
+        let d = LogNormal::from_mean_variance(3.0, 2.0).unwrap();
+        assert!((d.mean() - 3.0).abs() < 1e-12);
+        assert!((d.variance() - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn shape_moments_small_sigma() {
+        // mpmath with σ² = 1e-8
+        let ln = LogNormal::new(0.0, 1e-4).unwrap();
+        assert!((ln.skewness() / 3.0000000175e-4 - 1.0).abs() < 1e-12);
+        assert!((ln.kurtosis() / 1.600000023e-7 - 1.0).abs() < 1e-12);
+    }
 }
@@ -1,18 +1,51 @@
 //! Collection of probability distributions.
 //! This module groups all distribution implementations under `dist`.
+//!
+//! # API stability tiers
+//! - Core, sealed: [`Distribution`], [`Continuous`], [`Discrete`]. Only this crate
+//!   implements them, so new methods (with defaults) are not breaking changes.
+//!   Downstream distributions go through [`extension::Extension`].
+//! - Capabilities, open: [`Moments`], [`HigherMoments`], [`Mgf`], [`CharacteristicFunction`],
+//!   [`SampleFloat`].
+//!   Implement them directly on your own types; required methods only change in a major release.
+//! - Helpers: free functions such as [`numerical_cf`] and [`numerical_moment`]; their
+//!   numerical accuracy may improve between minor releases.
+use crate::num::Float;
 use crate::rng;
 
+/// `?` for `const fn`: returns the error early, otherwise yields the `Ok` value.
+macro_rules! const_try {
+    ($e:expr) => {
+        match $e {
+            Ok(v) => v,
+            Err(e) => return Err(e),
+        }
+    };
+}
+
+pub mod any;
 pub mod bernoulli;
 pub mod beta;
 pub mod binomial;
 pub mod chisquared;
+pub mod compare;
+pub mod divergence;
+pub mod dynamic;
 pub mod exponential;
+pub mod extension;
 pub mod gamma;
 pub mod geometric;
 pub mod lognormal;
+pub mod negative_binomial;
 pub mod normal;
+mod numeric;
+pub mod parallel;
 pub mod poisson;
+pub mod sampler;
+#[cfg(feature = "serde")]
+mod serde_impl;
 pub mod uniform;
+mod ziggurat;
 /// Basic moments available for a distribution.
 pub trait Moments {
     fn mean(&self) -> f64;
@@ -32,31 +65,501 @@ pub trait Moments {
     fn entropy(&self) -> f64;
 }
 
+/// Moment generating function M(t) = E[e^{tX}] and cumulant generating function K(t) = ln M(t).
+///
+/// Outside the domain where the expectation is finite both return `f64::INFINITY`;
+/// a NaN argument yields NaN.
+pub trait Mgf {
+    fn mgf(&self, t: f64) -> f64;
+    fn cgf(&self, t: f64) -> f64 {
+        self.mgf(t).ln()
+    }
+}
+
+/// Characteristic function φ(t) = E[e^{itX}], returned as `(re, im)`.
+///
+/// Closed forms are used where known; other distributions rely on [`numerical_cf`].
+pub trait CharacteristicFunction {
+    fn cf(&self, t: f64) -> (f64, f64);
+}
+
+/// Numerical characteristic function of any continuous distribution.
+///
+/// Integrates `e^{itF⁻¹(u)}` over u ∈ (0,1) with panelled Gauss–Legendre quadrature,
+/// refining towards the tails. Accuracy is limited by the quantile function and
+/// degrades for large |t|·scale (fast oscillation).
+pub fn numerical_cf<D: Continuous + ?Sized>(d: &D, t: f64) -> (f64, f64) {
+    let re = numeric::quantile_expectation(d, |x| (t * x).cos());
+    let im = numeric::quantile_expectation(d, |x| (t * x).sin());
+    (re, im)
+}
+
+/// Raw moments E[X^n] and central moments E[(X − μ)^n] of any order.
+///
+/// Closed forms are used where known; [`numerical_moment`] and [`summed_moment`]
+/// are the generic fallbacks.
+pub trait HigherMoments: Moments {
+    fn raw_moment(&self, n: u32) -> f64;
+    /// Defaults to the binomial expansion Σ C(n,k) E[X^k] (−μ)^{n−k}; implementations
+    /// override it when a direct form avoids the cancellation.
+    fn central_moment(&self, n: u32) -> f64 {
+        let neg_mu = -self.mean();
+        let mut binom = 1.0;
+        let mut sum = 0.0;
+        for k in 0..=n {
+            sum += binom * self.raw_moment(k) * neg_mu.powi((n - k) as i32);
+            binom = binom * (n - k) as f64 / (k + 1) as f64;
+        }
+        sum
+    }
+}
+
+/// Draws computed at the precision of `F` (`f32` or `f64`): the uniforms come from
+/// [`RngCore::next_float`](rng::RngCore::next_float) and the transform runs in `F`, so an
+/// `f32` simulation never widens to `f64`. Implemented by Uniform, Exponential, Normal and
+/// LogNormal; the streams differ from [`Distribution::sample`], which uses the ziggurat.
+pub trait SampleFloat {
+    fn sample_float<F: Float, R: rng::RngCore>(&self, rng: &mut R) -> F;
+    /// Fills `out` with independent draws.
+    fn sample_fill_float<F: Float, R: rng::RngCore>(&self, rng: &mut R, out: &mut [F]) {
+        for x in out {
+            *x = self.sample_float(rng);
+        }
+    }
+}
+
+/// E[(X − c)^n] of a continuous distribution by quadrature in probability space.
+pub fn numerical_moment<D: Continuous + ?Sized>(d: &D, n: u32, c: f64) -> f64 {
+    numeric::quantile_expectation(d, |x| (x - c).powi(n as i32))
+}
+
+/// E[(X − c)^n] of a discrete distribution by summing over its support; NaN if the sum
+/// needs more than ten million terms.
+pub fn summed_moment<D: Discrete + ?Sized>(d: &D, n: u32, c: f64) -> f64 {
+    numeric::pmf_expectation(d, |k| (k as f64 - c).powi(n as i32))
+}
+
+pub use compare::{ApproxEq, max_cdf_diff, max_cdf_diff_discrete};
+pub use dynamic::{ContinuousDyn, DiscreteDyn};
+
+mod private {
+    use super::*;
+
+    /// Seals the core traits; see [`extension`](super::extension) for third-party types.
+    pub trait Sealed {}
+
+    impl Sealed for any::AnyContinuous {}
+    impl Sealed for any::AnyDiscrete {}
+    impl Sealed for bernoulli::Bernoulli {}
+    impl Sealed for beta::Beta {}
+    impl Sealed for binomial::Binomial {}
+    impl Sealed for chisquared::ChiSquared {}
+    impl Sealed for exponential::Exponential {}
+    impl Sealed for gamma::Gamma {}
+    impl Sealed for geometric::Geometric {}
+    impl Sealed for lognormal::LogNormal {}
+    impl Sealed for negative_binomial::NegativeBinomial {}
+    impl Sealed for normal::Normal {}
+    impl Sealed for poisson::Poisson {}
+    impl Sealed for uniform::Uniform {}
+    impl<D: Distribution + ?Sized> Sealed for &D {}
+}
+
 /// Basic trait for distributions.
-pub trait Distribution {
+///
+/// Sealed: implement [`extension::ExtendedDistribution`] for types outside this crate.
+pub trait Distribution: private::Sealed {
     type Value;
     fn cdf(&self, x: Self::Value) -> f64;
     fn sample<R: rng::RngCore>(&self, rng: &mut R) -> Self::Value;
     fn in_support(&self, x: Self::Value) -> bool;
+    /// Fills `out` with independent draws.
+    ///
+    /// Overrides may consume the generator differently from repeated [`sample`](Self::sample)
+    /// calls (e.g. using both outputs of a pair), so the values need not match draw-for-draw.
+    fn sample_fill<R: rng::RngCore>(&self, rng: &mut R, out: &mut [Self::Value]) {
+        for x in out {
+            *x = self.sample(rng);
+        }
+    }
+    /// An antithetic pair: `sample`, then `sample` again on the complemented uniforms (see
+    /// [`rng::antithetic`]). Symmetric distributions override this to reflect the draw.
+    fn sample_antithetic<R: rng::RngCore>(&self, rng: &mut R) -> (Self::Value, Self::Value) {
+        let mut a = rng::antithetic::Antithetic::new(rng);
+        let x = self.sample(&mut a);
+        a.flip();
+        (x, self.sample(&mut a))
+    }
+    /// Writes `cdf(xs[i])` to `out[i]`. Panics if the lengths differ. Normal, Exponential
+    /// and Uniform override this and `pdf_slice` with loops that hoist their parameters.
+    fn cdf_slice(&self, xs: &[Self::Value], out: &mut [f64])
+    where
+        Self::Value: Copy,
+    {
+        assert_eq!(xs.len(), out.len(), "cdf_slice: length mismatch");
+        for (o, &x) in out.iter_mut().zip(xs) {
+            *o = self.cdf(x);
+        }
+    }
+    /// Endless iterator of draws; bound it with `take`. Pass `&dist` and `&mut rng` to keep
+    /// using both afterwards.
+    fn sample_iter<R: rng::RngCore>(self, rng: R) -> SampleIter<Self, R>
+    where
+        Self: Sized,
+    {
+        SampleIter { dist: self, rng }
+    }
+}
+
+impl<D: Distribution + ?Sized> Distribution for &D {
+    type Value = D::Value;
+    #[inline]
+    fn cdf(&self, x: Self::Value) -> f64 {
+        (**self).cdf(x)
+    }
+    #[inline]
+    fn sample<R: rng::RngCore>(&self, rng: &mut R) -> Self::Value {
+        (**self).sample(rng)
+    }
+    #[inline]
+    fn in_support(&self, x: Self::Value) -> bool {
+        (**self).in_support(x)
+    }
+    #[inline]
+    fn sample_fill<R: rng::RngCore>(&self, rng: &mut R, out: &mut [Self::Value]) {
+        (**self).sample_fill(rng, out)
+    }
+    #[inline]
+    fn sample_antithetic<R: rng::RngCore>(&self, rng: &mut R) -> (Self::Value, Self::Value) {
+        (**self).sample_antithetic(rng)
+    }
+    #[inline]
+    fn cdf_slice(&self, xs: &[Self::Value], out: &mut [f64])
+    where
+        Self::Value: Copy,
+    {
+        (**self).cdf_slice(xs, out)
+    }
+}
+
+/// Iterator returned by [`Distribution::sample_iter`].
+#[derive(Debug, Clone)]
+pub struct SampleIter<D, R> {
+    dist: D,
+    rng: R,
 }
 
+impl<D: Distribution, R: rng::RngCore> Iterator for SampleIter<D, R> {
+    type Item = D::Value;
+    #[inline]
+    fn next(&mut self) -> Option<D::Value> {
+        Some(self.dist.sample(&mut self.rng))
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<D: Distribution, R: rng::RngCore> std::iter::FusedIterator for SampleIter<D, R> {}
+
 /// Trait for continuous real-valued distributions.
 pub trait Continuous: Distribution<Value = f64> {
     /// Returns f(x) (density / pdf).
     fn pdf(&self, x: f64) -> f64;
-    /// Quantile: F^{-1}(p) for p in (0,1).
-    fn inv_cdf(&self, p: f64) -> f64;
+    /// Writes `pdf(xs[i])` to `out[i]`. Panics if the lengths differ.
+    fn pdf_slice(&self, xs: &[f64], out: &mut [f64]) {
+        assert_eq!(xs.len(), out.len(), "pdf_slice: length mismatch");
+        for (o, &x) in out.iter_mut().zip(xs) {
+            *o = self.pdf(x);
+        }
+    }
+    /// Support bounds `(lo, hi)`, possibly infinite. Defaults to the whole real line.
+    fn support(&self) -> (f64, f64) {
+        (f64::NEG_INFINITY, f64::INFINITY)
+    }
+    /// Quantile F^{-1}(p) for p in `[0,1]`; p = 0 and p = 1 give the support bounds
+    /// (possibly infinite). Other p, including NaN, yield [`DistError::DomainError`].
+    ///
+    /// The default brackets the quantile from [`support`](Self::support) and solves
+    /// `cdf(x) = p` with [`num::roots::brent`](crate::num::roots::brent), so it is accurate
+    /// to the cdf's absolute error; types with a closed form or a tail-accurate solver
+    /// override it.
+    fn try_inv_cdf(&self, p: f64) -> Result<f64, DistError> {
+        let p = check_probability(p)?;
+        Ok(numeric::bracketed_quantile(
+            |x| self.cdf(x),
+            self.support(),
+            p,
+        ))
+    }
+    /// Panicking form of [`try_inv_cdf`](Self::try_inv_cdf).
+    fn inv_cdf(&self, p: f64) -> f64 {
+        match self.try_inv_cdf(p) {
+            Ok(x) => x,
+            Err(e) => panic!("inv_cdf({p}): {e:?}"),
+        }
+    }
+    /// Writes `inv_cdf(ps[i])` to `out[i]`. Panics if the lengths differ or any p is
+    /// outside `[0,1]`.
+    fn inv_cdf_slice(&self, ps: &[f64], out: &mut [f64]) {
+        assert_eq!(ps.len(), out.len(), "inv_cdf_slice: length mismatch");
+        for (o, &p) in out.iter_mut().zip(ps) {
+            *o = self.inv_cdf(p);
+        }
+    }
+    /// Inverse survival function: the x with P(X > x) = q, i.e. `inv_cdf(1 - q)` without
+    /// rounding `1 - q` to 1 for tiny q. The default does round; Normal, LogNormal,
+    /// Exponential, Uniform, Gamma, Chi-squared and Beta override it with a tail-accurate form.
+    fn try_isf(&self, q: f64) -> Result<f64, DistError> {
+        self.try_inv_cdf(1.0 - check_probability(q)?)
+    }
+    /// Panicking form of [`try_isf`](Self::try_isf).
+    fn isf(&self, q: f64) -> f64 {
+        match self.try_isf(q) {
+            Ok(x) => x,
+            Err(e) => panic!("isf({q}): {e:?}"),
+        }
+    }
+    /// Equal-tailed interval holding probability `mass`, e.g. `interval(0.95)`.
+    /// Panics unless `mass` is in `[0,1]`.
+    fn interval(&self, mass: f64) -> (f64, f64) {
+        let tail = 0.5 * (1.0 - mass);
+        (self.inv_cdf(tail), self.inv_cdf(1.0 - tail))
+    }
+    /// Interquartile range Q(0.75) − Q(0.25).
+    fn iqr(&self) -> f64 {
+        self.inv_cdf(0.75) - self.inv_cdf(0.25)
+    }
+    /// Quantiles at each of `ps`.
+    fn quantiles(&self, ps: &[f64]) -> Vec<f64> {
+        ps.iter().map(|&p| self.inv_cdf(p)).collect()
+    }
+    /// `n` draws, one per probability stratum `(i/n, (i+1)/n)`, via
+    /// [`rng::stratified::stratified_uniforms`] and `inv_cdf`; sorted ascending.
+    fn sample_stratified<R: rng::RngCore>(&self, n: usize, rng: &mut R) -> Vec<f64> {
+        let mut u = rng::stratified::stratified_uniforms(n, rng);
+        for x in &mut u {
+            *x = self.inv_cdf(*x);
+        }
+        u
+    }
 }
 
 /// Trait for discrete distributions over {0,1} or small integers.
 pub trait Discrete: Distribution<Value = i64> {
     /// pmf(x)
     fn pmf(&self, x: Self::Value) -> f64;
-    fn inv_cdf(&self, p: f64) -> Self::Value;
+    /// Writes `pmf(ks[i])` to `out[i]`. Panics if the lengths differ.
+    fn pmf_slice(&self, ks: &[i64], out: &mut [f64]) {
+        assert_eq!(ks.len(), out.len(), "pmf_slice: length mismatch");
+        for (o, &k) in out.iter_mut().zip(ks) {
+            *o = self.pmf(k);
+        }
+    }
+    /// ln pmf(x); overrides stay finite where [`pmf`](Self::pmf) underflows.
+    fn ln_pmf(&self, x: Self::Value) -> f64 {
+        self.pmf(x).ln()
+    }
+    /// Smallest k with F(k) >= p, for p in `[0,1]`. p = 0 gives the lowest support point and
+    /// p = 1 the highest (`i64::MAX` for unbounded support); other p yield
+    /// [`DistError::DomainError`].
+    fn try_inv_cdf(&self, p: f64) -> Result<Self::Value, DistError>;
+    /// Panicking form of [`try_inv_cdf`](Self::try_inv_cdf).
+    fn inv_cdf(&self, p: f64) -> Self::Value {
+        match self.try_inv_cdf(p) {
+            Ok(k) => k,
+            Err(e) => panic!("inv_cdf({p}): {e:?}"),
+        }
+    }
+    /// Smallest k with P(X > k) <= q, i.e. `inv_cdf(1 - q)` searched against
+    /// [`sf`](Self::sf) so tiny q keep their precision. The default gallops up from the
+    /// median and bisects, so it costs O(log k) `sf` calls however deep the tail.
+    fn try_isf(&self, q: f64) -> Result<Self::Value, DistError> {
+        numeric::galloping_isf(|p| self.try_inv_cdf(p), |k| self.sf(k), q)
+    }
+    /// Panicking form of [`try_isf`](Self::try_isf).
+    fn isf(&self, q: f64) -> Self::Value {
+        match self.try_isf(q) {
+            Ok(k) => k,
+            Err(e) => panic!("isf({q}): {e:?}"),
+        }
+    }
+    /// Survival function P(X > k), accurate in the upper tail where `1 - cdf(k)` loses
+    /// all precision. The default sums the pmf above `k` once `cdf(k) > 0.5`, and is NaN if
+    /// that takes more than ten million terms.
+    fn sf(&self, k: Self::Value) -> f64 {
+        let c = self.cdf(k);
+        if c <= 0.5 {
+            1.0 - c
+        } else {
+            numeric::upper_tail_sum(|j| self.pmf(j), k)
+        }
+    }
+    /// ln P(X > k); overrides stay finite where [`sf`](Self::sf) underflows.
+    fn ln_sf(&self, k: Self::Value) -> f64 {
+        self.sf(k).ln()
+    }
+    /// Equal-tailed interval `(lo, hi)` with `P(lo <= X <= hi) >= mass`.
+    /// Panics unless `mass` is in `[0,1]`.
+    fn interval(&self, mass: f64) -> (Self::Value, Self::Value) {
+        let tail = 0.5 * (1.0 - mass);
+        (self.inv_cdf(tail), self.inv_cdf(1.0 - tail))
+    }
+    /// Interquartile range Q(0.75) − Q(0.25).
+    fn iqr(&self) -> Self::Value {
+        self.inv_cdf(0.75) - self.inv_cdf(0.25)
+    }
+    /// Quantiles at each of `ps`.
+    fn quantiles(&self, ps: &[f64]) -> Vec<Self::Value> {
+        ps.iter().map(|&p| self.inv_cdf(p)).collect()
+    }
+    /// `n` draws, one per probability stratum, as for
+    /// [`Continuous::sample_stratified`]; sorted ascending.
+    fn sample_stratified<R: rng::RngCore>(&self, n: usize, rng: &mut R) -> Vec<i64> {
+        let u = rng::stratified::stratified_uniforms(n, rng);
+        self.quantiles(&u)
+    }
+    /// Probability generating function G(s) = E[s^X].
+    /// Returns `f64::INFINITY` where the series diverges to +inf and NaN where it oscillates.
+    fn pgf(&self, s: f64) -> f64;
+    /// ln G(s), defined where G(s) > 0.
+    fn ln_pgf(&self, s: f64) -> f64 {
+        self.pgf(s).ln()
+    }
 }
 
-/// Error returned when constructing distributions with invalid parameters.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Error returned when constructing distributions with invalid parameters, or when an
+/// argument lies outside a function's domain.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
 pub enum DistError {
-    InvalidParameter,
+    /// A parameter is NaN or infinite.
+    NotFinite { name: &'static str },
+    /// A parameter that must be strictly positive is not.
+    NonPositive { name: &'static str, value: f64 },
+    /// A probability parameter lies outside its allowed range (`[0,1]` or `(0,1]`).
+    ProbabilityOutOfRange { name: &'static str, value: f64 },
+    /// An interval whose lower end is not below its upper end.
+    InvalidRange { low: f64, high: f64 },
+    /// A builder was finished without setting a required parameter.
+    MissingParameter { name: &'static str },
+    /// An argument lies outside the function's domain (e.g. a probability outside `[0,1]`).
+    DomainError,
+}
+
+impl std::fmt::Display for DistError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            DistError::NotFinite { name } => write!(f, "parameter `{name}` must be finite"),
+            DistError::NonPositive { name, value } => {
+                write!(f, "parameter `{name}` must be positive, got {value}")
+            }
+            DistError::ProbabilityOutOfRange { name, value } => {
+                write!(
+                    f,
+                    "parameter `{name}` is not an allowed probability: {value}"
+                )
+            }
+            DistError::InvalidRange { low, high } => {
+                write!(f, "invalid range: low ({low}) must be below high ({high})")
+            }
+            DistError::MissingParameter { name } => write!(f, "parameter `{name}` was not set"),
+            DistError::DomainError => f.write_str("argument outside the function's domain"),
+        }
+    }
+}
+
+impl std::error::Error for DistError {}
+
+/// `Ok` if `value` is finite.
+#[inline]
+pub(crate) const fn check_finite(name: &'static str, value: f64) -> Result<(), DistError> {
+    if value.is_finite() {
+        Ok(())
+    } else {
+        Err(DistError::NotFinite { name })
+    }
+}
+
+/// `Ok` if `value` is finite and strictly positive.
+#[inline]
+pub(crate) const fn check_positive(name: &'static str, value: f64) -> Result<(), DistError> {
+    if !value.is_finite() {
+        Err(DistError::NotFinite { name })
+    } else if value > 0.0 {
+        Ok(())
+    } else {
+        Err(DistError::NonPositive { name, value })
+    }
+}
+
+/// `Ok` if `value` lies in `[0,1]`, or in `(0,1]` when `allow_zero` is false.
+#[inline]
+pub(crate) const fn check_unit(
+    name: &'static str,
+    value: f64,
+    allow_zero: bool,
+) -> Result<(), DistError> {
+    if value.is_nan() {
+        Err(DistError::NotFinite { name })
+    } else if value <= 1.0 && (value > 0.0 || (allow_zero && value == 0.0)) {
+        Ok(())
+    } else {
+        Err(DistError::ProbabilityOutOfRange { name, value })
+    }
+}
+
+/// Unwraps a constructor result in `const` context, so distributions with `const fn`
+/// constructors can be `const` or `static` items; invalid parameters fail the build.
+///
+/// Every `new` is `const` except Gamma's, Beta's and ChiSquared's, which precompute a
+/// log-gamma normalizer, and `ln Γ` cannot run at compile time.
+///
+/// ```
+/// use probability_rs::dist::{const_unwrap, normal::Normal};
+/// static STANDARD: Normal = const_unwrap(Normal::new(0.0, 1.0));
+/// assert_eq!(STANDARD.sigma(), 1.0);
+/// ```
+pub const fn const_unwrap<T: Copy>(r: Result<T, DistError>) -> T {
+    match r {
+        Ok(v) => v,
+        Err(DistError::NotFinite { .. }) => panic!("invalid distribution: parameter not finite"),
+        Err(DistError::NonPositive { .. }) => {
+            panic!("invalid distribution: parameter must be positive")
+        }
+        Err(DistError::ProbabilityOutOfRange { .. }) => {
+            panic!("invalid distribution: probability out of range")
+        }
+        Err(DistError::InvalidRange { .. }) => panic!("invalid distribution: empty range"),
+        Err(DistError::MissingParameter { .. }) => {
+            panic!("invalid distribution: missing parameter")
+        }
+        Err(DistError::DomainError) => panic!("argument outside domain"),
+    }
+}
+
+/// Formats a parameter with the shortest round-tripping representation, switching to
+/// exponent notation for very large or small magnitudes.
+pub(crate) struct Param(pub(crate) f64);
+
+impl std::fmt::Display for Param {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let a = self.0.abs();
+        if a == 0.0 || !a.is_finite() || (1e-5..1e16).contains(&a) {
+            write!(f, "{}", self.0)
+        } else {
+            write!(f, "{:e}", self.0)
+        }
+    }
+}
+
+/// Returns `p` if it is a probability in [0,1].
+#[inline]
+pub(crate) fn check_probability(p: f64) -> Result<f64, DistError> {
+    if (0.0..=1.0).contains(&p) {
+        Ok(p)
+    } else {
+        Err(DistError::DomainError)
+    }
 }
@@ -0,0 +1,376 @@
+use super::gamma::Gamma;
+use super::poisson::Poisson;
+use crate::dist::{
+    CharacteristicFunction, Discrete, DistError, Distribution, HigherMoments, Mgf, Moments, Param,
+    check_positive, check_probability, check_unit,
+};
+use crate::num::sum::Kahan;
+use crate::num::{self, betainc, ln_betainc, ln_gamma};
+use crate::rng::RngCore;
+
+/// NegativeBinomial(r, p): number of failures before the r-th success, k = 0,1,2,...
+///
+/// `r > 0` may be non-integer (Pólya distribution).
+/// - Mean = r(1-p)/p
+/// - Var = r(1-p)/p²
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NegativeBinomial {
+    r: f64,
+    p: f64,
+}
+
+impl NegativeBinomial {
+    pub const fn new(r: f64, p: f64) -> Result<Self, DistError> {
+        const_try!(check_positive("r", r));
+        const_try!(check_unit("p", p, false));
+        Ok(Self { r, p })
+    }
+    /// Mean μ and dispersion (size) r, as in R's `dnbinom(mu = , size = )`:
+    /// p = r/(r + μ) and Var = μ + μ²/r.
+    pub fn from_mean_dispersion(mean: f64, dispersion: f64) -> Result<Self, DistError> {
+        check_positive("mean", mean)?;
+        check_positive("dispersion", dispersion)?;
+        Self::new(dispersion, dispersion / (dispersion + mean))
+    }
+    pub fn builder() -> NegativeBinomialBuilder {
+        NegativeBinomialBuilder::default()
+    }
+    #[inline]
+    pub const fn r(&self) -> f64 {
+        self.r
+    }
+    #[inline]
+    pub const fn p(&self) -> f64 {
+        self.p
+    }
+}
+
+/// Builder for [`NegativeBinomial`]; give `r` (R's `size`, the dispersion) and either the
+/// success probability `p` or the mean (the last one set wins).
+///
+/// ```
+/// use probability_rs::dist::negative_binomial::NegativeBinomial;
+/// let d = NegativeBinomial::builder().r(2.0).mean(6.0).build()?;
+/// assert_eq!(d.p(), 0.25);
+/// # Ok::<(), probability_rs::dist::DistError>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NegativeBinomialBuilder {
+    r: Option<f64>,
+    p_or_mean: Option<POrMean>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum POrMean {
+    P(f64),
+    Mean(f64),
+}
+
+impl NegativeBinomialBuilder {
+    pub fn r(mut self, r: f64) -> Self {
+        self.r = Some(r);
+        self
+    }
+    pub fn p(mut self, p: f64) -> Self {
+        self.p_or_mean = Some(POrMean::P(p));
+        self
+    }
+    pub fn mean(mut self, mean: f64) -> Self {
+        self.p_or_mean = Some(POrMean::Mean(mean));
+        self
+    }
+    pub fn build(self) -> Result<NegativeBinomial, DistError> {
+        let r = self.r.ok_or(DistError::MissingParameter { name: "r" })?;
+        match self.p_or_mean {
+            None => Err(DistError::MissingParameter { name: "p" }),
+            Some(POrMean::P(p)) => NegativeBinomial::new(r, p),
+            Some(POrMean::Mean(mean)) => NegativeBinomial::from_mean_dispersion(mean, r),
+        }
+    }
+}
+
+impl std::fmt::Display for NegativeBinomial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "NegativeBinomial(r={}, p={})",
+            Param(self.r),
+            Param(self.p)
+        )
+    }
+}
+
+impl Distribution for NegativeBinomial {
+    type Value = i64;
+    fn cdf(&self, k: i64) -> f64 {
+        if k < 0 {
+            return 0.0;
+        }
+        // P(X <= k) = I_p(r, k+1)
+        betainc(self.r, k as f64 + 1.0, self.p)
+    }
+    fn in_support(&self, k: i64) -> bool {
+        k >= 0
+    }
+    fn sample<R: RngCore>(&self, rng: &mut R) -> i64 {
+        // Gamma–Poisson mixture: λ ~ Gamma(r, (1-p)/p), X | λ ~ Poisson(λ)
+        if self.p == 1.0 {
+            return 0;
+        }
+        let lambda = Gamma::new(self.r, (1.0 - self.p) / self.p)
+            .unwrap()
+            .sample(rng);
+        match Poisson::new(lambda) {
+            Ok(pois) => pois.sample(rng),
+            Err(_) => 0,
+        }
+    }
+}
+
+impl Discrete for NegativeBinomial {
+    fn pmf(&self, k: i64) -> f64 {
+        if k < 0 {
+            return 0.0;
+        }
+        if self.p == 1.0 {
+            return if k == 0 { 1.0 } else { 0.0 };
+        }
+        let k = k as f64;
+        (ln_gamma(k + self.r) - ln_gamma(k + 1.0) - ln_gamma(self.r)
+            + self.r * self.p.ln()
+            + k * (1.0 - self.p).ln())
+        .exp()
+    }
+    fn try_inv_cdf(&self, p: f64) -> Result<i64, DistError> {
+        let p = check_probability(p)?;
+        if p == 0.0 || self.p == 1.0 {
+            return Ok(0);
+        }
+        if p == 1.0 {
+            return Ok(i64::MAX);
+        }
+        // ln p(k+1) = ln p(k) + ln((k + r)/(k + 1)) + ln(1-p), in logs because p(0) = p^r
+        // underflows for large r long before the bulk of the mass is reached. A zero term
+        // only ends the walk past the mode, where the terms are shrinking.
+        let ln_q = (-self.p).ln_1p();
+        let mode = (self.r - 1.0) * (1.0 - self.p) / self.p;
+        let mut k: i64 = 0;
+        let mut ln_pk = self.r * self.p.ln();
+        let mut acc = Kahan::new();
+        acc += ln_pk.exp();
+        while acc.sum() < p {
+            ln_pk += ((self.r - 1.0) / (k as f64 + 1.0)).ln_1p() + ln_q;
+            k += 1;
+            let pk = ln_pk.exp();
+            if pk == 0.0 && k as f64 > mode {
+                break;
+            }
+            acc += pk;
+        }
+        Ok(k)
+    }
+    fn sf(&self, k: i64) -> f64 {
+        // P(X > k) = I_{1−p}(k + 1, r)
+        if k < 0 {
+            return 1.0;
+        }
+        betainc(k as f64 + 1.0, self.r, 1.0 - self.p)
+    }
+    fn ln_sf(&self, k: i64) -> f64 {
+        if k < 0 {
+            return 0.0;
+        }
+        ln_betainc(k as f64 + 1.0, self.r, 1.0 - self.p)
+    }
+
+    fn pgf(&self, s: f64) -> f64 {
+        // (p / (1 - (1-p) s))^r for |s| < 1/(1-p)
+        let q = 1.0 - self.p;
+        let den = 1.0 - q * s;
+        if den > 0.0 && den < 2.0 {
+            (self.p / den).powf(self.r)
+        } else if den <= 0.0 {
+            f64::INFINITY
+        } else {
+            f64::NAN
+        }
+    }
+    fn ln_pgf(&self, s: f64) -> f64 {
+        let den = 1.0 - (1.0 - self.p) * s;
+        if den > 0.0 && den < 2.0 {
+            self.r * (self.p.ln() - den.ln())
+        } else {
+            self.pgf(s).ln()
+        }
+    }
+}
+
+impl Moments for NegativeBinomial {
+    fn mean(&self) -> f64 {
+        self.r * (1.0 - self.p) / self.p
+    }
+    fn variance(&self) -> f64 {
+        self.r * (1.0 - self.p) / (self.p * self.p)
+    }
+    fn skewness(&self) -> f64 {
+        let p = self.p;
+        (2.0 - p) / ((1.0 - p) * self.r).sqrt()
+    }
+    fn kurtosis(&self) -> f64 {
+        let p = self.p;
+        6.0 / self.r + p * p / ((1.0 - p) * self.r)
+    }
+    fn entropy(&self) -> f64 {
+        // Shannon entropy by summation until the remaining mass is negligible.
+        if self.p == 1.0 {
+            return 0.0;
+        }
+        let mut h = Kahan::new();
+        let mut mass = Kahan::new();
+        let mut k: i64 = 0;
+        let upper = self.mean() + 40.0 * self.variance().sqrt() + 50.0;
+        while mass.sum() < 1.0 - 1e-15 && (k as f64) < upper {
+            let pk = self.pmf(k);
+            if pk > 0.0 {
+                h += -pk * pk.ln();
+            }
+            mass += pk;
+            k += 1;
+        }
+        h.sum()
+    }
+}
+
+impl Mgf for NegativeBinomial {
+    fn mgf(&self, t: f64) -> f64 {
+        self.cgf(t).exp()
+    }
+    fn cgf(&self, t: f64) -> f64 {
+        // K(t) = r (ln p - ln(1 - (1-p) e^t)) for t < -ln(1-p)
+        let q = 1.0 - self.p;
+        if q == 0.0 {
+            return 0.0;
+        }
+        let s = t + q.ln();
+        if s < 0.0 {
+            self.r * (self.p.ln() - num::log1mexp(s))
+        } else if t.is_nan() {
+            f64::NAN
+        } else {
+            f64::INFINITY
+        }
+    }
+}
+
+impl CharacteristicFunction for NegativeBinomial {
+    fn cf(&self, t: f64) -> (f64, f64) {
+        // (p / (1 - (1-p) e^{it}))^r in polar form
+        let q = 1.0 - self.p;
+        let (den_re, den_im) = (1.0 - q * t.cos(), -q * t.sin());
+        let m = (self.p / den_re.hypot(den_im)).powf(self.r);
+        let arg = -self.r * den_im.atan2(den_re);
+        (m * arg.cos(), m * arg.sin())
+    }
+}
+
+impl HigherMoments for NegativeBinomial {
+    fn raw_moment(&self, n: u32) -> f64 {
+        super::summed_moment(self, n, 0.0)
+    }
+    fn central_moment(&self, n: u32) -> f64 {
+        super::summed_moment(self, n, self.mean())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_takes_p_or_mean() {
+        let d = NegativeBinomial::builder().r(3.0).p(0.4).build().unwrap();
+        assert_eq!(d, NegativeBinomial::new(3.0, 0.4).unwrap());
+        assert_eq!(
+            NegativeBinomial::builder().mean(2.0).build().unwrap_err(),
+            DistError::MissingParameter { name: "r" }
+        );
+        assert_eq!(
+            NegativeBinomial::builder().r(1.0).build().unwrap_err(),
+            DistError::MissingParameter { name: "p" }
+        );
+    }
+
+    #[test]
+    fn pmf_cdf_values() {
+        let nb = NegativeBinomial::new(3.0, 0.4).unwrap();
+        // pmf(2) = C(4,2) 0.4^3 0.6^2
+        assert!((nb.pmf(2) - 6.0 * 0.064 * 0.36).abs() < 1e-13);
+        let sum: f64 = (0..=5).map(|k| nb.pmf(k)).sum();
+        assert!((nb.cdf(5) - sum).abs() < 1e-13);
+        assert_eq!(nb.cdf(-1), 0.0);
+    }
+
+    #[test]
+    fn inv_cdf_roundtrip() {
+        let nb = NegativeBinomial::new(2.5, 0.3).unwrap();
+        for &u in &[0.05, 0.5, 0.9] {
+            let k = nb.inv_cdf(u);
+            assert!(nb.cdf(k) >= u - 1e-12);
+            if k > 0 {
+                assert!(nb.cdf(k - 1) < u + 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn inv_cdf_with_underflowing_start() {
+        // p^r = 1e-2000 is zero in f64; the mass sits near the mean of 99000.
+        let nb = NegativeBinomial::new(1000.0, 0.01).unwrap();
+        for &u in &[1e-6, 0.5, 0.999] {
+            let k = nb.inv_cdf(u);
+            assert!(
+                nb.cdf(k) >= u - 1e-9 && nb.cdf(k - 1) < u + 1e-9,
+                "{u}: {k}"
+            );
+        }
+        assert!((nb.inv_cdf(0.5) as f64 / nb.mean() - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn pgf_and_moments() {
+        let nb = NegativeBinomial::new(4.0, 0.5).unwrap();
+        assert!((nb.pgf(1.0) - 1.0).abs() < 1e-15);
+        // G'(1) = mean
+        let h = 1e-6;
+        let d = (nb.pgf(1.0 + h) - nb.pgf(1.0 - h)) / (2.0 * h);
+        assert!((d - nb.mean()).abs() < 1e-6);
+        assert_eq!(nb.pgf(2.0), f64::INFINITY);
+        assert!((nb.mean() - 4.0).abs() < 1e-15);
+        assert!((nb.variance() - 8.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn sample_mean() {
+        let nb = NegativeBinomial::new(5.0, 0.25).unwrap();
+        let mut rng = crate::rng::SplitMix64::seed_from_u64(9);
+        let n = 20_000;
+        let mean = (0..n).map(|_| nb.sample(&mut rng) as f64).sum::<f64>() / n as f64;
+        assert!((mean - nb.mean()).abs() < 0.3);
+    }
+
+    #[test]
+    fn mean_dispersion_form() {
+        let nb = NegativeBinomial::from_mean_dispersion(4.0, 2.0).unwrap();
+        assert!((nb.mean() - 4.0).abs() < 1e-12);
+        assert!((nb.variance() - (4.0 + 16.0 / 2.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn sf_matches_generic_tail_sum() {
+        let nb = NegativeBinomial::new(3.0, 0.6).unwrap();
+        // mpmath.betainc(11, 3, 0, 0.4, regularized=True)
+        assert!((nb.sf(10) - 0.0013153337344000007).abs() < 1e-15);
+        let tail = crate::dist::numeric::upper_tail_sum(|j| nb.pmf(j), 40);
+        assert!((nb.sf(40) / tail - 1.0).abs() < 1e-10);
+    }
+}
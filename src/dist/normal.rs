@@ -1,19 +1,36 @@
-use crate::dist::{Continuous, DistError, Distribution, Moments};
+use crate::dist::sampler::Sampler;
+use crate::dist::ziggurat;
+use crate::dist::{
+    CharacteristicFunction, Continuous, DistError, Distribution, HigherMoments, Mgf, Moments,
+    Param, SampleFloat, check_finite, check_positive, check_probability, const_unwrap,
+};
+use crate::num::Float;
+use crate::telemetry::{self, Counter};
 use crate::{num, rng::RngCore};
 
-#[derive(Debug, Clone, Copy)]
+/// Normal distribution N(mu, sigma²).
+///
+/// `cdf` and the quantiles follow the instance's [`num::Accuracy`]: full double precision by
+/// default, the A&S `erf` and Acklam's probit after
+/// [`with_accuracy(Accuracy::Fast)`](Self::with_accuracy). `ln_cdf` / `ln_sf` and `pdf` are
+/// the same in both modes.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Normal {
     mu: f64,
     sigma: f64,
     inv_sigma: f64,
     norm: f64, // 1/(sigma*sqrt(2*pi))
+    accuracy: num::Accuracy,
 }
 
 impl Normal {
-    pub fn new(mu: f64, sigma: f64) -> Result<Self, DistError> {
-        if !(sigma > 0.0 && sigma.is_finite() && mu.is_finite()) {
-            return Err(DistError::InvalidParameter);
-        }
+    /// N(0, 1).
+    pub const fn standard() -> Self {
+        const_unwrap(Self::new(0.0, 1.0))
+    }
+    pub const fn new(mu: f64, sigma: f64) -> Result<Self, DistError> {
+        const_try!(check_finite("mu", mu));
+        const_try!(check_positive("sigma", sigma));
         let inv_sigma = 1.0 / sigma;
         let norm = num::INV_SQRT_2PI * inv_sigma;
         Ok(Self {
@@ -21,38 +38,236 @@ impl Normal {
             sigma,
             inv_sigma,
             norm,
+            accuracy: num::Accuracy::Full,
         })
     }
+    /// The same distribution evaluating `cdf` and quantiles at `accuracy`. Not serialized:
+    /// a deserialized Normal is [`Accuracy::Full`](num::Accuracy::Full).
+    ///
+    /// ```
+    /// use probability_rs::{Continuous, Distribution, dist::normal::Normal, num::Accuracy};
+    /// let fast = Normal::standard().with_accuracy(Accuracy::Fast);
+    /// assert!((fast.cdf(1.0) - Normal::standard().cdf(1.0)).abs() < 1e-7);
+    /// ```
+    pub const fn with_accuracy(self, accuracy: num::Accuracy) -> Self {
+        Self { accuracy, ..self }
+    }
+    #[inline]
+    pub const fn accuracy(&self) -> num::Accuracy {
+        self.accuracy
+    }
+    pub fn builder() -> NormalBuilder {
+        NormalBuilder::default()
+    }
+
+    /// From the mean and precision τ = 1/σ².
+    pub fn from_mean_precision(mean: f64, precision: f64) -> Result<Self, DistError> {
+        check_positive("precision", precision)?;
+        Self::new(mean, precision.sqrt().recip())
+    }
     #[inline]
-    pub fn mean_param(&self) -> f64 {
+    pub const fn mean_param(&self) -> f64 {
         self.mu
     }
     #[inline]
-    pub fn sigma(&self) -> f64 {
+    pub const fn sigma(&self) -> f64 {
         self.sigma
     }
 }
 
+/// Builder for [`Normal`]; the spread may be given as standard deviation, variance or
+/// precision (the last one set wins).
+///
+/// ```
+/// use probability_rs::dist::normal::Normal;
+/// let n = Normal::builder().mean(1.0).variance(4.0).build()?;
+/// assert_eq!(n.sigma(), 2.0);
+/// # Ok::<(), probability_rs::dist::DistError>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NormalBuilder {
+    mean: Option<f64>,
+    spread: Option<Spread>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Spread {
+    StdDev(f64),
+    Variance(f64),
+    Precision(f64),
+}
+
+impl NormalBuilder {
+    pub fn mean(mut self, mean: f64) -> Self {
+        self.mean = Some(mean);
+        self
+    }
+    pub fn std_dev(mut self, sigma: f64) -> Self {
+        self.spread = Some(Spread::StdDev(sigma));
+        self
+    }
+    pub fn variance(mut self, variance: f64) -> Self {
+        self.spread = Some(Spread::Variance(variance));
+        self
+    }
+    pub fn precision(mut self, precision: f64) -> Self {
+        self.spread = Some(Spread::Precision(precision));
+        self
+    }
+    pub fn build(self) -> Result<Normal, DistError> {
+        let mean = self
+            .mean
+            .ok_or(DistError::MissingParameter { name: "mean" })?;
+        match self.spread {
+            None => Err(DistError::MissingParameter { name: "std_dev" }),
+            Some(Spread::StdDev(sigma)) => Normal::new(mean, sigma),
+            Some(Spread::Variance(v)) => {
+                check_positive("variance", v)?;
+                Normal::new(mean, v.sqrt())
+            }
+            Some(Spread::Precision(tau)) => Normal::from_mean_precision(mean, tau),
+        }
+    }
+}
+
+impl Default for Normal {
+    /// [`Normal::standard`].
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl std::fmt::Display for Normal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Normal(mu={}, sigma={})",
+            Param(self.mu),
+            Param(self.sigma)
+        )
+    }
+}
+
 impl Distribution for Normal {
     type Value = f64;
     fn cdf(&self, x: f64) -> f64 {
         let z = (x - self.mu) * self.inv_sigma;
-        num::standard_normal_cdf(z)
+        num::normal_cdf_with(self.accuracy, z)
+    }
+    fn cdf_slice(&self, xs: &[f64], out: &mut [f64]) {
+        assert_eq!(xs.len(), out.len(), "cdf_slice: length mismatch");
+        let (mu, inv_sigma) = (self.mu, self.inv_sigma);
+        for (o, &x) in out.iter_mut().zip(xs) {
+            *o = num::normal_cdf_with(self.accuracy, (x - mu) * inv_sigma);
+        }
     }
     fn in_support(&self, x: f64) -> bool {
         x.is_finite()
     }
+    /// Ziggurat method (256 layers).
     fn sample<R: RngCore>(&self, rng: &mut R) -> f64 {
-        // Box-Muller polar (Marsaglia) without external dependencies.
-        loop {
-            let u1 = 2.0 * rng.next_f64() - 1.0; // (-1,1)
-            let u2 = 2.0 * rng.next_f64() - 1.0;
-            let s = u1 * u1 + u2 * u2;
-            if s >= 1.0 || s == 0.0 {
-                continue;
+        self.mu + self.sigma * ziggurat::standard_normal(rng)
+    }
+    /// Reflects the draw about the mean: `(mu + sigma z, mu - sigma z)`.
+    fn sample_antithetic<R: RngCore>(&self, rng: &mut R) -> (f64, f64) {
+        let z = ziggurat::standard_normal(rng);
+        (self.mu + self.sigma * z, self.mu - self.sigma * z)
+    }
+}
+
+/// [`Normal`] as a [`Sampler`]; see [`Normal::sampler`].
+///
+/// The ziggurat yields one variate per draw, so unlike a polar sampler there is no spare
+/// to keep: this only saves re-reading the parameters through the distribution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NormalSampler {
+    mu: f64,
+    sigma: f64,
+}
+
+impl Sampler for NormalSampler {
+    type Value = f64;
+    #[inline]
+    fn next<R: RngCore>(&mut self, rng: &mut R) -> f64 {
+        self.mu + self.sigma * ziggurat::standard_normal(rng)
+    }
+}
+
+impl Normal {
+    /// A sampler drawing the same stream as [`Distribution::sample`].
+    pub fn sampler(&self) -> NormalSampler {
+        NormalSampler {
+            mu: self.mu,
+            sigma: self.sigma,
+        }
+    }
+
+    /// `ln F(x)`, finite far into the lower tail where `cdf(x).ln()` is already `−∞`; see
+    /// [`num::standard_normal_ln_cdf`].
+    pub fn ln_cdf(&self, x: f64) -> f64 {
+        num::standard_normal_ln_cdf((x - self.mu) * self.inv_sigma)
+    }
+
+    /// `ln(1 − F(x))`, the upper-tail counterpart of [`ln_cdf`](Self::ln_cdf).
+    pub fn ln_sf(&self, x: f64) -> f64 {
+        num::standard_normal_ln_sf((x - self.mu) * self.inv_sigma)
+    }
+
+    /// One draw by the Marsaglia polar method, the sampler used before the ziggurat.
+    /// Slower, but kept as a reference for comparisons and reproducing older streams.
+    pub fn sample_polar<R: RngCore>(&self, rng: &mut R) -> f64 {
+        self.mu + self.sigma * polar_pair::<f64, R>(rng).0
+    }
+
+    /// Fills `out` like [`sample_fill`](Distribution::sample_fill), running the ziggurat over
+    /// 64-word blocks of [`RngCore::fill_u64`] output: the rectangle test is a branch-free
+    /// pass over the block and the few misses are finished afterwards. The stream differs
+    /// from `sample_fill`'s.
+    ///
+    /// Opt in only when building for AVX2 (`-C target-cpu=x86-64-v3` or `native`), where the
+    /// table lookups become gathers: 1000 draws take about 2.3 µs against 4.6 µs for
+    /// `sample_fill`. On baseline x86-64 it is about 25% slower. Generators without uniform
+    /// low bits ([`RngCore::uniform_bits`]) get `sample_fill`.
+    pub fn sample_fill_wide<R: RngCore>(&self, rng: &mut R, out: &mut [f64]) {
+        if rng.uniform_bits() {
+            ziggurat::fill_normal(rng, self.mu, self.sigma, out);
+        } else {
+            self.sample_fill(rng, out);
+        }
+    }
+}
+
+/// Box-Muller polar (Marsaglia): two independent standard normals per accepted point, at
+/// the precision of `F` (the `f64` stream is that of `next_f64`).
+fn polar_pair<F: Float, R: RngCore>(rng: &mut R) -> (F, F) {
+    let two = F::from_f64(2.0);
+    loop {
+        let u1 = two * rng.next_float::<F>() - F::ONE; // (-1,1)
+        let u2 = two * rng.next_float::<F>() - F::ONE;
+        let s = u1 * u1 + u2 * u2;
+        if s >= F::ONE || s == F::ZERO {
+            telemetry::record(Counter::NormalPolarRejections, 1);
+            continue;
+        }
+        let factor = (-two * s.ln() / s).sqrt();
+        return (u1 * factor, u2 * factor);
+    }
+}
+
+impl SampleFloat for Normal {
+    /// Polar method in `F`, keeping the first of each pair.
+    fn sample_float<F: Float, R: RngCore>(&self, rng: &mut R) -> F {
+        F::from_f64(self.mu) + F::from_f64(self.sigma) * polar_pair::<F, R>(rng).0
+    }
+    /// Uses both normals of each polar pair.
+    fn sample_fill_float<F: Float, R: RngCore>(&self, rng: &mut R, out: &mut [F]) {
+        let (mu, sigma) = (F::from_f64(self.mu), F::from_f64(self.sigma));
+        for pair in out.chunks_mut(2) {
+            let (z1, z2) = polar_pair::<F, R>(rng);
+            pair[0] = mu + sigma * z1;
+            if let Some(x) = pair.get_mut(1) {
+                *x = mu + sigma * z2;
             }
-            let factor = (-2.0 * s.ln() / s).sqrt();
-            return self.mu + self.sigma * u1 * factor;
         }
     }
 }
@@ -65,8 +280,37 @@ impl Continuous for Normal {
         let z = (x - self.mu) * self.inv_sigma;
         self.norm * (-0.5 * z * z).exp()
     }
-    fn inv_cdf(&self, p: f64) -> f64 {
-        self.mu + self.sigma * num::standard_normal_inv_cdf(p)
+    fn pdf_slice(&self, xs: &[f64], out: &mut [f64]) {
+        // Branch-free body (a select for the support) so the loop can vectorize.
+        assert_eq!(xs.len(), out.len(), "pdf_slice: length mismatch");
+        let (mu, inv_sigma, norm) = (self.mu, self.inv_sigma, self.norm);
+        for (o, &x) in out.iter_mut().zip(xs) {
+            let z = (x - mu) * inv_sigma;
+            let v = norm * (-0.5 * z * z).exp();
+            *o = if x.is_finite() { v } else { 0.0 };
+        }
+    }
+    fn try_inv_cdf(&self, p: f64) -> Result<f64, DistError> {
+        let p = check_probability(p)?;
+        if p == 0.0 {
+            return Ok(f64::NEG_INFINITY);
+        }
+        if p == 1.0 {
+            return Ok(f64::INFINITY);
+        }
+        Ok(self.mu + self.sigma * num::normal_inv_cdf_with(self.accuracy, p))
+    }
+
+    fn try_isf(&self, q: f64) -> Result<f64, DistError> {
+        // Φ⁻¹(1 − q) = −Φ⁻¹(q), so small q use the accurate lower-tail branch.
+        let q = check_probability(q)?;
+        if q == 0.0 {
+            return Ok(f64::INFINITY);
+        }
+        if q == 1.0 {
+            return Ok(f64::NEG_INFINITY);
+        }
+        Ok(self.mu - self.sigma * num::normal_inv_cdf_with(self.accuracy, q))
     }
 }
 
@@ -88,21 +332,87 @@ impl Moments for Normal {
     }
 }
 
+impl Mgf for Normal {
+    fn mgf(&self, t: f64) -> f64 {
+        self.cgf(t).exp()
+    }
+    fn cgf(&self, t: f64) -> f64 {
+        self.mu * t + 0.5 * self.sigma * self.sigma * t * t
+    }
+}
+
+impl CharacteristicFunction for Normal {
+    fn cf(&self, t: f64) -> (f64, f64) {
+        // exp(iμt − σ²t²/2)
+        let m = (-0.5 * self.sigma * self.sigma * t * t).exp();
+        let arg = self.mu * t;
+        (m * arg.cos(), m * arg.sin())
+    }
+}
+
+impl HigherMoments for Normal {
+    fn raw_moment(&self, n: u32) -> f64 {
+        // E[X^n] = Σ_{k even} C(n,k) μ^{n−k} σ^k (k−1)!!
+        let mut sum = 0.0;
+        let mut binom = 1.0;
+        let mut dfact = 1.0; // (k-1)!! for even k
+        for k in 0..=n {
+            if k % 2 == 0 {
+                if k > 0 {
+                    dfact *= (k - 1) as f64;
+                }
+                sum += binom * self.mu.powi((n - k) as i32) * self.sigma.powi(k as i32) * dfact;
+            }
+            binom = binom * (n - k) as f64 / (k + 1) as f64;
+        }
+        sum
+    }
+    fn central_moment(&self, n: u32) -> f64 {
+        // 0 for odd n, σ^n (n−1)!! for even n
+        if n % 2 == 1 {
+            return 0.0;
+        }
+        let dfact: f64 = (1..n).step_by(2).map(|k| k as f64).product();
+        self.sigma.powi(n as i32) * dfact
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::rng::SplitMix64;
+    use crate::rng::halton::Halton;
 
     #[test]
     fn normal_basic() {
         let n = Normal::new(0.0, 1.0).unwrap();
         assert!((n.pdf(0.0) - 0.3989422804014327).abs() < 1e-12);
-        // CDF approximation via erf has typical error ~1e-7; use generous tolerance.
-        assert!((n.cdf(0.0) - 0.5).abs() < 2e-6);
         let q = n.inv_cdf(0.975);
         assert!((q - 1.959963).abs() < 5e-4);
     }
 
+    #[test]
+    fn cdf_and_quantiles_follow_the_accuracy_mode() {
+        let n = Normal::new(0.0, 1.0).unwrap();
+        assert_eq!(n.accuracy(), num::Accuracy::Full);
+        assert_eq!(n.cdf(0.0), 0.5);
+        // mpmath ncdf(-10): the lower tail keeps its relative accuracy.
+        assert!((n.cdf(-10.0) / 7.619853024160525e-24 - 1.0).abs() < 1e-14);
+        assert!((n.inv_cdf(0.975) - 1.9599639845400538).abs() < 1e-15);
+        let fast = n.with_accuracy(num::Accuracy::Fast);
+        for i in -80..=80 {
+            let z = 0.1 * i as f64;
+            assert!(
+                (fast.cdf(z) - num::standard_normal_cdf(z)).abs() < 1e-7,
+                "z = {z}"
+            );
+        }
+        assert!((fast.inv_cdf(0.975) / 1.9599639845400538 - 1.0).abs() < 2e-9);
+        // ln_cdf is full precision either way, and the default instance is untouched.
+        assert_eq!(fast.ln_cdf(-40.0), n.ln_cdf(-40.0));
+        assert_eq!(n.cdf(0.0), 0.5);
+    }
+
     #[test]
     fn cdf_symmetry() {
         let n = Normal::new(0.0, 1.0).unwrap();
@@ -130,10 +440,232 @@ mod tests {
         assert_eq!(x1.to_bits(), x2.to_bits());
     }
 
+    #[test]
+    fn mgf_cgf() {
+        let n = Normal::new(1.0, 2.0).unwrap();
+        assert_eq!(n.mgf(0.0), 1.0);
+        assert!((n.cgf(0.5) - (0.5 + 0.5 * 4.0 * 0.25)).abs() < 1e-15);
+        // M'(0) = mean, by central difference.
+        let h = 1e-5;
+        assert!(((n.mgf(h) - n.mgf(-h)) / (2.0 * h) - 1.0).abs() < 1e-8);
+    }
+
     #[test]
     fn entropy_normal() {
         let n = Normal::new(0.0, 2.0).unwrap();
         let expected = 0.5 * (2.0 * std::f64::consts::PI * std::f64::consts::E * 4.0).ln();
         assert!((n.entropy() - expected).abs() < 1e-12);
     }
+
+    #[test]
+    fn higher_moments() {
+        let n = Normal::new(1.0, 2.0).unwrap();
+        // E[X^4] = μ^4 + 6μ²σ² + 3σ^4
+        assert!((n.raw_moment(4) - (1.0 + 24.0 + 48.0)).abs() < 1e-12);
+        assert!((n.central_moment(4) - 48.0).abs() < 1e-12);
+        assert_eq!(n.central_moment(3), 0.0);
+    }
+
+    #[test]
+    fn sample_iter_matches_sample() {
+        let n = Normal::new(1.0, 2.0).unwrap();
+        let mut a = SplitMix64::seed_from_u64(9);
+        let mut b = SplitMix64::seed_from_u64(9);
+        let xs: Vec<f64> = (&n).sample_iter(&mut a).take(16).collect();
+        assert_eq!(xs.len(), 16);
+        assert!(xs.iter().all(|&x| x == n.sample(&mut b)));
+        // Both the distribution and the generator remain usable afterwards.
+        assert_eq!(n.sample(&mut a), n.sample(&mut b));
+    }
+
+    #[test]
+    fn sample_fill_moments() {
+        let n = Normal::new(2.0, 3.0).unwrap();
+        let mut rng = SplitMix64::seed_from_u64(11);
+        let mut xs = vec![0.0; 20_001];
+        n.sample_fill(&mut rng, &mut xs);
+        let m = xs.iter().sum::<f64>() / xs.len() as f64;
+        let v = xs.iter().map(|x| (x - m) * (x - m)).sum::<f64>() / (xs.len() - 1) as f64;
+        assert!((m - 2.0).abs() < 0.1);
+        assert!((v - 9.0).abs() < 0.4);
+    }
+
+    #[test]
+    fn sample_float_runs_in_f32() {
+        let n = Normal::new(2.0, 3.0).unwrap();
+        let mut rng = SplitMix64::seed_from_u64(12);
+        let mut xs = vec![0.0f32; 20_001];
+        n.sample_fill_float(&mut rng, &mut xs);
+        let m = xs.iter().sum::<f32>() / xs.len() as f32;
+        let v = xs.iter().map(|x| (x - m) * (x - m)).sum::<f32>() / (xs.len() - 1) as f32;
+        assert!((m - 2.0).abs() < 0.1);
+        assert!((v - 9.0).abs() < 0.4);
+        // In f64 the polar stream is the one `sample_polar` draws.
+        let mut a = SplitMix64::seed_from_u64(3);
+        let mut b = a.clone();
+        assert_eq!(n.sample_float::<f64, _>(&mut a), n.sample_polar(&mut b));
+        let ln = crate::dist::lognormal::LogNormal::new(0.0, 0.5).unwrap();
+        let y: f32 = ln.sample_float(&mut rng);
+        assert!(y > 0.0 && y.is_finite());
+    }
+
+    #[test]
+    fn sampler_replays_sample() {
+        let n = Normal::new(1.0, 3.0).unwrap();
+        let mut a = SplitMix64::seed_from_u64(4);
+        let mut b = a.clone();
+        let mut s = n.sampler();
+        let mut xs = [0.0; 32];
+        s.fill(&mut a, &mut xs);
+        assert!(xs.iter().all(|&x| x == n.sample(&mut b)));
+    }
+
+    #[test]
+    fn ziggurat_and_polar_pass_ks() {
+        let n = Normal::standard();
+        let mut rng = SplitMix64::seed_from_u64(21);
+        let m = 20_000;
+        let ks = |mut xs: Vec<f64>| {
+            xs.sort_unstable_by(f64::total_cmp);
+            xs.iter()
+                .enumerate()
+                .map(|(i, &x)| (n.cdf(x) - i as f64 / m as f64).abs())
+                .fold(0.0, f64::max)
+        };
+        // 1% critical value of the KS statistic is 1.63 / sqrt(m) = 0.0115.
+        let zig = ks((0..m).map(|_| n.sample(&mut rng)).collect());
+        let polar = ks((0..m).map(|_| n.sample_polar(&mut rng)).collect());
+        assert!(zig < 0.0115, "{zig}");
+        assert!(polar < 0.0115, "{polar}");
+    }
+
+    #[test]
+    fn try_inv_cdf_domain() {
+        let n = Normal::new(0.0, 1.0).unwrap();
+        assert_eq!(n.try_inv_cdf(0.0), Ok(f64::NEG_INFINITY));
+        assert_eq!(n.try_inv_cdf(1.0), Ok(f64::INFINITY));
+        assert_eq!(n.try_inv_cdf(1.5), Err(DistError::DomainError));
+        assert_eq!(n.try_inv_cdf(f64::NAN), Err(DistError::DomainError));
+    }
+
+    #[test]
+    #[should_panic(expected = "DomainError")]
+    fn inv_cdf_panics_outside_unit_interval() {
+        Normal::new(0.0, 1.0).unwrap().inv_cdf(-0.1);
+    }
+
+    #[test]
+    fn constructor_errors_name_the_parameter() {
+        assert_eq!(
+            Normal::new(0.0, -1.0).unwrap_err(),
+            DistError::NonPositive {
+                name: "sigma",
+                value: -1.0
+            }
+        );
+        assert_eq!(
+            Normal::new(f64::NAN, 1.0).unwrap_err(),
+            DistError::NotFinite { name: "mu" }
+        );
+        let msg = Normal::new(0.0, 0.0).unwrap_err().to_string();
+        assert_eq!(msg, "parameter `sigma` must be positive, got 0");
+    }
+
+    #[test]
+    fn const_construction() {
+        const N: Normal = crate::dist::const_unwrap(Normal::new(1.0, 4.0));
+        const SIGMA: f64 = N.sigma();
+        assert_eq!(SIGMA, 4.0);
+        assert_eq!(N.pdf(1.0), Normal::new(1.0, 4.0).unwrap().pdf(1.0));
+        const BAD: Result<Normal, DistError> = Normal::new(0.0, -1.0);
+        assert!(BAD.is_err());
+    }
+
+    #[test]
+    fn builder_spread_forms() {
+        let a = Normal::builder().mean(1.0).std_dev(0.5).build().unwrap();
+        let b = Normal::builder().mean(1.0).precision(4.0).build().unwrap();
+        assert_eq!(a, b);
+        assert_eq!(
+            Normal::builder().std_dev(1.0).build().unwrap_err(),
+            DistError::MissingParameter { name: "mean" }
+        );
+        assert!(Normal::builder().mean(0.0).variance(0.0).build().is_err());
+    }
+
+    #[test]
+    fn interval_and_quantile_summaries() {
+        let n = Normal::new(10.0, 2.0).unwrap();
+        let (lo, hi) = n.interval(0.95);
+        assert!((lo - (10.0 - 2.0 * 1.959964)).abs() < 1e-3);
+        assert!((hi - 10.0 + (lo - 10.0)).abs() < 1e-9);
+        assert!((n.iqr() - 2.0 * 1.3489795).abs() < 1e-3);
+        assert_eq!(n.quantiles(&[0.0, 0.5]), vec![f64::NEG_INFINITY, 10.0]);
+    }
+
+    #[test]
+    fn isf_keeps_tiny_tail_probabilities() {
+        let n = Normal::new(1.0, 2.0).unwrap();
+        // inv_cdf(1 − 1e-20) would see exactly 1.0.
+        let x = n.isf(1e-20);
+        assert!(
+            (x / (1.0 + 2.0 * 9.262340089798153) - 1.0).abs() < 1e-8,
+            "{x}"
+        );
+        assert_eq!(n.isf(0.5), 1.0);
+        // Tail branches: Φ⁻¹(0.01) ≈ −2.3263
+        assert!((n.inv_cdf(0.01) - (1.0 - 2.0 * 2.326347874)).abs() < 1e-6);
+        assert!((n.isf(0.01) - n.inv_cdf(0.99)).abs() < 1e-12);
+        assert_eq!(n.try_isf(2.0), Err(DistError::DomainError));
+    }
+
+    #[test]
+    fn log_tails_stay_finite() {
+        let d = Normal::new(1.0, 2.0).unwrap();
+        assert_eq!(d.cdf(-79.0).ln(), f64::NEG_INFINITY);
+        // ln Φ(−40)
+        assert!((d.ln_cdf(-79.0) / -804.60844201375378817 - 1.0).abs() < 1e-15);
+        assert_eq!(d.ln_sf(81.0), d.ln_cdf(-79.0));
+        assert!((d.ln_cdf(1.0) + std::f64::consts::LN_2).abs() < 1e-16);
+    }
+
+    #[test]
+    fn standard_and_default() {
+        const N: Normal = Normal::standard();
+        assert_eq!(N, Normal::new(0.0, 1.0).unwrap());
+        assert_eq!(Normal::default(), N);
+    }
+
+    #[test]
+    fn slice_evaluation_matches_pointwise() {
+        let n = Normal::new(-1.0, 0.5).unwrap();
+        let xs = [f64::NEG_INFINITY, -3.0, -1.0, 0.25, 2.0, f64::NAN];
+        let (mut pdf, mut cdf) = ([0.0; 6], [0.0; 6]);
+        n.pdf_slice(&xs, &mut pdf);
+        n.cdf_slice(&xs, &mut cdf);
+        for i in 0..5 {
+            assert_eq!(pdf[i], n.pdf(xs[i]));
+            assert_eq!(cdf[i], n.cdf(xs[i]));
+        }
+        assert_eq!(pdf[5], 0.0);
+        let mut q = [0.0; 3];
+        n.inv_cdf_slice(&[0.1, 0.5, 0.9], &mut q);
+        assert_eq!(q, [n.inv_cdf(0.1), -1.0, n.inv_cdf(0.9)]);
+    }
+
+    #[test]
+    fn sample_from_a_halton_sequence() {
+        // Halton words carry only the coordinate's high bits; the ziggurat would read a
+        // near-constant strip index from them.
+        let mut h = Halton::new(1);
+        let n = Normal::standard();
+        let zs: Vec<f64> = (0..100_000).map(|_| n.sample(&mut h)).collect();
+        let m = zs.iter().sum::<f64>() / zs.len() as f64;
+        let v = zs.iter().map(|z| (z - m) * (z - m)).sum::<f64>() / zs.len() as f64;
+        assert!(m.abs() < 1e-3 && (v - 1.0).abs() < 1e-3, "{m} {v}");
+        // The block path falls back to `sample_fill` for it.
+        let mut wide = [0.0; 100];
+        n.sample_fill_wide(&mut Halton::new(1), &mut wide);
+        assert_eq!(wide[..], zs[..100]);
+    }
 }
@@ -0,0 +1,202 @@
+//! Generic numerical fallbacks shared by the distribution traits.
+
+use crate::dist::{Continuous, Discrete, DistError, check_probability};
+use crate::num::sum::Kahan;
+use crate::num::{quadrature, roots};
+
+/// Panel edges over probability space (0,1): uniform in the bulk and geometrically
+/// refined towards both tails, down to 1e-15 of mass.
+pub(crate) fn probability_edges() -> Vec<f64> {
+    let mut edges = Vec::with_capacity(48);
+    for k in (2..=15).rev() {
+        edges.push(10f64.powi(-k));
+    }
+    for i in 0..=16 {
+        edges.push(0.1 + 0.05 * i as f64);
+    }
+    for k in 2..=15 {
+        edges.push(1.0 - 10f64.powi(-k));
+    }
+    edges
+}
+
+/// [`quantile_expectation`] with adaptive panel refinement to absolute tolerance `tol`.
+pub(crate) fn adaptive_quantile_expectation<D, G>(d: &D, mut g: G, tol: f64) -> f64
+where
+    D: Continuous + ?Sized,
+    G: FnMut(f64) -> f64,
+{
+    quadrature::integrate_adaptive(|u| g(d.inv_cdf(u)), &probability_edges(), tol)
+}
+
+/// E[g(X)] = ∫₀¹ g(F⁻¹(u)) du, integrated in probability space so no support bounds are needed.
+pub(crate) fn quantile_expectation<D, G>(d: &D, mut g: G) -> f64
+where
+    D: Continuous + ?Sized,
+    G: FnMut(f64) -> f64,
+{
+    quadrature::integrate_panels(|u| g(d.inv_cdf(u)), &probability_edges())
+}
+
+/// Terms the pmf sums below take before giving up with NaN.
+const MAX_TERMS: i64 = 10_000_000;
+
+/// E[g(X)] = Σ pmf(k) g(k), starting at the 1e-17 quantile so a support far from zero
+/// (a Poisson with a huge mean) is not walked from its lower end.
+///
+/// Stops once the accumulated mass is within 1e-15 of one and the terms `pmf(k)·g(k)`
+/// have become negligible, so polynomially growing `g` still sees its tail. NaN if that
+/// takes more than ten million terms.
+pub(crate) fn pmf_expectation<D, G>(d: &D, mut g: G) -> f64
+where
+    D: Discrete + ?Sized,
+    G: FnMut(i64) -> f64,
+{
+    let start = d.inv_cdf(1e-17);
+    let mut sum = Kahan::new();
+    let mut mass = Kahan::new();
+    let mut small_run = 0;
+    for k in start..start.saturating_add(MAX_TERMS) {
+        let pk = d.pmf(k);
+        let term = if pk > 0.0 { pk * g(k) } else { 0.0 };
+        sum += term;
+        mass += pk;
+        if mass.sum() >= 1.0 - 1e-15 && term.abs() <= 1e-17 * sum.sum().abs() {
+            small_run += 1;
+            if small_run >= 8 {
+                return sum.sum();
+            }
+        } else {
+            small_run = 0;
+        }
+    }
+    f64::NAN
+}
+
+/// P(X > k) by summing the pmf upwards from k + 1, for when `1 − cdf(k)` would cancel.
+///
+/// Stops after 8 consecutive terms below 1e-17 of the running sum; NaN if the tail is
+/// still contributing after ten million terms.
+pub(crate) fn upper_tail_sum<F: Fn(i64) -> f64>(pmf: F, k: i64) -> f64 {
+    let mut sum = Kahan::new();
+    let mut small_run = 0;
+    for j in k.saturating_add(1)..k.saturating_add(MAX_TERMS) {
+        let pj = pmf(j);
+        sum += pj;
+        if pj <= 1e-17 * sum.sum() {
+            small_run += 1;
+            if small_run >= 8 {
+                return sum.sum();
+            }
+        } else {
+            small_run = 0;
+        }
+    }
+    f64::NAN
+}
+
+/// Smallest k with `sf(k) <= q`: `inv_cdf(1 − q)` for `q >= ½`, otherwise a gallop up from
+/// the median and a bisection, so O(log k) `sf` calls however deep the tail.
+pub(crate) fn galloping_isf<I, S>(inv_cdf: I, sf: S, q: f64) -> Result<i64, DistError>
+where
+    I: Fn(f64) -> Result<i64, DistError>,
+    S: Fn(i64) -> f64,
+{
+    let q = check_probability(q)?;
+    if q == 0.0 || q >= 0.5 {
+        return inv_cdf(1.0 - q);
+    }
+    // Below the median sf > ½ > q, so the answer is at or above it.
+    let mut lo = inv_cdf(0.5)?;
+    if sf(lo) <= q {
+        return Ok(lo);
+    }
+    let mut step = 1i64;
+    let mut hi = lo.saturating_add(step);
+    while sf(hi) > q {
+        if hi == i64::MAX {
+            return Ok(hi);
+        }
+        lo = hi;
+        step = step.saturating_mul(2);
+        hi = hi.saturating_add(step);
+    }
+    // sf(lo) > q >= sf(hi)
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if sf(mid) > q {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Ok(hi)
+}
+
+/// F⁻¹(p) for p in `[0,1]` by [`roots::brent`] on `cdf(x) − p`.
+///
+/// Infinite ends of `support` are replaced by walking outwards from the finite end (or 0)
+/// in doubling steps until the cdf crosses `p`; p = 0 and p = 1 give the bounds themselves.
+pub(crate) fn bracketed_quantile<F: Fn(f64) -> f64>(cdf: F, support: (f64, f64), p: f64) -> f64 {
+    let (mut lo, mut hi) = support;
+    if p == 0.0 {
+        return lo;
+    }
+    if p == 1.0 {
+        return hi;
+    }
+    let f = |x: f64| cdf(x) - p;
+    if lo == f64::NEG_INFINITY && hi == f64::INFINITY {
+        if f(0.0) < 0.0 {
+            lo = 0.0;
+        } else {
+            hi = 0.0;
+        }
+    }
+    let mut step = 1.0;
+    if lo == f64::NEG_INFINITY {
+        lo = hi - step;
+        while f(lo) > 0.0 {
+            hi = lo;
+            step *= 2.0;
+            lo = hi - step;
+            if lo == f64::NEG_INFINITY {
+                return lo;
+            }
+        }
+    }
+    if hi == f64::INFINITY {
+        hi = lo + step;
+        while f(hi) < 0.0 {
+            lo = hi;
+            step *= 2.0;
+            hi = lo + step;
+            if hi == f64::INFINITY {
+                return hi;
+            }
+        }
+    }
+    roots::brent(f, lo, hi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dist::{Moments, geometric::Geometric, poisson::Poisson};
+
+    #[test]
+    fn sums_start_at_the_quantile_range() {
+        // The mass of Poisson(1e9) lies within a few hundred thousand of the mean, far
+        // beyond what walking up from k = 0 could reach.
+        let p = Poisson::new(1e9).unwrap();
+        assert!((pmf_expectation(&p, |k| k as f64) / 1e9 - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn sums_past_the_term_cap_are_nan() {
+        // Geometric(1e-9) spreads its mass over ~1e10 integers.
+        let g = Geometric::new(1e-9).unwrap();
+        assert!(pmf_expectation(&g, |k| k as f64).is_nan());
+        assert!(upper_tail_sum(|j| g.pmf(j), g.mean() as i64).is_nan());
+    }
+}
@@ -0,0 +1,95 @@
+//! Multi-threaded sampling with one RNG substream per worker.
+//!
+//! The output is split into `threads` contiguous chunks and worker `i` fills chunk `i`
+//! from `base.substream(i)` (see [`Streams`]), so the result depends only on the base
+//! generator, the length and the thread count, never on scheduling. Changing `threads`
+//! changes the stream.
+//!
+//! ```
+//! use probability_rs::dist::normal::Normal;
+//! use probability_rs::dist::parallel::sample_par;
+//!
+//! let n = Normal::standard();
+//! let a = sample_par(&n, 10_000, 42, 4);
+//! assert_eq!(a, sample_par(&n, 10_000, 42, 4));
+//! ```
+
+use crate::dist::Distribution;
+use crate::rng::xoshiro256::Xoshiro256StarStar;
+use crate::rng::{RngCore, Streams};
+
+/// `n` draws from `dist` across `threads` workers, each on a
+/// [`jump`](Xoshiro256StarStar::jump)ed substream of `Xoshiro256StarStar::seed_from_u64(base_seed)`.
+/// Panics if `threads == 0`.
+pub fn sample_par<D>(dist: &D, n: usize, base_seed: u64, threads: usize) -> Vec<D::Value>
+where
+    D: Distribution + Sync + ?Sized,
+    D::Value: Send + Clone + Default,
+{
+    let mut out = vec![D::Value::default(); n];
+    fill_par(
+        dist,
+        &Xoshiro256StarStar::seed_from_u64(base_seed),
+        &mut out,
+        threads,
+    );
+    out
+}
+
+/// Fills `out` from `dist` across `threads` workers; worker `i` runs
+/// [`sample_fill`](Distribution::sample_fill) on its chunk with `base.substream(i)`.
+/// `base` itself is not advanced. Panics if `threads == 0`.
+pub fn fill_par<D, R>(dist: &D, base: &R, out: &mut [D::Value], threads: usize)
+where
+    D: Distribution + Sync + ?Sized,
+    D::Value: Send,
+    R: Streams + RngCore + Send,
+{
+    assert!(threads > 0, "fill_par needs at least one thread");
+    if threads == 1 {
+        dist.sample_fill(&mut base.substream(0), out);
+        return;
+    }
+    std::thread::scope(|s| {
+        let mut rest = out;
+        for (i, len) in chunk_lens(rest.len(), threads).enumerate() {
+            let (chunk, tail) = rest.split_at_mut(len);
+            rest = tail;
+            let mut rng = base.substream(i as u64);
+            s.spawn(move || dist.sample_fill(&mut rng, chunk));
+        }
+    });
+}
+
+/// `threads` chunk lengths summing to `n`, the first `n % threads` one longer.
+fn chunk_lens(n: usize, threads: usize) -> impl Iterator<Item = usize> {
+    let (q, r) = (n / threads, n % threads);
+    (0..threads).map(move |i| q + usize::from(i < r))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dist::poisson::Poisson;
+
+    #[test]
+    fn matches_sequential_substreams() {
+        let d = Poisson::new(4.0).unwrap();
+        let got = sample_par(&d, 1003, 9, 4);
+        let base = Xoshiro256StarStar::seed_from_u64(9);
+        let mut want = Vec::new();
+        for (i, len) in chunk_lens(1003, 4).enumerate() {
+            let mut chunk = vec![0; len];
+            d.sample_fill(&mut base.substream(i as u64), &mut chunk);
+            want.extend(chunk);
+        }
+        assert_eq!(got, want);
+        assert_eq!(
+            chunk_lens(1003, 4).collect::<Vec<_>>(),
+            [251, 251, 251, 250]
+        );
+        // More threads than draws leaves some workers idle.
+        assert_eq!(sample_par(&d, 2, 9, 8).len(), 2);
+        assert_eq!(sample_par(&d, 5, 9, 1), sample_par(&d, 5, 9, 1));
+    }
+}
@@ -1,112 +1,99 @@
-use crate::dist::{Discrete, DistError, Distribution, Moments};
-use crate::num;
+use crate::dist::sampler::Sampler;
+use crate::dist::{
+    CharacteristicFunction, Discrete, DistError, Distribution, HigherMoments, Mgf, Moments, Param,
+    check_positive, check_probability,
+};
+use crate::num::sum::Kahan;
+use crate::num::{self, bd0, stirlerr};
 use crate::rng::RngCore;
+use crate::telemetry::{self, Counter};
 
 /// Poisson(λ) distribution over non-negative integers.
 ///
 /// - Support: k = 0,1,2,...
 /// - Mean = λ
 /// - Var = λ
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Poisson {
     lambda: f64,
 }
 
 impl Poisson {
-    pub fn new(lambda: f64) -> Result<Self, DistError> {
-        if !(lambda.is_finite() && lambda > 0.0) {
-            return Err(DistError::InvalidParameter);
-        }
+    /// λ at and above which [`Distribution::sample`] uses PTRS instead of inversion.
+    pub const PTRS_THRESHOLD: f64 = 10.0;
+
+    pub const fn new(lambda: f64) -> Result<Self, DistError> {
+        const_try!(check_positive("lambda", lambda));
         Ok(Self { lambda })
     }
     #[inline]
-    pub fn lambda(&self) -> f64 {
+    pub const fn lambda(&self) -> f64 {
         self.lambda
     }
+}
 
-    #[inline]
-    fn pmf_rec_start(&self) -> f64 {
-        // p(0) = e^{-lambda}
-        (-self.lambda).exp()
-    }
-
-    /// Compute pmf(k) using recurrence from 0..k: p(k) = p(k-1) * λ / k
-    /// Cost O(k). Accurate and avoids factorial overflow.
-    fn pmf_via_recurrence(&self, k: i64) -> f64 {
-        if k < 0 {
-            return 0.0;
-        }
-        let k = k as u64;
-        let mut p = self.pmf_rec_start();
-        for i in 1..=k {
-            p *= self.lambda / (i as f64);
-        }
-        p
-    }
-
-    /// CDF up to k by summing recurrence.
-    fn cdf_via_recurrence(&self, k: i64) -> f64 {
-        if k < 0 {
-            return 0.0;
-        }
-        let k = k as u64;
-        let mut p = self.pmf_rec_start();
-        let mut acc = p;
-        for i in 1..=k {
-            p *= self.lambda / (i as f64);
-            acc += p;
-        }
-        acc
+impl std::fmt::Display for Poisson {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Poisson(lambda={})", Param(self.lambda))
     }
 }
 
 impl Distribution for Poisson {
     type Value = i64;
 
+    /// Q(k + 1, λ), the regularized upper incomplete gamma.
     fn cdf(&self, x: Self::Value) -> f64 {
-        self.cdf_via_recurrence(x)
+        if x < 0 {
+            return 0.0;
+        }
+        num::gamma_q(x as f64 + 1.0, self.lambda)
     }
 
     fn in_support(&self, x: Self::Value) -> bool {
         x >= 0
     }
 
+    /// Inversion from 0 for λ < [`Poisson::PTRS_THRESHOLD`], Hörmann's PTRS transformed
+    /// rejection (constant expected cost) above it.
     fn sample<R: RngCore>(&self, rng: &mut R) -> Self::Value {
+        self.sampler().next(rng)
+    }
+}
+
+/// [`Poisson`] sampler keeping `e^-λ` or the PTRS constants between draws; see
+/// [`Poisson::sampler`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoissonSampler {
+    method: Method,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Method {
+    FromZero { lambda: f64, p0: f64 },
+    Ptrs(Ptrs),
+}
+
+impl Poisson {
+    /// The inversion-based sampler used before PTRS: from 0 for λ < 30, from the mode
+    /// below 400 and from a normal-quantile anchor above (O(√λ) search steps). Kept for
+    /// reference and for reproducing older streams.
+    pub fn sample_inversion<R: RngCore>(&self, rng: &mut R) -> i64 {
         // Hybrid sampler:
         // - λ < 30: simple inversion from 0 (exact, fast enough)
         // - 30 ≤ λ < 400: inversion from the mode (exact, ~O(√λ))
         // - λ ≥ 400: quantile-anchored inversion (exact, near O(1) with small constant)
         if self.lambda < 30.0 {
-            // Small-λ inversion
-            let mut k: i64 = 0;
-            let mut p = self.pmf_rec_start();
-            let mut c = p;
-            let u = rng.next_f64();
-            while u > c {
-                k += 1;
-                p *= self.lambda / (k as f64);
-                c += p;
-            }
-            return k;
+            return self.sample_from_zero(rng);
         }
         let lambda = self.lambda;
         let m = lambda.floor() as i64; // mode = floor(λ)
         if lambda < 400.0 {
             // Mode-based symmetric inversion
-            let log_p_m = (m as f64) * lambda.ln() - lambda - ln_factorial_u64(m as u64);
+            let log_p_m = (m as f64) * lambda.ln() - lambda - num::ln_factorial(m as u64);
             let p_m = log_p_m.exp();
             if p_m.partial_cmp(&0.0) != Some(std::cmp::Ordering::Greater) {
                 // Fallback to small-λ path (pathological underflow)
-                let mut k: i64 = 0;
-                let mut p = self.pmf_rec_start();
-                let mut c = p;
-                let u = rng.next_f64();
-                while u > c {
-                    k += 1;
-                    p *= self.lambda / (k as f64);
-                    c += p;
-                }
-                return k;
+                return self.sample_from_zero(rng);
             }
             let u = rng.next_f64();
             let mut c = p_m;
@@ -117,6 +104,7 @@ impl Distribution for Poisson {
             let mut right = p_m;
             let mut i: i64 = 1;
             loop {
+                telemetry::record(Counter::PoissonSearchSteps, 1);
                 if i <= m {
                     left *= (m - (i - 1)) as f64 / lambda; // p(m-(i-1)) -> p(m-i)
                     c += left;
@@ -135,17 +123,18 @@ impl Distribution for Poisson {
             }
         }
         // Quantile-anchored inversion for very large λ
-        let u_anchor = rng.next_f64();
-        let z = num::standard_normal_inv_cdf(u_anchor);
+        // Open interval: the probit is undefined at 0.
+        let u_anchor = rng.next_f64_open_open();
+        let z = num::standard_normal_inv_cdf_fast(u_anchor);
         let mut k0 = (lambda + z * lambda.sqrt()).floor() as i64;
         if k0 < 0 {
             k0 = 0;
         }
-        let log_p0 = (k0 as f64) * lambda.ln() - lambda - ln_factorial_u64(k0 as u64);
+        let log_p0 = (k0 as f64) * lambda.ln() - lambda - num::ln_factorial(k0 as u64);
         let p0 = log_p0.exp();
         if !(p0 > 0.0 && p0.is_finite()) {
             // Fallback to mode-based
-            let log_p_m = (m as f64) * lambda.ln() - lambda - ln_factorial_u64(m as u64);
+            let log_p_m = (m as f64) * lambda.ln() - lambda - num::ln_factorial(m as u64);
             let p_m = log_p_m.exp();
             let u = rng.next_f64();
             let mut c = p_m;
@@ -156,6 +145,7 @@ impl Distribution for Poisson {
             let mut right = p_m;
             let mut i: i64 = 1;
             loop {
+                telemetry::record(Counter::PoissonSearchSteps, 1);
                 if i <= m {
                     left *= (m - (i - 1)) as f64 / lambda;
                     c += left;
@@ -182,6 +172,7 @@ impl Distribution for Poisson {
         let mut right = p0;
         let mut i: i64 = 1;
         loop {
+            telemetry::record(Counter::PoissonSearchSteps, 1);
             if i <= k0 {
                 left *= (k0 - (i - 1)) as f64 / lambda;
                 c += left;
@@ -199,30 +190,176 @@ impl Distribution for Poisson {
             i += 1;
         }
     }
+
+    /// Sequential inversion from 0, O(λ) steps.
+    fn sample_from_zero<R: RngCore>(&self, rng: &mut R) -> i64 {
+        from_zero(self.lambda, (-self.lambda).exp(), rng)
+    }
+
+    /// A sampler with the per-distribution setup done once.
+    pub fn sampler(&self) -> PoissonSampler {
+        let method = if self.lambda < Self::PTRS_THRESHOLD {
+            Method::FromZero {
+                lambda: self.lambda,
+                p0: (-self.lambda).exp(),
+            }
+        } else {
+            Method::Ptrs(Ptrs::new(self.lambda))
+        };
+        PoissonSampler { method }
+    }
+}
+
+impl Sampler for PoissonSampler {
+    type Value = i64;
+    #[inline]
+    fn next<R: RngCore>(&mut self, rng: &mut R) -> i64 {
+        match self.method {
+            Method::FromZero { lambda, p0 } => from_zero(lambda, p0, rng),
+            Method::Ptrs(ptrs) => ptrs.sample(rng),
+        }
+    }
+}
+
+fn from_zero<R: RngCore>(lambda: f64, p0: f64, rng: &mut R) -> i64 {
+    let mut k: i64 = 0;
+    let mut p = p0;
+    let mut c = p;
+    let u = rng.next_f64();
+    while u > c {
+        telemetry::record(Counter::PoissonSearchSteps, 1);
+        k += 1;
+        p *= lambda / (k as f64);
+        c += p;
+    }
+    k
+}
+
+/// PTRS (Hörmann 1993) for λ >= 10: rejection from a transformed-uniform hat with a
+/// squeeze that accepts about 89% of candidates without logarithms.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Ptrs {
+    lambda: f64,
+    ln_lambda: f64,
+    a: f64,
+    b: f64,
+    ln_inv_alpha: f64,
+    v_r: f64,
+}
+
+impl Ptrs {
+    fn new(lambda: f64) -> Self {
+        let b = 0.931 + 2.53 * lambda.sqrt();
+        Self {
+            lambda,
+            ln_lambda: lambda.ln(),
+            a: -0.059 + 0.02483 * b,
+            b,
+            ln_inv_alpha: (1.1239 + 1.1328 / (b - 3.4)).ln(),
+            v_r: 0.9277 - 3.6224 / (b - 2.0),
+        }
+    }
+
+    fn sample<R: RngCore>(&self, rng: &mut R) -> i64 {
+        let Ptrs {
+            lambda,
+            ln_lambda,
+            a,
+            b,
+            ln_inv_alpha,
+            v_r,
+        } = *self;
+        loop {
+            let u = rng.next_f64() - 0.5;
+            let v = rng.next_f64();
+            let us = 0.5 - u.abs();
+            let k = ((2.0 * a / us + b) * u + lambda + 0.43).floor();
+            if us >= 0.07 && v <= v_r {
+                return k as i64;
+            }
+            if k < 0.0 || (us < 0.013 && v > us) {
+                telemetry::record(Counter::PoissonPtrsRejections, 1);
+                continue;
+            }
+            let ln_hat = v.ln() + ln_inv_alpha - (a / (us * us) + b).ln();
+            if ln_hat <= -lambda + k * ln_lambda - num::ln_factorial(k as u64) {
+                return k as i64;
+            }
+            telemetry::record(Counter::PoissonPtrsRejections, 1);
+        }
+    }
 }
 
 impl Discrete for Poisson {
+    /// `e^(−δ(k) − bd0(k, λ)) / √(2πk)`, as R's `dpois`: the prefactor stays outside the
+    /// exponential so its logarithm adds no rounding to it.
     fn pmf(&self, x: Self::Value) -> f64 {
-        self.pmf_via_recurrence(x)
+        if x <= 0 {
+            return self.ln_pmf(x).exp();
+        }
+        let k = x as f64;
+        (-stirlerr(k) - bd0(k, self.lambda)).exp() / (2.0 * std::f64::consts::PI * k).sqrt()
+    }
+
+    /// Saddle-point form `-bd0(k, λ) - ½ ln(2πk) - δ(k)` (Loader), with no ln k! − k ln λ
+    /// cancellation.
+    fn ln_pmf(&self, x: Self::Value) -> f64 {
+        if x < 0 {
+            return f64::NEG_INFINITY;
+        }
+        if x == 0 {
+            return -self.lambda;
+        }
+        let k = x as f64;
+        -bd0(k, self.lambda) - 0.5 * (2.0 * std::f64::consts::PI * k).ln() - stirlerr(k)
     }
 
-    fn inv_cdf(&self, p: f64) -> Self::Value {
-        debug_assert!((0.0..=1.0).contains(&p));
-        if p <= 0.0 {
-            return 0;
+    fn try_inv_cdf(&self, p: f64) -> Result<i64, DistError> {
+        let p = check_probability(p)?;
+        if p == 0.0 {
+            return Ok(0);
+        }
+        if p == 1.0 {
+            return Ok(i64::MAX);
+        }
+        // Smallest k with cdf(k) >= p: bracket above the mean, then bisect.
+        let mut hi = (self.lambda + 10.0 * self.lambda.sqrt()).ceil() as i64 + 10;
+        while self.cdf(hi) < p {
+            hi = hi.saturating_mul(2);
+        }
+        let mut lo = 0;
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.cdf(mid) >= p {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
         }
-        if p >= 1.0 {
-            return i64::MAX;
+        Ok(lo)
+    }
+
+    fn sf(&self, k: i64) -> f64 {
+        // P(X > k) = P(k + 1, λ), the regularized lower incomplete gamma
+        if k < 0 {
+            return 1.0;
         }
-        let mut k: i64 = 0;
-        let mut pk = self.pmf_rec_start();
-        let mut acc = pk;
-        while acc < p {
-            k += 1;
-            pk *= self.lambda / (k as f64);
-            acc += pk;
+        num::gamma_p(k as f64 + 1.0, self.lambda)
+    }
+
+    fn ln_sf(&self, k: i64) -> f64 {
+        if k < 0 {
+            return 0.0;
         }
-        k
+        num::ln_gamma_p(k as f64 + 1.0, self.lambda)
+    }
+
+    fn pgf(&self, s: f64) -> f64 {
+        self.ln_pgf(s).exp()
+    }
+
+    fn ln_pgf(&self, s: f64) -> f64 {
+        self.lambda * (s - 1.0)
     }
 }
 
@@ -249,56 +386,48 @@ impl Moments for Poisson {
             k_min = 0;
         }
         let k_max = (mean + 10.0 * std).ceil() as i64;
-        let mut h = 0.0;
+        let mut h = Kahan::new();
         for k in k_min..=k_max {
-            let pk = self.pmf_via_recurrence(k);
-            if pk > 0.0 {
-                h -= pk * pk.ln();
-            }
+            let ln_pk = self.ln_pmf(k);
+            h += -ln_pk.exp() * ln_pk;
         }
-        h
+        h.sum()
     }
 }
 
-// -------- Internal helpers for large-λ sampling --------
-
-#[inline]
-fn ln_factorial_u64(n: u64) -> f64 {
-    // Exact table for 0..=20
-    const LN_FACT_SMALL: [f64; 21] = [
-        0.0,
-        0.0,
-        std::f64::consts::LN_2,
-        1.791759469228055,
-        3.1780538303479458,
-        4.787491742782046,
-        6.579251212010101,
-        8.525161361065415,
-        10.60460290274525,
-        12.80182748008147,
-        15.104412573075516,
-        17.502307845873887,
-        19.98721449566189,
-        22.552163853123425,
-        25.19122118273868,
-        27.899271383840894,
-        30.671860106080675,
-        33.50507345013689,
-        36.39544520803305,
-        39.339884187199495,
-        42.335616460753485,
-    ];
-    if n <= 20 {
-        return LN_FACT_SMALL[n as usize];
-    }
-    // Stirling with 1/(12n) - 1/(360n^3) + 1/(1260 n^5) correction
-    let x = n as f64;
-    let inv = 1.0 / x;
-    let inv2 = inv * inv;
-    let inv3 = inv2 * inv;
-    let inv5 = inv3 * inv2;
-    x * x.ln() - x + 0.5 * ((2.0 * std::f64::consts::PI * x).ln()) + (inv / 12.0) - (inv3 / 360.0)
-        + (inv5 / 1260.0)
+impl Mgf for Poisson {
+    fn mgf(&self, t: f64) -> f64 {
+        self.cgf(t).exp()
+    }
+    fn cgf(&self, t: f64) -> f64 {
+        self.lambda * t.exp_m1()
+    }
+}
+
+impl CharacteristicFunction for Poisson {
+    fn cf(&self, t: f64) -> (f64, f64) {
+        // exp(λ(e^{it} − 1))
+        let m = (self.lambda * (t.cos() - 1.0)).exp();
+        let arg = self.lambda * t.sin();
+        (m * arg.cos(), m * arg.sin())
+    }
+}
+
+impl HigherMoments for Poisson {
+    fn raw_moment(&self, n: u32) -> f64 {
+        // Touchard polynomial: E[X^n] = Σ_k S(n,k) λ^k, with Stirling numbers of the
+        // second kind from S(m,k) = k S(m−1,k) + S(m−1,k−1).
+        let n = n as usize;
+        let mut s = vec![0.0; n + 1];
+        s[0] = 1.0;
+        for m in 1..=n {
+            for k in (1..=m).rev() {
+                s[k] = k as f64 * s[k] + s[k - 1];
+            }
+            s[0] = 0.0;
+        }
+        s.iter().rev().fold(0.0, |acc, &c| acc * self.lambda + c)
+    }
 }
 
 #[cfg(test)]
@@ -312,6 +441,11 @@ mod tests {
         assert!((p.pmf(0) - e3).abs() < 1e-15);
         // pmf(3) = e^-3 * 3^3/3! = e^-3 * 27/6 = 4.5 e^-3
         assert!((p.pmf(3) - 4.5 * e3).abs() < 1e-12);
+        // mpmath exp(-λ) λ^k / k!
+        let p = Poisson::new(12.0).unwrap();
+        assert!((p.pmf(9) / 0.087364379903049434 - 1.0).abs() < 1e-15);
+        let p = Poisson::new(1e6).unwrap();
+        assert!((p.pmf(1_000_000) / 0.00039894224715624403 - 1.0).abs() < 1e-14);
     }
 
     #[test]
@@ -352,4 +486,143 @@ mod tests {
         assert!((p.kurtosis() - 0.25).abs() < 1e-15);
         assert!((p.kurtosis_full() - 3.25).abs() < 1e-15);
     }
+
+    #[test]
+    fn mgf_cgf() {
+        let p = Poisson::new(2.0).unwrap();
+        let t: f64 = 0.3;
+        assert!((p.cgf(t) - 2.0 * (t.exp() - 1.0)).abs() < 1e-15);
+        assert!((p.mgf(t) - p.cgf(t).exp()).abs() < 1e-15);
+    }
+
+    #[test]
+    fn cf_matches_pmf_sum() {
+        let pois = Poisson::new(1.7).unwrap();
+        let t = 0.9;
+        let (mut re, mut im) = (0.0, 0.0);
+        for k in 0..60 {
+            let pk = pois.pmf(k);
+            re += pk * (t * k as f64).cos();
+            im += pk * (t * k as f64).sin();
+        }
+        let (cre, cim) = pois.cf(t);
+        assert!((re - cre).abs() < 1e-14 && (im - cim).abs() < 1e-14);
+    }
+
+    #[test]
+    fn large_lambda_sample_at_zero_uniform() {
+        // The anchor uniform used to reach the probit as exactly 0, which panics.
+        let pois = Poisson::new(1000.0).unwrap();
+        let mut rng = crate::rng::mock::ScriptedRng::new(&[0]);
+        let k = pois.sample_inversion(&mut rng);
+        assert!(k >= 0);
+    }
+
+    #[test]
+    fn ptrs_matches_pmf() {
+        // PTRS just above its threshold and at λ = 250, against the pmf by Pearson
+        // chi-squared over cells with expected count >= 5.
+        let mut rng = crate::rng::SplitMix64::seed_from_u64(12);
+        for lambda in [10.0, 250.0] {
+            let pois = Poisson::new(lambda).unwrap();
+            let draws = 200_000;
+            let mut counts = vec![0u32; 600];
+            for _ in 0..draws {
+                counts[pois.sample(&mut rng) as usize] += 1;
+            }
+            let (mut chi2, mut cells) = (0.0, 0);
+            for (k, &c) in counts.iter().enumerate() {
+                let e = pois.pmf(k as i64) * draws as f64;
+                if e >= 5.0 {
+                    chi2 += (c as f64 - e).powi(2) / e;
+                    cells += 1;
+                }
+            }
+            let df = (cells - 1) as f64;
+            assert!(
+                chi2 < df + 5.0 * (2.0 * df).sqrt(),
+                "λ={lambda}: {chi2} on {df}"
+            );
+        }
+    }
+
+    #[test]
+    fn sampler_replays_sample() {
+        for lambda in [3.0, 40.0] {
+            let pois = Poisson::new(lambda).unwrap();
+            let mut a = crate::rng::SplitMix64::seed_from_u64(4);
+            let mut r = a.clone();
+            let mut s = pois.sampler();
+            assert!((0..100).all(|_| s.next(&mut a) == pois.sample(&mut r)));
+        }
+    }
+
+    #[test]
+    fn pgf_values() {
+        let p = Poisson::new(2.0).unwrap();
+        assert!((p.pgf(1.0) - 1.0).abs() < 1e-15);
+        assert!((p.pgf(0.0) - p.pmf(0)).abs() < 1e-15);
+        assert!((p.ln_pgf(0.5) + 1.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn touchard_moments() {
+        let p = Poisson::new(1.5).unwrap();
+        // E[X^3] = λ^3 + 3λ^2 + λ
+        assert!((p.raw_moment(3) - (3.375 + 6.75 + 1.5)).abs() < 1e-12);
+        // Central third moment equals λ.
+        assert!((p.central_moment(3) - 1.5).abs() < 1e-11);
+        let summed = crate::dist::summed_moment(&p, 5, 0.0);
+        assert!((p.raw_moment(5) - summed).abs() < 1e-9 * summed);
+    }
+
+    #[test]
+    fn discrete_interval_covers_mass() {
+        let pois = Poisson::new(6.0).unwrap();
+        let (lo, hi) = pois.interval(0.9);
+        let covered = pois.cdf(hi) - if lo > 0 { pois.cdf(lo - 1) } else { 0.0 };
+        assert!(covered >= 0.9);
+        assert_eq!(pois.quantiles(&[0.0, 0.5]), vec![0, 6]);
+    }
+
+    #[test]
+    fn sf_upper_tail() {
+        // mpmath.gammainc(k + 1, 0, λ, regularized=True)
+        let p = Poisson::new(1.0).unwrap();
+        assert!((p.sf(30) / 4.618047461027192e-35 - 1.0).abs() < 1e-10);
+        assert!((p.ln_sf(300) + 1421.609643493616).abs() < 1e-9);
+        assert_eq!(p.sf(-1), 1.0);
+        assert!((p.sf(2) - (1.0 - p.cdf(2))).abs() < 1e-15);
+    }
+
+    #[test]
+    fn pmf_and_cdf_at_large_lambda() {
+        // mpmath: -λ + k ln λ - loggamma(k + 1) and gammainc(k + 1, λ, inf, regularized=True)
+        let p = Poisson::new(1e6).unwrap();
+        for (k, ln_pmf, cdf) in [
+            (1_000_000, -7.8266938955201431, 0.50026596148628365),
+            (999_000, -8.3263603954868015, 0.15877629981172561),
+            (1_005_000, -20.308406260130049, 0.9999997081107533),
+        ] {
+            assert!((p.ln_pmf(k) - ln_pmf).abs() < 1e-9 * ln_pmf.abs(), "{k}");
+            assert!((p.cdf(k) / cdf - 1.0).abs() < 1e-10, "{k}");
+        }
+        assert!((p.sf(1_005_000) / 2.9188924670030269e-7 - 1.0).abs() < 1e-9);
+        assert_eq!(p.ln_pmf(0), -1e6);
+        assert_eq!(p.inv_cdf(0.5), 1_000_000);
+    }
+
+    #[test]
+    fn isf_searches_the_tail() {
+        let p = Poisson::new(1.0).unwrap();
+        let k = p.isf(1e-30);
+        assert!(p.sf(k) <= 1e-30 && p.sf(k - 1) > 1e-30);
+        assert_eq!(p.isf(0.3), p.inv_cdf(0.7));
+        // Thousands of steps above the median, found by galloping.
+        let p = Poisson::new(1e6).unwrap();
+        let k = p.isf(1e-15);
+        assert!(p.sf(k) <= 1e-15 && p.sf(k - 1) > 1e-15, "{k}");
+        let b = crate::dist::binomial::Binomial::new(10, 0.5).unwrap();
+        assert_eq!(b.isf(1e-300), 10);
+    }
 }
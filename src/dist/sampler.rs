@@ -0,0 +1,431 @@
+//! Stateful samplers: precompute per-distribution setup once and reuse it across draws.
+//!
+//! [`Distribution::sample`] takes `&self`, so it redoes any setup (BTPE's hat, PTRS's
+//! constants) on every call. A sampler is built once with `dist.sampler()` and then drawn
+//! from through `&mut self`, which also lets it carry state between draws.
+//!
+//! ```
+//! use probability_rs::dist::binomial::Binomial;
+//! use probability_rs::dist::sampler::Sampler;
+//! use probability_rs::rng::SplitMix64;
+//!
+//! let mut s = Binomial::new(1_000_000, 0.3)?.sampler();
+//! let mut rng = SplitMix64::seed_from_u64(1);
+//! let k = s.next(&mut rng);
+//! assert!((0..=1_000_000).contains(&k));
+//! # Ok::<(), probability_rs::dist::DistError>(())
+//! ```
+
+use crate::dist::{
+    Continuous, Discrete, DistError, Distribution, check_positive, check_probability,
+};
+use crate::num::cheb::ChebyshevFit;
+use crate::num::sum::Kahan;
+use crate::rng::RngCore;
+use crate::rng::alias::AliasTable;
+
+/// A source of variates that may keep state between draws.
+pub trait Sampler {
+    type Value;
+    fn next<R: RngCore>(&mut self, rng: &mut R) -> Self::Value;
+    /// Fills `out` with independent draws.
+    fn fill<R: RngCore>(&mut self, rng: &mut R, out: &mut [Self::Value]) {
+        for x in out {
+            *x = self.next(rng);
+        }
+    }
+}
+
+/// Any distribution is a stateless sampler through a reference.
+impl<D: Distribution + ?Sized> Sampler for &D {
+    type Value = D::Value;
+    #[inline]
+    fn next<R: RngCore>(&mut self, rng: &mut R) -> D::Value {
+        (**self).sample(rng)
+    }
+}
+
+/// Indices `0..weights.len()` drawn with probability proportional to their weights, from
+/// a prebuilt [`AliasTable`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoricalSampler {
+    table: AliasTable,
+}
+
+impl CategoricalSampler {
+    /// Errors as [`AliasTable::new`].
+    pub fn new(weights: &[f64]) -> Result<Self, DistError> {
+        AliasTable::new(weights).map(Self::from)
+    }
+
+    #[inline]
+    pub fn table(&self) -> &AliasTable {
+        &self.table
+    }
+}
+
+impl From<AliasTable> for CategoricalSampler {
+    fn from(table: AliasTable) -> Self {
+        Self { table }
+    }
+}
+
+impl Sampler for CategoricalSampler {
+    type Value = usize;
+    #[inline]
+    fn next<R: RngCore>(&mut self, rng: &mut R) -> usize {
+        self.table.sample(rng)
+    }
+}
+
+/// The CDF of a [`Discrete`] distribution tabulated once over `lo..=hi`, for repeated
+/// inversion: [`inv_cdf`](Self::inv_cdf) starts from a guide table (Chen & Asau) and costs
+/// O(1) expected, so bootstrap-style loops stop paying for a CDF search per draw.
+///
+/// Probabilities outside the covered range clamp to its ends: `inv_cdf(u)` is `lo` for
+/// `u <= F(lo)` and `hi` for `u > F(hi)`.
+///
+/// ```
+/// use probability_rs::Discrete;
+/// use probability_rs::dist::poisson::Poisson;
+/// use probability_rs::dist::sampler::DiscreteCdfTable;
+///
+/// let d = Poisson::new(30.0)?;
+/// let table = DiscreteCdfTable::covering(&d, 1e-12)?;
+/// assert_eq!(table.inv_cdf(0.9), d.inv_cdf(0.9));
+/// # Ok::<(), probability_rs::dist::DistError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscreteCdfTable {
+    lo: i64,
+    cdf: Vec<f64>,
+    // guide[j]: smallest index with cdf >= j / guide.len().
+    guide: Vec<usize>,
+}
+
+impl DiscreteCdfTable {
+    /// Tabulates `F(k)` for `k` in `lo..=hi` from `F(lo − 1)` plus a compensated running
+    /// sum of the pmf. Errors if `hi < lo`.
+    pub fn new<D: Discrete + ?Sized>(dist: &D, lo: i64, hi: i64) -> Result<Self, DistError> {
+        if hi < lo {
+            return Err(DistError::InvalidRange {
+                low: lo as f64,
+                high: hi as f64,
+            });
+        }
+        let mut acc = Kahan::new();
+        acc += dist.cdf(lo.saturating_sub(1));
+        let cdf: Vec<f64> = (lo..=hi)
+            .map(|k| {
+                acc += dist.pmf(k);
+                acc.sum().min(1.0)
+            })
+            .collect();
+        let m = cdf.len();
+        let mut guide = Vec::with_capacity(m);
+        let mut i = 0;
+        for j in 0..m {
+            let u = j as f64 / m as f64;
+            while i + 1 < m && cdf[i] < u {
+                i += 1;
+            }
+            guide.push(i);
+        }
+        Ok(Self { lo, cdf, guide })
+    }
+
+    /// A table from `inv_cdf(tail)` to `inv_cdf(1 − tail)`, leaving at most `tail` of the
+    /// mass uncovered on each side. Errors unless `tail` is in `(0, 0.5]`.
+    pub fn covering<D: Discrete + ?Sized>(dist: &D, tail: f64) -> Result<Self, DistError> {
+        if !(tail > 0.0 && tail <= 0.5) {
+            return Err(DistError::ProbabilityOutOfRange {
+                name: "tail",
+                value: tail,
+            });
+        }
+        Self::new(dist, dist.try_inv_cdf(tail)?, dist.try_isf(tail)?)
+    }
+
+    /// The covered support `(lo, hi)`.
+    #[inline]
+    pub fn range(&self) -> (i64, i64) {
+        (self.lo, self.lo + self.cdf.len() as i64 - 1)
+    }
+
+    /// The tabulated `F(k)`: 0 below the range and 1 above it.
+    pub fn cdf(&self, k: i64) -> f64 {
+        if k < self.lo {
+            return 0.0;
+        }
+        self.cdf.get((k - self.lo) as usize).copied().unwrap_or(1.0)
+    }
+
+    /// Smallest `k` in the range with `F(k) >= u`, clamped to the range.
+    pub fn try_inv_cdf(&self, u: f64) -> Result<i64, DistError> {
+        let u = check_probability(u)?;
+        let m = self.cdf.len();
+        let mut i = self.guide[((u * m as f64) as usize).min(m - 1)];
+        while i + 1 < m && self.cdf[i] < u {
+            i += 1;
+        }
+        Ok(self.lo + i as i64)
+    }
+
+    /// Panicking form of [`try_inv_cdf`](Self::try_inv_cdf).
+    #[inline]
+    pub fn inv_cdf(&self, u: f64) -> i64 {
+        match self.try_inv_cdf(u) {
+            Ok(k) => k,
+            Err(e) => panic!("inv_cdf({u}): {e:?}"),
+        }
+    }
+}
+
+impl Sampler for DiscreteCdfTable {
+    type Value = i64;
+    /// Inversion of one uniform.
+    #[inline]
+    fn next<R: RngCore>(&mut self, rng: &mut R) -> i64 {
+        self.inv_cdf(rng.next_f64())
+    }
+}
+
+/// The quantile function of a [`Continuous`] distribution fitted once by piecewise
+/// [`ChebyshevFit`]s over `[tail, 1 − tail]`, for when `inv_cdf` is an iterative solve and
+/// is called millions of times. Probabilities in the two tails go to the distribution.
+///
+/// The lower half is fitted in `u` through `inv_cdf` and the upper half in `1 − u` through
+/// `isf`, so both tails are fitted from quantiles that are accurate to the last digit.
+///
+/// ```
+/// use probability_rs::Continuous;
+/// use probability_rs::dist::gamma::Gamma;
+/// use probability_rs::dist::sampler::QuantileFit;
+///
+/// let d = Gamma::new(2.5, 1.0)?;
+/// let q = QuantileFit::new(d, 1e-6, 1e-12)?;
+/// assert!((q.inv_cdf(0.3) - d.inv_cdf(0.3)).abs() < 1e-11);
+/// # Ok::<(), probability_rs::dist::DistError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantileFit<D> {
+    dist: D,
+    // F⁻¹(u) and F⁻¹(1 − q), both for u, q in [tail, ½].
+    lower: Pieces,
+    upper: Pieces,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Pieces {
+    // fits[i] covers [edges[i], edges[i + 1]].
+    edges: Vec<f64>,
+    fits: Vec<ChebyshevFit>,
+}
+
+impl Pieces {
+    /// Degree + 1 of each piece.
+    const TERMS: usize = 24;
+    /// Most pieces per half before giving up on `tol`.
+    const MAX_PIECES: usize = 4096;
+    /// Error estimates within this many ulps of the values are rounding noise, which
+    /// bisecting cannot reduce.
+    const ROUNDING: f64 = 32.0 * f64::EPSILON;
+
+    /// Bisects `[lo, hi]` until every piece's error estimate is within `tol` of the largest
+    /// magnitude of `f` on it.
+    fn fit<F: Fn(f64) -> f64>(f: F, lo: f64, hi: f64, tol: f64) -> Result<Self, DistError> {
+        let mut edges = vec![lo];
+        let mut fits = Vec::new();
+        // Right-to-left stack of pieces still to fit, so they come out in order.
+        let mut todo = vec![(lo, hi)];
+        while let Some((lo, hi)) = todo.pop() {
+            let fit = ChebyshevFit::fit(&f, lo, hi, Self::TERMS);
+            let scale = f(lo).abs().max(f(hi).abs());
+            let est = fit.error_estimate();
+            if est <= tol * scale {
+                edges.push(hi);
+                fits.push(fit);
+                continue;
+            }
+            let mid = 0.5 * (lo + hi);
+            if est <= Self::ROUNDING * scale
+                || fits.len() + todo.len() >= Self::MAX_PIECES
+                || !(lo < mid && mid < hi)
+            {
+                return Err(DistError::DomainError);
+            }
+            todo.push((mid, hi));
+            todo.push((lo, mid));
+        }
+        Ok(Self { edges, fits })
+    }
+
+    fn eval(&self, t: f64) -> f64 {
+        let i = self
+            .edges
+            .partition_point(|&e| e <= t)
+            .clamp(1, self.fits.len());
+        self.fits[i - 1].eval(t)
+    }
+}
+
+impl<D: Continuous> QuantileFit<D> {
+    /// Bisects each half until every piece's [`ChebyshevFit::error_estimate`] is within
+    /// `tol` of the largest quantile magnitude on it. Errors unless `tail` is in `(0, 0.5)`
+    /// and `tol` is positive, and with [`DistError::DomainError`] when `tol` is below the
+    /// rounding noise of the quantile or needs more than 4096 pieces per half.
+    pub fn new(dist: D, tail: f64, tol: f64) -> Result<Self, DistError> {
+        if !(tail > 0.0 && tail < 0.5) {
+            return Err(DistError::ProbabilityOutOfRange {
+                name: "tail",
+                value: tail,
+            });
+        }
+        check_positive("tol", tol)?;
+        let lower = Pieces::fit(|u| dist.inv_cdf(u), tail, 0.5, tol)?;
+        let upper = Pieces::fit(|q| dist.isf(q), tail, 0.5, tol)?;
+        Ok(Self { dist, lower, upper })
+    }
+
+    /// The fitted F⁻¹(u); outside `[tail, 1 − tail]` the distribution's own quantile.
+    pub fn try_inv_cdf(&self, u: f64) -> Result<f64, DistError> {
+        let u = check_probability(u)?;
+        let tail = self.lower.edges[0];
+        if u < tail || u > 1.0 - tail {
+            return self.dist.try_inv_cdf(u);
+        }
+        if u <= 0.5 {
+            Ok(self.lower.eval(u))
+        } else {
+            // Exact for u >= ½.
+            Ok(self.upper.eval(1.0 - u))
+        }
+    }
+
+    /// Panicking form of [`try_inv_cdf`](Self::try_inv_cdf).
+    #[inline]
+    pub fn inv_cdf(&self, u: f64) -> f64 {
+        match self.try_inv_cdf(u) {
+            Ok(x) => x,
+            Err(e) => panic!("inv_cdf({u}): {e:?}"),
+        }
+    }
+
+    /// Number of Chebyshev pieces the fit needed.
+    #[inline]
+    pub fn pieces(&self) -> usize {
+        self.lower.fits.len() + self.upper.fits.len()
+    }
+
+    #[inline]
+    pub fn distribution(&self) -> &D {
+        &self.dist
+    }
+}
+
+impl<D: Continuous> Sampler for QuantileFit<D> {
+    type Value = f64;
+    /// Inversion of one uniform.
+    #[inline]
+    fn next<R: RngCore>(&mut self, rng: &mut R) -> f64 {
+        self.inv_cdf(rng.next_f64_open_open())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dist::poisson::Poisson;
+    use crate::rng::SplitMix64;
+
+    #[test]
+    fn references_sample_like_the_distribution() {
+        let d = Poisson::new(3.0).unwrap();
+        let (mut a, mut b) = (SplitMix64::seed_from_u64(6), SplitMix64::seed_from_u64(6));
+        let mut s = &d;
+        let mut xs = [0; 8];
+        s.fill(&mut a, &mut xs);
+        assert!(xs.iter().all(|&x| x == d.sample(&mut b)));
+
+        let mut c = CategoricalSampler::new(&[0.0, 1.0]).unwrap();
+        assert!((0..50).all(|_| c.next(&mut a) == 1));
+        assert_eq!(c.table().len(), 2);
+    }
+
+    #[test]
+    fn cdf_table_inverts_like_the_distribution() {
+        use crate::dist::binomial::Binomial;
+        let d = Binomial::new(200, 0.3).unwrap();
+        let t = DiscreteCdfTable::covering(&d, 1e-14).unwrap();
+        let (lo, hi) = t.range();
+        assert!(lo > 0 && hi < 200, "{lo}..{hi}");
+        for j in 1..1000 {
+            let u = j as f64 / 1000.0;
+            assert_eq!(t.inv_cdf(u), d.inv_cdf(u), "u={u}");
+        }
+        assert_eq!(t.inv_cdf(0.0), lo);
+        assert_eq!(t.inv_cdf(1.0), hi);
+        assert!((t.cdf(60) - d.cdf(60)).abs() < 1e-14);
+        assert_eq!((t.cdf(lo - 1), t.cdf(hi + 1)), (0.0, 1.0));
+        let full = DiscreteCdfTable::new(&d, 0, 200).unwrap();
+        assert_eq!(full.range(), (0, 200));
+        assert!(DiscreteCdfTable::new(&d, 5, 4).is_err());
+        assert!(DiscreteCdfTable::covering(&d, 0.0).is_err());
+        assert!(t.try_inv_cdf(1.5).is_err());
+    }
+
+    #[test]
+    fn quantile_fit_tracks_the_distribution() {
+        use crate::dist::beta::Beta;
+        use crate::dist::normal::Normal;
+        let d = Beta::new(2.0, 5.0).unwrap();
+        let q = QuantileFit::new(d, 1e-8, 1e-12).unwrap();
+        assert!(q.pieces() < 200, "{} pieces", q.pieces());
+        for j in 1..1000 {
+            let u = j as f64 / 1000.0;
+            let x = d.inv_cdf(u);
+            assert!((q.inv_cdf(u) - x).abs() <= 1e-11 * x, "u={u}");
+        }
+        assert_eq!(q.inv_cdf(1e-10), d.inv_cdf(1e-10));
+        assert_eq!(q.inv_cdf(1.0), 1.0);
+        let mut n = QuantileFit::new(Normal::standard(), 1e-3, 1e-13).unwrap();
+        assert!(n.inv_cdf(0.5).abs() < 1e-13);
+        let mut rng = SplitMix64::seed_from_u64(2);
+        assert!(n.next(&mut rng).is_finite());
+        assert!(QuantileFit::new(d, 0.5, 1e-12).is_err());
+        assert!(QuantileFit::new(d, 1e-3, 0.0).is_err());
+        assert!(q.try_inv_cdf(-0.1).is_err());
+    }
+
+    #[test]
+    fn quantile_fit_reaches_deep_tails_or_errors() {
+        use crate::dist::normal::Normal;
+        let d = Normal::standard();
+        let q = QuantileFit::new(d, 1e-10, 1e-12).unwrap();
+        for u in [
+            1e-10,
+            3e-9,
+            1e-4,
+            0.2,
+            0.5,
+            0.9,
+            1.0 - 1e-6,
+            1.0 - 1e-9,
+            1.0 - 1e-10,
+        ] {
+            let x = d.inv_cdf(u);
+            assert!(
+                (q.inv_cdf(u) - x).abs() <= 1e-11 * x.abs().max(1.0),
+                "u={u}"
+            );
+        }
+        // The upper half is fitted in 1 − u, so it resolves what inv_cdf cannot.
+        let x = d.isf(2e-10);
+        assert!((q.upper.eval(2e-10) - x).abs() <= 1e-11 * x);
+        // Below the rounding noise of the quantile, no amount of bisection helps.
+        assert_eq!(
+            QuantileFit::new(d, 1e-3, 1e-17),
+            Err(DistError::DomainError)
+        );
+    }
+}
@@ -0,0 +1,80 @@
+//! `serde` support (feature `serde`).
+//!
+//! Distributions serialize as their parameters, e.g. `{"mu":0.0,"sigma":1.0}`, and
+//! deserialize through their constructors, so invalid parameters are rejected with the
+//! constructor's [`DistError`](crate::dist::DistError) message.
+
+use crate::dist::{
+    bernoulli::Bernoulli, beta::Beta, binomial::Binomial, chisquared::ChiSquared,
+    exponential::Exponential, gamma::Gamma, geometric::Geometric, lognormal::LogNormal,
+    negative_binomial::NegativeBinomial, normal::Normal, poisson::Poisson, uniform::Uniform,
+};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+macro_rules! serde_via_params {
+    ($($ty:ident { $($field:ident: $fty:ty = $getter:ident),+ };)*) => {$(
+        const _: () = {
+            #[derive(Serialize, Deserialize)]
+            #[serde(deny_unknown_fields)]
+            struct Params {
+                $($field: $fty,)+
+            }
+
+            impl Serialize for $ty {
+                fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                    Params { $($field: self.$getter(),)+ }.serialize(s)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $ty {
+                fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+                    let p = Params::deserialize(d)?;
+                    $ty::new($(p.$field),+).map_err(D::Error::custom)
+                }
+            }
+        };
+    )*};
+}
+
+serde_via_params! {
+    Bernoulli { p: f64 = p };
+    Beta { a: f64 = a, b: f64 = b };
+    Binomial { n: u64 = n, p: f64 = p };
+    ChiSquared { v: f64 = dof };
+    Exponential { lambda: f64 = lambda };
+    Gamma { shape: f64 = shape, scale: f64 = scale };
+    Geometric { p: f64 = p };
+    LogNormal { mu: f64 = mu, sigma: f64 = sigma };
+    NegativeBinomial { r: f64 = r, p: f64 = p };
+    Normal { mu: f64 = mean_param, sigma: f64 = sigma };
+    Poisson { lambda: f64 = lambda };
+    Uniform { a: f64 = a, b: f64 = b };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dist::any::{AnyContinuous, AnyDistribution};
+    use crate::dist::{gamma::Gamma, normal::Normal, poisson::Poisson};
+
+    #[test]
+    fn round_trip_and_validation() {
+        let n = Normal::new(0.5, 2.0).unwrap();
+        let json = serde_json::to_string(&n).unwrap();
+        assert_eq!(json, r#"{"mu":0.5,"sigma":2.0}"#);
+        assert_eq!(serde_json::from_str::<Normal>(&json).unwrap(), n);
+        let err = serde_json::from_str::<Normal>(r#"{"mu":0.0,"sigma":-1.0}"#).unwrap_err();
+        assert!(err.to_string().contains("`sigma` must be positive"));
+        assert!(serde_json::from_str::<Poisson>(r#"{"lambda":1,"k":2}"#).is_err());
+    }
+
+    #[test]
+    fn any_enums() {
+        let any: AnyDistribution = AnyContinuous::from(Gamma::new(2.0, 0.5).unwrap()).into();
+        let json = serde_json::to_string(&any).unwrap();
+        assert_eq!(json, r#"{"Gamma":{"shape":2.0,"scale":0.5}}"#);
+        assert_eq!(serde_json::from_str::<AnyDistribution>(&json).unwrap(), any);
+        let p: AnyDistribution = serde_json::from_str(r#"{"Poisson":{"lambda":3.0}}"#).unwrap();
+        assert_eq!(p.name(), "Poisson");
+    }
+}
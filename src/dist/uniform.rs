@@ -1,7 +1,11 @@
-use crate::dist::{Continuous, DistError, Distribution, Moments};
+use crate::dist::{
+    CharacteristicFunction, Continuous, DistError, Distribution, HigherMoments, Mgf, Moments,
+    Param, SampleFloat, check_finite, check_probability, const_unwrap,
+};
+use crate::num::Float;
 use crate::rng::RngCore;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Uniform {
     a: f64,
     b: f64,
@@ -9,21 +13,92 @@ pub struct Uniform {
 }
 
 impl Uniform {
-    pub fn new(a: f64, b: f64) -> Result<Self, DistError> {
-        if !(a < b && a.is_finite() && b.is_finite()) {
-            return Err(DistError::InvalidParameter);
+    /// U(0, 1).
+    pub const fn standard() -> Self {
+        const_unwrap(Self::new(0.0, 1.0))
+    }
+    pub const fn new(a: f64, b: f64) -> Result<Self, DistError> {
+        const_try!(check_finite("a", a));
+        const_try!(check_finite("b", b));
+        if a >= b {
+            return Err(DistError::InvalidRange { low: a, high: b });
         }
         let inv_width = 1.0 / (b - a);
         Ok(Self { a, b, inv_width })
     }
+    pub fn builder() -> UniformBuilder {
+        UniformBuilder::default()
+    }
     #[inline]
-    pub fn a(&self) -> f64 {
+    pub const fn a(&self) -> f64 {
         self.a
     }
     #[inline]
-    pub fn b(&self) -> f64 {
+    pub const fn b(&self) -> f64 {
         self.b
     }
+    /// Fills `out` by converting 64-word blocks of [`RngCore::fill_u64`] output in a
+    /// straight-line loop, the uniform counterpart of
+    /// [`Normal::sample_fill_wide`](crate::dist::normal::Normal::sample_fill_wide). Matches
+    /// `sample_fill` for generators using the default `next_f64`; generators without
+    /// uniform low bits ([`RngCore::uniform_bits`]) get `sample_fill` itself.
+    pub fn sample_fill_wide<R: RngCore>(&self, rng: &mut R, out: &mut [f64]) {
+        if !rng.uniform_bits() {
+            return self.sample_fill(rng, out);
+        }
+        let (a, w) = (self.a, self.b - self.a);
+        let mut block = [0u64; 64];
+        for chunk in out.chunks_mut(block.len()) {
+            let bits = &mut block[..chunk.len()];
+            rng.fill_u64(bits);
+            for (x, &b) in chunk.iter_mut().zip(bits.iter()) {
+                *x = a + w * crate::rng::convert::closed_open(b);
+            }
+        }
+    }
+}
+
+/// Builder for [`Uniform`]; give both ends of `[a, b]`.
+///
+/// ```
+/// use probability_rs::dist::uniform::Uniform;
+/// let u = Uniform::builder().a(-1.0).b(3.0).build()?;
+/// assert_eq!((u.a(), u.b()), (-1.0, 3.0));
+/// # Ok::<(), probability_rs::dist::DistError>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UniformBuilder {
+    a: Option<f64>,
+    b: Option<f64>,
+}
+
+impl UniformBuilder {
+    pub fn a(mut self, a: f64) -> Self {
+        self.a = Some(a);
+        self
+    }
+    pub fn b(mut self, b: f64) -> Self {
+        self.b = Some(b);
+        self
+    }
+    pub fn build(self) -> Result<Uniform, DistError> {
+        let a = self.a.ok_or(DistError::MissingParameter { name: "a" })?;
+        let b = self.b.ok_or(DistError::MissingParameter { name: "b" })?;
+        Uniform::new(a, b)
+    }
+}
+
+impl Default for Uniform {
+    /// [`Uniform::standard`].
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl std::fmt::Display for Uniform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Uniform(a={}, b={})", Param(self.a), Param(self.b))
+    }
 }
 
 impl Distribution for Uniform {
@@ -37,12 +112,58 @@ impl Distribution for Uniform {
             (x - self.a) * self.inv_width
         }
     }
+    fn cdf_slice(&self, xs: &[f64], out: &mut [f64]) {
+        assert_eq!(xs.len(), out.len(), "cdf_slice: length mismatch");
+        let (a, b, inv_width) = (self.a, self.b, self.inv_width);
+        for (o, &x) in out.iter_mut().zip(xs) {
+            let v = (x - a) * inv_width;
+            *o = if x <= a {
+                0.0
+            } else if x >= b {
+                1.0
+            } else {
+                v
+            };
+        }
+    }
     fn in_support(&self, x: f64) -> bool {
         x >= self.a && x <= self.b && x.is_finite()
     }
     fn sample<R: RngCore>(&self, rng: &mut R) -> f64 {
         self.a + (self.b - self.a) * rng.next_f64()
     }
+    fn sample_fill<R: RngCore>(&self, rng: &mut R, out: &mut [f64]) {
+        // Same draws as `sample`, with the width hoisted out of the loop.
+        let (a, w) = (self.a, self.b - self.a);
+        for x in out {
+            *x = a + w * rng.next_f64();
+        }
+    }
+}
+
+impl SampleFloat for Uniform {
+    /// In `[a, b)` with the ends rounded to `F`; as in
+    /// [`gen_range_f64`](crate::rng::RngExt::gen_range_f64), draws that round up to `b` are
+    /// redrawn. An interval narrower than one step of `F` gives `a`.
+    fn sample_float<F: Float, R: RngCore>(&self, rng: &mut R) -> F {
+        let (a, b) = (F::from_f64(self.a), F::from_f64(self.b));
+        if a >= b {
+            return a;
+        }
+        let w = b - a;
+        loop {
+            let u = rng.next_float::<F>();
+            // The width can overflow `F` even when both ends fit.
+            let x = if w.is_finite() {
+                a + w * u
+            } else {
+                a * (F::ONE - u) + b * u
+            };
+            if x < b {
+                return x;
+            }
+        }
+    }
 }
 
 impl Continuous for Uniform {
@@ -53,9 +174,24 @@ impl Continuous for Uniform {
             0.0
         }
     }
-    fn inv_cdf(&self, p: f64) -> f64 {
-        debug_assert!((0.0..=1.0).contains(&p));
-        self.a + (self.b - self.a) * p
+    fn pdf_slice(&self, xs: &[f64], out: &mut [f64]) {
+        assert_eq!(xs.len(), out.len(), "pdf_slice: length mismatch");
+        let (a, b, inv_width) = (self.a, self.b, self.inv_width);
+        for (o, &x) in out.iter_mut().zip(xs) {
+            *o = if x >= a && x <= b { inv_width } else { 0.0 };
+        }
+    }
+    fn support(&self) -> (f64, f64) {
+        (self.a, self.b)
+    }
+    fn try_inv_cdf(&self, p: f64) -> Result<f64, DistError> {
+        let p = check_probability(p)?;
+        Ok(self.a + (self.b - self.a) * p)
+    }
+
+    fn try_isf(&self, q: f64) -> Result<f64, DistError> {
+        let q = check_probability(q)?;
+        Ok(self.b - (self.b - self.a) * q)
     }
 }
 
@@ -77,6 +213,56 @@ impl Moments for Uniform {
     }
 }
 
+impl Mgf for Uniform {
+    fn mgf(&self, t: f64) -> f64 {
+        self.cgf(t).exp()
+    }
+    fn cgf(&self, t: f64) -> f64 {
+        // ln[(e^{tb} - e^{ta}) / (t(b - a))] = ta + ln(expm1(w) / w) with w = t(b - a):
+        // expm1 keeps t near 0 accurate, and for |w| > 1 the log is taken term by term so
+        // an underflowing e^{ta} never meets an overflowing e^w.
+        if t == 0.0 {
+            return 0.0;
+        }
+        let w = t * (self.b - self.a);
+        let g = if w.abs() <= 1.0 {
+            (w.exp_m1() / w).ln()
+        } else if w > 0.0 {
+            w - w.ln() + (-(-w).exp()).ln_1p()
+        } else {
+            (-w.exp()).ln_1p() - (-w).ln()
+        };
+        t * self.a + g
+    }
+}
+
+impl CharacteristicFunction for Uniform {
+    fn cf(&self, t: f64) -> (f64, f64) {
+        // (e^{itb} − e^{ita}) / (it(b − a))
+        if t == 0.0 {
+            return (1.0, 0.0);
+        }
+        let tw = t * (self.b - self.a);
+        let re = ((t * self.b).sin() - (t * self.a).sin()) / tw;
+        let im = ((t * self.a).cos() - (t * self.b).cos()) / tw;
+        (re, im)
+    }
+}
+
+impl HigherMoments for Uniform {
+    fn raw_moment(&self, n: u32) -> f64 {
+        // (b^{n+1} − a^{n+1}) / ((n+1)(b − a))
+        let m = (n + 1) as i32;
+        (self.b.powi(m) - self.a.powi(m)) * self.inv_width / m as f64
+    }
+    fn central_moment(&self, n: u32) -> f64 {
+        if n % 2 == 1 {
+            return 0.0;
+        }
+        (0.5 * (self.b - self.a)).powi(n as i32) / (n + 1) as f64
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,9 +287,95 @@ mod tests {
         assert!((u.kurtosis() + 6.0 / 5.0).abs() < 1e-15);
     }
 
+    #[test]
+    fn mgf_near_zero() {
+        let u = Uniform::new(-1.0, 3.0).unwrap();
+        assert_eq!(u.mgf(0.0), 1.0);
+        assert!((u.mgf(1e-12) - 1.0).abs() < 1e-11);
+        let t: f64 = 0.5;
+        let expected = ((3.0 * t).exp() - (-t).exp()) / (4.0 * t);
+        assert!((u.mgf(t) - expected).abs() < 1e-14);
+    }
+
+    #[test]
+    fn mgf_with_far_apart_ends() {
+        // e^{ta} underflows and e^{t(b−a)} overflows; ln M = tb − ln(t(b − a)) + ln(1 − e^{−w}).
+        let u = Uniform::new(-1000.0, 700.0).unwrap();
+        let want = 700.0 - 1700f64.ln();
+        assert!((u.cgf(1.0) / want - 1.0).abs() < 1e-15);
+        assert!((u.mgf(1.0) / want.exp() - 1.0).abs() < 1e-12);
+        let u = Uniform::new(-1000.0, 1000.0).unwrap();
+        assert_eq!(u.mgf(1.0), f64::INFINITY);
+        assert!((u.cgf(1.0) - (1000.0 - 2000f64.ln())).abs() < 1e-12);
+        assert!((u.cgf(-1.0) - (1000.0 - 2000f64.ln())).abs() < 1e-12);
+    }
+
     #[test]
     fn entropy_uniform() {
         let u = Uniform::new(2.0, 5.0).unwrap();
         assert!((u.entropy() - (3.0f64).ln()).abs() < 1e-15);
     }
+
+    #[test]
+    fn higher_moments() {
+        let u = Uniform::new(0.0, 2.0).unwrap();
+        assert!((u.raw_moment(2) - 4.0 / 3.0).abs() < 1e-15);
+        assert!((u.central_moment(4) - 1.0 / 5.0).abs() < 1e-15);
+        // Default expansion agrees with the direct form.
+        let b = crate::dist::beta::Beta::new(1.0, 1.0).unwrap();
+        assert!((b.central_moment(4) - 1.0 / 80.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn sample_fill_matches_sample() {
+        let u = Uniform::new(-1.0, 3.0).unwrap();
+        let mut a = crate::rng::SplitMix64::seed_from_u64(4);
+        let mut b = crate::rng::SplitMix64::seed_from_u64(4);
+        let mut xs = [0.0; 8];
+        u.sample_fill(&mut a, &mut xs);
+        assert!(xs.iter().all(|&x| x == u.sample(&mut b)));
+        u.sample_fill_wide(&mut a, &mut xs);
+        assert!(xs.iter().all(|&x| x == u.sample(&mut b)));
+    }
+
+    #[test]
+    fn sample_float_stays_below_b() {
+        // 1 + (1 − 2^-24) rounds to 2 in f32; that draw is replaced by the next one.
+        let u = Uniform::new(1.0, 2.0).unwrap();
+        let mut rng = crate::rng::mock::ScriptedRng::new(&[u64::MAX, 0]);
+        assert_eq!(u.sample_float::<f32, _>(&mut rng), 1.0);
+        let wide = Uniform::new(-3e38, 3e38).unwrap();
+        let mut rng = crate::rng::SplitMix64::seed_from_u64(1);
+        assert!((0..100).all(|_| wide.sample_float::<f32, _>(&mut rng).is_finite()));
+        let narrow = Uniform::new(1.0, 1.0 + 1e-12).unwrap();
+        assert_eq!(narrow.sample_float::<f32, _>(&mut rng), 1.0);
+    }
+
+    #[test]
+    fn constructor_errors() {
+        assert_eq!(
+            Uniform::new(2.0, 1.0).unwrap_err(),
+            DistError::InvalidRange {
+                low: 2.0,
+                high: 1.0
+            }
+        );
+        assert_eq!(
+            Uniform::new(0.0, f64::INFINITY).unwrap_err(),
+            DistError::NotFinite { name: "b" }
+        );
+    }
+
+    #[test]
+    fn slice_evaluation_matches_pointwise() {
+        let u = Uniform::new(-1.0, 3.0).unwrap();
+        let xs = [-2.0, -1.0, 0.5, 3.0, 4.0, f64::INFINITY];
+        let (mut pdf, mut cdf) = ([0.0; 6], [0.0; 6]);
+        u.pdf_slice(&xs, &mut pdf);
+        u.cdf_slice(&xs, &mut cdf);
+        for i in 0..6 {
+            assert_eq!(pdf[i], u.pdf(xs[i]));
+            assert_eq!(cdf[i], u.cdf(xs[i]));
+        }
+    }
 }
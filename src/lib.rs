@@ -12,14 +12,14 @@
 //! Quick examples:
 //! ```
 //! use probability_rs::{rng::SplitMix64, dist::normal::Normal, Continuous, Distribution};
-//! let normal = Normal::new(0.0, 1.0).unwrap();
+//! let normal = Normal::standard();
 //! let mut rng = SplitMix64::seed_from_u64(123);
 //! let x = normal.sample(&mut rng);
 //! let p = normal.pdf(0.0);
 //! let c = normal.cdf(0.0);
 //! let q = normal.inv_cdf(0.975); // ~ 1.96
 //! assert!((p - 0.39894228).abs() < 1e-7);
-//! assert!((c - 0.5).abs() < 2e-6); // tolerance due to erf approximation
+//! assert_eq!(c, 0.5);
 //! assert!((q - 1.95996).abs() < 5e-3);
 //! ```
 //!
@@ -30,6 +30,11 @@
 pub mod dist;
 pub mod num;
 pub mod rng;
+pub mod stats;
+pub mod telemetry;
 
 // Re-export commonly used traits at crate root for ergonomic imports
-pub use dist::{Continuous, Discrete, Distribution, Moments};
+pub use dist::{
+    CharacteristicFunction, Continuous, ContinuousDyn, Discrete, DiscreteDyn, Distribution,
+    HigherMoments, Mgf, Moments,
+};
@@ -0,0 +1,126 @@
+//! Chebyshev approximation: fit a smooth function once on an interval, then evaluate the
+//! truncated series by Clenshaw's recurrence at a fixed cost per call.
+//!
+//! ```
+//! use probability_rs::num::cheb::ChebyshevFit;
+//! use probability_rs::num::erf;
+//!
+//! let fit = ChebyshevFit::fit(erf, -2.0, 2.0, 40);
+//! assert!(fit.error_estimate() < 1e-14);
+//! assert!((fit.eval(0.3) - erf(0.3)).abs() < 1e-14);
+//! ```
+
+/// A truncated Chebyshev series `Σ cⱼ Tⱼ(t)` on `[a, b]`, with `t = (2x − a − b)/(b − a)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChebyshevFit {
+    a: f64,
+    b: f64,
+    // c[0] already halved, so the series is a plain Σ c[j] T_j(t).
+    coef: Vec<f64>,
+}
+
+impl ChebyshevFit {
+    /// Interpolates `f` at the `n` Chebyshev nodes of `[a, b]`, giving a series of degree
+    /// `n − 1` that is within a small factor of the best polynomial approximation of that
+    /// degree. Costs exactly `n` calls of `f`. Panics unless `n >= 1` and `a < b` are finite.
+    pub fn fit<F: FnMut(f64) -> f64>(mut f: F, a: f64, b: f64, n: usize) -> Self {
+        assert!(n >= 1, "ChebyshevFit::fit requires n >= 1");
+        assert!(
+            a.is_finite() && b.is_finite() && a < b,
+            "ChebyshevFit::fit requires finite a < b"
+        );
+        let (mid, half) = (0.5 * (b + a), 0.5 * (b - a));
+        let theta = |k: usize| std::f64::consts::PI * (k as f64 + 0.5) / n as f64;
+        let fx: Vec<f64> = (0..n).map(|k| f(mid + half * theta(k).cos())).collect();
+        let mut coef: Vec<f64> = (0..n)
+            .map(|j| {
+                let s: f64 = fx
+                    .iter()
+                    .enumerate()
+                    .map(|(k, y)| y * (j as f64 * theta(k)).cos())
+                    .sum();
+                2.0 * s / n as f64
+            })
+            .collect();
+        coef[0] *= 0.5;
+        Self { a, b, coef }
+    }
+
+    /// The series at `x`, by Clenshaw's recurrence. Outside `[a, b]` this extrapolates the
+    /// polynomial, which quickly stops resembling `f`.
+    pub fn eval(&self, x: f64) -> f64 {
+        let t = (2.0 * x - self.a - self.b) / (self.b - self.a);
+        let (mut b1, mut b2) = (0.0, 0.0);
+        for &c in self.coef[1..].iter().rev() {
+            (b1, b2) = (2.0 * t * b1 - b2 + c, b1);
+        }
+        t * b1 - b2 + self.coef[0]
+    }
+
+    /// Estimated maximum absolute error on `[a, b]`: the size of the last two coefficients,
+    /// which bounds the neglected tail once the coefficients decay geometrically.
+    pub fn error_estimate(&self) -> f64 {
+        self.coef.iter().rev().take(2).map(|c| c.abs()).sum()
+    }
+
+    /// The same fit with the trailing coefficients dropped while their summed magnitude
+    /// stays within `tol`, for cheaper evaluation at a known accuracy cost.
+    pub fn truncated(&self, tol: f64) -> Self {
+        let mut dropped = 0.0;
+        let mut len = self.coef.len();
+        while len > 1 && dropped + self.coef[len - 1].abs() <= tol {
+            dropped += self.coef[len - 1].abs();
+            len -= 1;
+        }
+        Self {
+            a: self.a,
+            b: self.b,
+            coef: self.coef[..len].to_vec(),
+        }
+    }
+
+    /// The interval `(a, b)` the fit was made on.
+    #[inline]
+    pub fn domain(&self) -> (f64, f64) {
+        (self.a, self.b)
+    }
+
+    /// The coefficients `cⱼ` of `Σ cⱼ Tⱼ(t)`, lowest degree first.
+    #[inline]
+    pub fn coefficients(&self) -> &[f64] {
+        &self.coef
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reproduces_polynomials_and_smooth_functions() {
+        // T_3 on [-1, 1] is 4t³ − 3t; on [1, 3] it is the same in t = x − 2.
+        let p = ChebyshevFit::fit(|x| 4.0 * (x - 2.0).powi(3) - 3.0 * (x - 2.0), 1.0, 3.0, 6);
+        let c = p.coefficients();
+        assert!((c[3] - 1.0).abs() < 1e-15);
+        assert!(c.iter().enumerate().all(|(j, c)| j == 3 || c.abs() < 1e-15));
+        assert_eq!(p.truncated(1e-14).coefficients().len(), 4);
+        assert_eq!(p.domain(), (1.0, 3.0));
+
+        let f = ChebyshevFit::fit(f64::exp, 0.0, 2.0, 20);
+        assert!(f.error_estimate() < 1e-14);
+        for i in 0..=40 {
+            let x = 0.05 * i as f64;
+            assert!((f.eval(x) - x.exp()).abs() < 1e-14, "x = {x}");
+        }
+        // A kink converges slowly, and the estimate says so.
+        let k = ChebyshevFit::fit(f64::abs, -1.0, 1.0, 20);
+        assert!(k.error_estimate() > 1e-3);
+        assert_eq!(ChebyshevFit::fit(|_| 2.5, 0.0, 1.0, 1).eval(0.7), 2.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "finite a < b")]
+    fn rejects_an_empty_interval() {
+        ChebyshevFit::fit(f64::sin, 1.0, 1.0, 8);
+    }
+}
@@ -0,0 +1,100 @@
+//! Minimal floating-point abstraction over `f32` and `f64`.
+//!
+//! The shared vocabulary for code that is generic over precision: the RNG's
+//! [`next_float`](crate::rng::RngCore::next_float) and
+//! [`SampleFloat`](crate::dist::SampleFloat), which draws Uniform, Exponential, Normal and
+//! LogNormal variates entirely in `f32`. Densities, CDFs and quantiles stay in `f64`.
+
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Operations shared by `f32` and `f64`.
+pub trait Float:
+    Copy
+    + PartialOrd
+    + Debug
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const EPSILON: Self;
+    /// Explicit mantissa bits plus the implicit one (24 for `f32`, 53 for `f64`).
+    const PRECISION: u32;
+
+    fn from_f64(x: f64) -> Self;
+    fn to_f64(self) -> f64;
+    /// Uniform in `[0,1)` from the top [`PRECISION`](Self::PRECISION) bits of `bits`.
+    fn unit_from_bits(bits: u64) -> Self;
+
+    fn abs(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn ln(self) -> Self;
+    fn exp(self) -> Self;
+    fn is_finite(self) -> bool;
+}
+
+macro_rules! impl_float {
+    ($t:ty, $precision:expr) => {
+        impl Float for $t {
+            const ZERO: Self = 0.0;
+            const ONE: Self = 1.0;
+            const EPSILON: Self = <$t>::EPSILON;
+            const PRECISION: u32 = $precision;
+
+            #[inline]
+            fn from_f64(x: f64) -> Self {
+                x as $t
+            }
+            #[inline]
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+            #[inline]
+            fn unit_from_bits(bits: u64) -> Self {
+                const DEN: $t = (1u64 << $precision) as $t;
+                (bits >> (64 - $precision)) as $t / DEN
+            }
+            #[inline]
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+            #[inline]
+            fn sqrt(self) -> Self {
+                <$t>::sqrt(self)
+            }
+            #[inline]
+            fn ln(self) -> Self {
+                <$t>::ln(self)
+            }
+            #[inline]
+            fn exp(self) -> Self {
+                <$t>::exp(self)
+            }
+            #[inline]
+            fn is_finite(self) -> bool {
+                <$t>::is_finite(self)
+            }
+        }
+    };
+}
+
+impl_float!(f32, 24);
+impl_float!(f64, 53);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_from_bits_stays_below_one() {
+        assert_eq!(f32::unit_from_bits(0), 0.0);
+        assert!(f32::unit_from_bits(u64::MAX) < 1.0);
+        assert!(f64::unit_from_bits(u64::MAX) < 1.0);
+        assert_eq!(f64::unit_from_bits(1 << 63), 0.5);
+    }
+}
@@ -0,0 +1,138 @@
+//! Arithmetic on log-probabilities.
+//!
+//! Sums and complements of terms held as logarithms, so likelihoods far below
+//! `f64::MIN_POSITIVE` can still be combined; `−∞` stands for zero throughout.
+
+use super::LN_2;
+
+/// `ln(eᵃ + eᵇ)` without overflow or underflow.
+pub fn logaddexp(a: f64, b: f64) -> f64 {
+    if a.is_nan() || b.is_nan() {
+        return f64::NAN;
+    }
+    if a == b {
+        // also covers two infinities of the same sign, where a − b is NaN
+        return a + LN_2;
+    }
+    let (hi, lo) = if a > b { (a, b) } else { (b, a) };
+    hi + (lo - hi).exp().ln_1p()
+}
+
+/// `ln Σ exp(xᵢ)`, shifted by the largest term; `−∞` for an empty slice.
+pub fn log_sum_exp(xs: &[f64]) -> f64 {
+    let mut top = None;
+    for (i, &x) in xs.iter().enumerate() {
+        if x.is_nan() {
+            return f64::NAN;
+        }
+        if top.is_none_or(|(_, m)| x > m) {
+            top = Some((i, x));
+        }
+    }
+    let Some((imax, m)) = top else {
+        return f64::NEG_INFINITY;
+    };
+    if m.is_infinite() {
+        return m;
+    }
+    // The largest term contributes exactly 1; ln_1p keeps the rest when they are tiny.
+    let rest: f64 = xs
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i != imax)
+        .map(|(_, &x)| (x - m).exp())
+        .sum();
+    m + rest.ln_1p()
+}
+
+/// `ln(1 − eˣ)` for `x <= 0`, e.g. `ln(1 − p)` from `ln p`; NaN for `x > 0`.
+///
+/// Mächler's switch at `−ln 2` between `ln(−expm1(x))` and `ln1p(−eˣ)` keeps full
+/// relative accuracy on both sides.
+pub fn log1mexp(x: f64) -> f64 {
+    if x > 0.0 {
+        f64::NAN
+    } else if x > -LN_2 {
+        (-x.exp_m1()).ln()
+    } else {
+        (-x.exp()).ln_1p()
+    }
+}
+
+/// Log-odds `ln(p / (1 − p))`; `±∞` at 1 and 0, NaN outside `[0, 1]`.
+pub fn logit(p: f64) -> f64 {
+    if !(0.0..=1.0).contains(&p) {
+        f64::NAN
+    } else if (0.25..=0.75).contains(&p) {
+        // 2p − 1 is exact here, and the ratio is too close to 1 for ln
+        2.0 * (2.0 * p - 1.0).atanh()
+    } else {
+        (p / (1.0 - p)).ln()
+    }
+}
+
+/// Logistic sigmoid `1 / (1 + e⁻ˣ)`, the inverse of [`logit`].
+pub fn expit(x: f64) -> f64 {
+    if x >= 0.0 {
+        1.0 / (1.0 + (-x).exp())
+    } else {
+        let e = x.exp();
+        e / (1.0 + e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combines_terms_far_below_underflow() {
+        assert_eq!(logaddexp(-1000.0, -1000.0), -1000.0 + LN_2);
+        // mpmath log(exp(a) + exp(b))
+        assert!((logaddexp(-800.0, -803.0) / -799.9514126484263 - 1.0).abs() < 1e-15);
+        assert_eq!(logaddexp(f64::NEG_INFINITY, 3.0), 3.0);
+        assert_eq!(logaddexp(f64::INFINITY, f64::INFINITY), f64::INFINITY);
+
+        let xs = [-1e4, -1e4 + 1.0, -1e4 - 2.0];
+        // mpmath log(1 + e + e^-2)
+        assert!((log_sum_exp(&xs) - (-1e4 + 1.3490122167681864)).abs() < 1e-11);
+        assert_eq!(log_sum_exp(&[]), f64::NEG_INFINITY);
+        assert_eq!(log_sum_exp(&[f64::NEG_INFINITY; 3]), f64::NEG_INFINITY);
+        assert!(log_sum_exp(&[0.0, f64::NAN]).is_nan());
+        // a term below the largest one's ulp still shows up through ln_1p
+        assert_eq!(log_sum_exp(&[0.0, -40.0]), logaddexp(0.0, -40.0));
+    }
+
+    #[test]
+    fn complements_and_odds() {
+        // mpmath log(1 - exp(x))
+        for (x, want) in [
+            (-1e-20, -46.051701859880914),
+            (-0.5, -0.9327521295671886),
+            (-2.0, -0.14541345786885906),
+            (-50.0, -1.9287498479639178e-22),
+        ] {
+            assert!((log1mexp(x) / want - 1.0).abs() < 1e-15, "log1mexp({x})");
+        }
+        assert_eq!(log1mexp(0.0), f64::NEG_INFINITY);
+        assert!(log1mexp(1.0).is_nan());
+
+        // mpmath log(p / (1 - p))
+        for (p, want) in [
+            (1e-300, -690.7755278982137),
+            (0.5 + 1e-9, 3.999999886872274e-09),
+            (0.999, 6.906754778648553),
+        ] {
+            assert!((logit(p) / want - 1.0).abs() < 1e-15, "logit({p})");
+            assert!(
+                (expit(logit(p)) / p - 1.0).abs() < 1e-12,
+                "expit(logit({p}))"
+            );
+        }
+        assert_eq!(
+            (logit(0.0), logit(1.0), logit(0.5)),
+            (f64::NEG_INFINITY, f64::INFINITY, 0.0)
+        );
+        assert_eq!((expit(-800.0), expit(800.0)), (0.0, 1.0));
+    }
+}
@@ -0,0 +1,123 @@
+//! Bracketed root finding.
+
+// More than enough for Brent's worst case (bisection through the whole f64 range).
+const MAX_ITER: usize = 2200;
+
+/// A root of `f` in `[lo, hi]` by Brent's method: inverse quadratic interpolation and
+/// secant steps, falling back to bisection whenever they stall, so it converges whenever
+/// the bracket is valid and usually in a handful of evaluations for smooth `f`.
+///
+/// Iterates until the bracket is within 2 ulp of the root (or `f` is exactly 0). Panics
+/// unless `f(lo)` and `f(hi)` are of opposite sign (or one of them is 0).
+///
+/// ```
+/// use probability_rs::num::roots::brent;
+///
+/// let r = brent(|x| x * x - 2.0, 0.0, 2.0);
+/// assert!((r - 2f64.sqrt()).abs() <= 2.0 * f64::EPSILON);
+/// ```
+pub fn brent<F: FnMut(f64) -> f64>(mut f: F, lo: f64, hi: f64) -> f64 {
+    let (mut a, mut b) = (lo, hi);
+    let (mut fa, mut fb) = (f(a), f(b));
+    if fa == 0.0 {
+        return a;
+    }
+    if fb == 0.0 {
+        return b;
+    }
+    assert!(
+        (fa < 0.0 && fb > 0.0) || (fa > 0.0 && fb < 0.0),
+        "brent: f({lo}) = {fa} and f({hi}) = {fb} do not bracket a root"
+    );
+    // b is the best estimate, c the previous one on the other side of the root, and
+    // a the previous b.
+    let (mut c, mut fc) = (a, fa);
+    let mut d = b - a;
+    let mut e = d;
+    for _ in 0..MAX_ITER {
+        if (fb > 0.0) == (fc > 0.0) {
+            (c, fc) = (a, fa);
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            (a, fa) = (b, fb);
+            (b, fb) = (c, fc);
+            (c, fc) = (a, fa);
+        }
+        let tol = 2.0 * f64::EPSILON * b.abs() + 0.5 * f64::MIN_POSITIVE;
+        let m = 0.5 * (c - b);
+        if m.abs() <= tol || fb == 0.0 {
+            return b;
+        }
+        if e.abs() >= tol && fa.abs() > fb.abs() {
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                // secant
+                (2.0 * m * s, 1.0 - s)
+            } else {
+                // inverse quadratic through a, b, c
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2.0 * m * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            if p > 0.0 {
+                q = -q;
+            } else {
+                p = -p;
+            }
+            // Accept the step only if it stays well inside the bracket and shrinks faster
+            // than the one before last.
+            if 2.0 * p < (3.0 * m * q - (tol * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = m;
+                e = m;
+            }
+        } else {
+            d = m;
+            e = m;
+        }
+        (a, fa) = (b, fb);
+        b += if d.abs() > tol { d } else { tol.copysign(m) };
+        fb = f(b);
+    }
+    b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converges_on_smooth_and_awkward_functions() {
+        let mut calls = 0;
+        let r = brent(
+            |x: f64| {
+                calls += 1;
+                x.cos() - x
+            },
+            0.0,
+            1.0,
+        );
+        assert!((r - 0.7390851332151607).abs() <= 2e-16);
+        assert!(calls < 12, "{calls} evaluations");
+        // A root at 0, reversed bracket, a step function and a flat-then-steep function.
+        assert_eq!(brent(|x| x, 1.0, -1.0), 0.0);
+        let step = brent(|x| if x < 0.3 { -1.0 } else { 1.0 }, 0.0, 1.0);
+        assert!((step - 0.3).abs() <= 1e-16);
+        let r = brent(|x: f64| x.powi(9) - 1e-9, 0.0, 4.0);
+        assert!((r / 10f64.powf(-1.0) - 1.0).abs() < 1e-14);
+        assert_eq!(brent(|x| x - 2.0, 2.0, 5.0), 2.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "do not bracket")]
+    fn rejects_an_invalid_bracket() {
+        brent(|x| x * x + 1.0, -1.0, 1.0);
+    }
+}
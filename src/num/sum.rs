@@ -0,0 +1,81 @@
+//! Compensated summation.
+
+use std::ops::AddAssign;
+
+/// Neumaier's variant of Kahan summation: carries the rounding error of every addition
+/// in a separate term, so the result is accurate to about one ulp of the exact sum
+/// regardless of the number of terms (and also when a term exceeds the running total).
+///
+/// ```
+/// use probability_rs::num::sum::Kahan;
+///
+/// let mut s = Kahan::new();
+/// s += 1.0;
+/// for _ in 0..10_000 {
+///     s += 1e-16;
+/// }
+/// assert!((s.sum() - (1.0 + 1e-12)).abs() < 1e-15);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Kahan {
+    sum: f64,
+    comp: f64,
+}
+
+impl Kahan {
+    pub const fn new() -> Self {
+        Self {
+            sum: 0.0,
+            comp: 0.0,
+        }
+    }
+
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        let t = self.sum + x;
+        if self.sum.abs() >= x.abs() {
+            self.comp += (self.sum - t) + x;
+        } else {
+            self.comp += (x - t) + self.sum;
+        }
+        self.sum = t;
+    }
+
+    /// The compensated total.
+    #[inline]
+    pub fn sum(&self) -> f64 {
+        self.sum + self.comp
+    }
+}
+
+impl AddAssign<f64> for Kahan {
+    #[inline]
+    fn add_assign(&mut self, x: f64) {
+        self.add(x);
+    }
+}
+
+impl FromIterator<f64> for Kahan {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut s = Self::new();
+        iter.into_iter().for_each(|x| s.add(x));
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recovers_lost_low_order_terms() {
+        let naive: f64 = std::iter::once(1.0)
+            .chain(std::iter::repeat_n(1e-16, 10_000))
+            .sum();
+        assert_eq!(naive, 1.0);
+        // Neumaier also handles terms larger than the running sum.
+        let s: Kahan = [1.0, 1e100, 1.0, -1e100].into_iter().collect();
+        assert_eq!(s.sum(), 2.0);
+        assert_eq!(Kahan::new().sum(), 0.0);
+    }
+}
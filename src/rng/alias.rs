@@ -0,0 +1,164 @@
+//! Walker/Vose alias tables: O(1) draws of an index with arbitrary fixed weights.
+
+use super::{RngCore, RngExt};
+use crate::dist::DistError;
+
+/// Alias table over indices `0..weights.len()`, `P(i) = weights[i] / sum(weights)`.
+///
+/// Build once in O(n), then each draw costs one index and one uniform. Use
+/// [`rebuild`](Self::rebuild) to change the weights without reallocating.
+///
+/// ```
+/// use probability_rs::rng::{SplitMix64, alias::AliasTable};
+///
+/// let table = AliasTable::new(&[1.0, 0.0, 3.0]).unwrap();
+/// let mut rng = SplitMix64::seed_from_u64(4);
+/// let i = table.sample(&mut rng);
+/// assert!(i == 0 || i == 2);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AliasTable {
+    prob: Vec<f64>,
+    alias: Vec<usize>,
+    // Worklists reused by `rebuild`.
+    small: Vec<usize>,
+    large: Vec<usize>,
+}
+
+impl AliasTable {
+    /// Errors if `weights` is empty, has a negative or non-finite entry, or sums to zero
+    /// ([`DistError::NonPositive`]) or overflows to infinity ([`DistError::NotFinite`]).
+    pub fn new(weights: &[f64]) -> Result<Self, DistError> {
+        let mut table = Self {
+            prob: Vec::new(),
+            alias: Vec::new(),
+            small: Vec::new(),
+            large: Vec::new(),
+        };
+        table.rebuild(weights)?;
+        Ok(table)
+    }
+
+    /// Replaces the weights, reusing the existing buffers. On error the table is unchanged.
+    pub fn rebuild(&mut self, weights: &[f64]) -> Result<(), DistError> {
+        let mut total = 0.0;
+        for &w in weights {
+            if !w.is_finite() {
+                return Err(DistError::NotFinite { name: "weights" });
+            }
+            if w < 0.0 {
+                return Err(DistError::NonPositive {
+                    name: "weights",
+                    value: w,
+                });
+            }
+            total += w;
+        }
+        if total == f64::INFINITY {
+            return Err(DistError::NotFinite {
+                name: "total weight",
+            });
+        }
+        if total <= 0.0 {
+            return Err(DistError::NonPositive {
+                name: "total weight",
+                value: total,
+            });
+        }
+
+        let n = weights.len();
+        let scale = n as f64 / total;
+        self.prob.clear();
+        self.prob.extend(weights.iter().map(|&w| w * scale));
+        self.alias.clear();
+        self.alias.extend(0..n);
+        self.small.clear();
+        self.large.clear();
+        for (i, &p) in self.prob.iter().enumerate() {
+            if p < 1.0 {
+                self.small.push(i);
+            } else {
+                self.large.push(i);
+            }
+        }
+        // Vose: pair each under-full column with an over-full one.
+        while let (Some(&s), Some(&l)) = (self.small.last(), self.large.last()) {
+            self.small.pop();
+            self.alias[s] = l;
+            self.prob[l] -= 1.0 - self.prob[s];
+            if self.prob[l] < 1.0 {
+                self.large.pop();
+                self.small.push(l);
+            }
+        }
+        // Leftovers are full up to rounding.
+        for &i in self.small.iter().chain(&self.large) {
+            self.prob[i] = 1.0;
+        }
+        Ok(())
+    }
+
+    /// An index drawn with probability proportional to its weight.
+    #[inline]
+    pub fn sample<R: RngCore + ?Sized>(&self, rng: &mut R) -> usize {
+        let i = rng.gen_index(self.prob.len());
+        if rng.next_f64() < self.prob[i] {
+            i
+        } else {
+            self.alias[i]
+        }
+    }
+
+    /// Number of indices.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.prob.len()
+    }
+
+    /// Always `false`: a table has at least one index.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.prob.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+
+    #[test]
+    fn frequencies_follow_weights() {
+        let table = AliasTable::new(&[1.0, 2.0, 0.0, 5.0]).unwrap();
+        let mut rng = SplitMix64::seed_from_u64(9);
+        let mut counts = [0u32; 4];
+        for _ in 0..80_000 {
+            counts[table.sample(&mut rng)] += 1;
+        }
+        assert_eq!(counts[2], 0);
+        for (c, want) in counts.iter().zip([10_000.0, 20_000.0, 0.0, 50_000.0]) {
+            assert!((*c as f64 - want).abs() < 600.0, "{counts:?}");
+        }
+    }
+
+    #[test]
+    fn rebuild_validates_and_keeps_old_table() {
+        let mut table = AliasTable::new(&[1.0, 1.0]).unwrap();
+        let before = table.clone();
+        assert!(table.rebuild(&[1.0, -1.0]).is_err());
+        assert!(table.rebuild(&[0.0, 0.0]).is_err());
+        assert!(table.rebuild(&[f64::NAN]).is_err());
+        assert!(table.rebuild(&[]).is_err());
+        assert_eq!(
+            table.rebuild(&[f64::MAX, f64::MAX]).unwrap_err(),
+            DistError::NotFinite {
+                name: "total weight"
+            }
+        );
+        assert_eq!(table, before);
+        table.rebuild(&[0.0, 0.0, 2.0]).unwrap();
+        assert_eq!(table.len(), 3);
+        let mut rng = SplitMix64::seed_from_u64(1);
+        assert!((0..100).all(|_| table.sample(&mut rng) == 2));
+    }
+}
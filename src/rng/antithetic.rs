@@ -0,0 +1,123 @@
+//! Antithetic variates: replay a pass of draws with every word complemented.
+//!
+//! Complementing a `u64` maps the uniform `u` to `1 - u` (exactly for
+//! [`next_f64_open_open`](RngCore::next_f64_open_open), up to one 2^-53 step for
+//! `next_f64`), so an inverse-transform sampler fed the complemented pass returns the
+//! mirrored quantile. Averaging `f(x)` and `f(x')` cancels the odd part of `f`'s
+//! variation, often a large variance reduction for monotone `f`.
+//!
+//! The pairing only propagates to the variates when a sampler maps its uniforms
+//! monotonically to the output (Uniform, Exponential, Geometric, ...). Rejection samplers
+//! may draw a different number of words on the second pass; extra words then come fresh
+//! from the inner generator. Symmetric distributions such as Normal override
+//! [`Distribution::sample_antithetic`](crate::Distribution::sample_antithetic) to reflect
+//! the variate directly instead.
+
+use super::RngCore;
+
+/// Words remembered per pass; later draws in a long pass are not replayed.
+const CAPACITY: usize = 16;
+
+/// Records the words drawn in one pass and replays them complemented after
+/// [`flip`](Self::flip).
+///
+/// ```
+/// use probability_rs::rng::{RngCore, SplitMix64, antithetic::Antithetic};
+///
+/// let mut rng = Antithetic::alternating(SplitMix64::seed_from_u64(1));
+/// let u = rng.next_f64_open_open();
+/// let v = rng.next_f64_open_open();
+/// assert_eq!(u + v, 1.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Antithetic<R> {
+    inner: R,
+    recorded: [u64; CAPACITY],
+    len: usize,
+    pos: usize,
+    replaying: bool,
+    auto_flip: bool,
+}
+
+impl<R: RngCore> Antithetic<R> {
+    /// Starts recording; call [`flip`](Self::flip) between the two passes.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            recorded: [0; CAPACITY],
+            len: 0,
+            pos: 0,
+            replaying: false,
+            auto_flip: false,
+        }
+    }
+
+    /// Flips after every draw, yielding `u, 1 - u, u', 1 - u', ...`.
+    pub fn alternating(inner: R) -> Self {
+        Self {
+            auto_flip: true,
+            ..Self::new(inner)
+        }
+    }
+
+    /// Switches from recording to replaying the complemented words, or from replaying
+    /// back to recording a fresh pass.
+    pub fn flip(&mut self) {
+        if self.replaying {
+            self.len = 0;
+        }
+        self.pos = 0;
+        self.replaying = !self.replaying;
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: RngCore> RngCore for Antithetic<R> {
+    fn next_u64(&mut self) -> u64 {
+        let x = if !self.replaying {
+            let x = self.inner.next_u64();
+            if self.len < CAPACITY {
+                self.recorded[self.len] = x;
+                self.len += 1;
+            }
+            x
+        } else if self.pos < self.len {
+            self.pos += 1;
+            !self.recorded[self.pos - 1]
+        } else {
+            self.inner.next_u64()
+        };
+        if self.auto_flip {
+            self.flip();
+        }
+        x
+    }
+    fn uniform_bits(&self) -> bool {
+        self.inner.uniform_bits()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+
+    #[test]
+    fn replays_complemented_pass() {
+        let mut plain = SplitMix64::seed_from_u64(3);
+        let mut a = Antithetic::new(SplitMix64::seed_from_u64(3));
+        let first: Vec<u64> = (0..3).map(|_| a.next_u64()).collect();
+        a.flip();
+        let second: Vec<u64> = (0..4).map(|_| a.next_u64()).collect();
+        let want: Vec<u64> = (0..4).map(|_| plain.next_u64()).collect();
+        assert_eq!(first, want[..3]);
+        assert_eq!(second[..3], [!want[0], !want[1], !want[2]]);
+        // Past the recorded pass, draws come fresh from the inner generator.
+        assert_eq!(second[3], want[3]);
+        a.flip();
+        assert_eq!(a.next_u64(), plain.next_u64());
+    }
+}
@@ -0,0 +1,94 @@
+//! Block-buffered generator wrapper.
+
+use super::RngCore;
+
+/// Words generated per refill.
+pub const BLOCK: usize = 64;
+
+/// Draws [`BLOCK`] `u64`s from `R` at a time and serves them one by one.
+///
+/// The `next_u64` stream is exactly that of the wrapped generator; `next_u32`, floats and
+/// `fill_bytes` are derived from it (so for 32-bit generators they differ from the
+/// unwrapped native output). Each refill is a single
+/// [`fill_u64`](RngCore::fill_u64) call, which pays off for generators with per-call
+/// overhead, e.g. one lock per block instead of per draw for a
+/// [`&SharedRng`](super::sync::SharedRng).
+#[derive(Clone, Debug)]
+pub struct BufferedRng<R> {
+    inner: R,
+    buf: [u64; BLOCK],
+    idx: usize,
+}
+
+impl<R: RngCore> BufferedRng<R> {
+    /// Wraps `inner`; nothing is drawn until the first call.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: [0; BLOCK],
+            idx: BLOCK,
+        }
+    }
+
+    /// Number of generated words not yet served.
+    #[inline]
+    pub fn buffered(&self) -> usize {
+        BLOCK - self.idx
+    }
+
+    /// The wrapped generator, positioned after the last refill: the
+    /// [`buffered`](Self::buffered) words still pending are lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    #[inline(never)]
+    fn refill(&mut self) {
+        self.inner.fill_u64(&mut self.buf);
+        self.idx = 0;
+    }
+}
+
+impl<R: RngCore> RngCore for BufferedRng<R> {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        if self.idx == BLOCK {
+            self.refill();
+        }
+        let x = self.buf[self.idx];
+        self.idx += 1;
+        x
+    }
+    #[inline]
+    fn uniform_bits(&self) -> bool {
+        self.inner.uniform_bits()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::{Philox4x32, SplitMix64};
+
+    #[test]
+    fn stream_is_transparent() {
+        let mut plain = Philox4x32::seed_from_u64(2);
+        let mut buffered = BufferedRng::new(Philox4x32::seed_from_u64(2));
+        assert_eq!(buffered.buffered(), 0);
+        for _ in 0..3 * BLOCK + 5 {
+            assert_eq!(buffered.next_u64(), plain.next_u64());
+        }
+        let mut a = SplitMix64::seed_from_u64(1);
+        let mut b = BufferedRng::new(a.clone());
+        let (mut x, mut y) = ([0u8; 13], [0u8; 13]);
+        a.fill_bytes(&mut x);
+        b.fill_bytes(&mut y);
+        assert_eq!(x, y);
+        assert_eq!(b.buffered(), BLOCK - 2);
+        let mut inner = b.into_inner();
+        for _ in 0..BLOCK - 2 {
+            a.next_u64();
+        }
+        assert_eq!(inner.next_u64(), a.next_u64());
+    }
+}
@@ -0,0 +1,73 @@
+//! Mappings from 64 random bits to a uniform `f64`, one per interval convention.
+//!
+//! The [`RngCore`](super::RngCore) float methods are built on these; call them directly to
+//! convert words from another source (e.g. a GPU buffer or a recorded stream).
+
+/// `[0,1)` on the 2^-53 grid from the top 53 bits (what `next_f64` returns).
+#[inline]
+pub const fn closed_open(bits: u64) -> f64 {
+    (bits >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+}
+
+/// `(0,1)`: the top 52 bits plus half a step, so both ends are excluded.
+#[inline]
+pub const fn open_open(bits: u64) -> f64 {
+    ((bits >> 12) as f64 + 0.5) * (1.0 / (1u64 << 52) as f64)
+}
+
+/// `(0,1]`: the `[0,1)` grid shifted up one step.
+#[inline]
+pub const fn open_closed(bits: u64) -> f64 {
+    ((bits >> 11) + 1) as f64 * (1.0 / (1u64 << 53) as f64)
+}
+
+/// `[0,1]`: the top 53 bits divided by 2^53 - 1, so both ends occur.
+#[inline]
+pub const fn closed_closed(bits: u64) -> f64 {
+    (bits >> 11) as f64 / ((1u64 << 53) - 1) as f64
+}
+
+/// `[0,1)` as `bits / 2^64` truncated to 53 significant bits: small values keep full
+/// relative precision down to 2^-64 instead of snapping to the 2^-53 grid.
+///
+/// For the whole range down to subnormals, use
+/// [`next_f64_dense`](super::RngCore::next_f64_dense), which draws further words.
+#[inline]
+pub const fn high_resolution(bits: u64) -> f64 {
+    if bits == 0 {
+        return 0.0;
+    }
+    let lz = bits.leading_zeros();
+    let mantissa = (bits << lz) >> 11;
+    // 2^-(53 + lz), exact: the exponent stays within the normal range.
+    let scale = f64::from_bits((1023 - 53 - lz as u64) << 52);
+    mantissa as f64 * scale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interval_ends() {
+        assert_eq!(closed_open(0), 0.0);
+        assert!(closed_open(u64::MAX) < 1.0);
+        assert!(open_open(0) > 0.0 && open_open(u64::MAX) < 1.0);
+        assert_eq!(open_closed(u64::MAX), 1.0);
+        assert_eq!(closed_closed(0), 0.0);
+        assert_eq!(closed_closed(u64::MAX), 1.0);
+    }
+
+    #[test]
+    fn high_resolution_keeps_small_values() {
+        assert_eq!(high_resolution(0), 0.0);
+        assert_eq!(high_resolution(1), 2f64.powi(-64));
+        assert_eq!(high_resolution(1 << 63), 0.5);
+        assert!(high_resolution(u64::MAX) < 1.0);
+        assert_eq!(closed_open(1234), 0.0);
+        assert_eq!(high_resolution(1234), 1234.0 * 2f64.powi(-64));
+        // Agrees with the 53-bit grid whenever the top bit is set.
+        let x = 0xF123_4567_89AB_CDEF;
+        assert_eq!(high_resolution(x), closed_open(x));
+    }
+}
@@ -0,0 +1,161 @@
+//! Quick statistical health checks for any [`RngCore`], after NIST SP 800-22 and Knuth
+//! (TAOCP vol. 2, 3.3.2).
+//!
+//! Each test returns a p-value: uniform on `(0,1)` for a good generator, near 0 for a
+//! broken one. They catch gross defects such as bad seeding, stuck bits or a wrong output
+//! function, not the subtle flaws TestU01 or PractRand look for. With several tests, expect
+//! occasional small p-values by chance; treat `p < 1e-4` as a failure.
+//!
+//! ```
+//! use probability_rs::rng::{SplitMix64, diagnostics};
+//!
+//! let mut rng = SplitMix64::seed_from_u64(1);
+//! for (name, p) in diagnostics::run_all(&mut rng, 10_000) {
+//!     assert!(p > 1e-6, "{name}: p = {p}");
+//! }
+//! ```
+
+use super::RngCore;
+use crate::num::gamma_q;
+
+/// Two-sided normal p-value, `erfc(|z| / sqrt 2)`.
+fn normal_two_sided(z: f64) -> f64 {
+    crate::num::erfc(z.abs() * crate::num::INV_SQRT_2)
+}
+
+/// Upper-tail chi-squared p-value with `df` degrees of freedom.
+fn chi_squared_sf(stat: f64, df: f64) -> f64 {
+    gamma_q(0.5 * df, 0.5 * stat)
+}
+
+/// Frequency (monobit) test: are ones and zeros equally common over `64 * words` bits?
+pub fn monobit<R: RngCore + ?Sized>(rng: &mut R, words: usize) -> f64 {
+    let ones: u64 = (0..words).map(|_| rng.next_u64().count_ones() as u64).sum();
+    let n = 64.0 * words as f64;
+    let s = 2.0 * ones as f64 - n;
+    normal_two_sided(s / n.sqrt())
+}
+
+/// Runs test: is the number of uninterrupted same-bit runs over `64 * words` bits as
+/// expected? Returns 0 when the monobit proportion is already too far off to apply it.
+pub fn runs<R: RngCore + ?Sized>(rng: &mut R, words: usize) -> f64 {
+    // Bits are read most significant first; runs = 1 + number of bit changes.
+    let mut ones = 0u64;
+    let mut runs = 1u64;
+    let mut prev_low: Option<u64> = None;
+    for _ in 0..words {
+        let w = rng.next_u64();
+        ones += w.count_ones() as u64;
+        runs += ((w ^ (w >> 1)) & (u64::MAX >> 1)).count_ones() as u64;
+        if prev_low.is_some_and(|b| b != w >> 63) {
+            runs += 1;
+        }
+        prev_low = Some(w & 1);
+    }
+    let n = 64.0 * words as f64;
+    let pi = ones as f64 / n;
+    if (pi - 0.5).abs() >= 2.0 / n.sqrt() {
+        return 0.0;
+    }
+    let v = runs as f64;
+    let q = pi * (1.0 - pi);
+    normal_two_sided((v - 2.0 * n * q) / (2.0 * (2.0 * n).sqrt() * q))
+}
+
+/// Lag-1 serial correlation of `n` uniforms, which is `N(0, 1/n)` under independence.
+pub fn serial_correlation<R: RngCore + ?Sized>(rng: &mut R, n: usize) -> f64 {
+    let u: Vec<f64> = (0..n).map(|_| rng.next_f64() - 0.5).collect();
+    let lag: f64 = u.windows(2).map(|w| w[0] * w[1]).sum();
+    let var: f64 = u.iter().map(|x| x * x).sum();
+    let r = lag / var;
+    normal_two_sided(r * (n as f64).sqrt())
+}
+
+/// Chi-squared test of the 256 byte values over `8 * words` bytes from `fill_bytes`.
+pub fn byte_chi_squared<R: RngCore + ?Sized>(rng: &mut R, words: usize) -> f64 {
+    let mut bytes = vec![0u8; 8 * words];
+    rng.fill_bytes(&mut bytes);
+    let mut counts = [0u64; 256];
+    for &b in &bytes {
+        counts[b as usize] += 1;
+    }
+    let expected = bytes.len() as f64 / 256.0;
+    let stat: f64 = counts
+        .iter()
+        .map(|&c| (c as f64 - expected).powi(2) / expected)
+        .sum();
+    chi_squared_sf(stat, 255.0)
+}
+
+/// Gap test: lengths of the `gaps` runs of uniforms outside `[0, 1/2)` between hits
+/// should be geometric; lengths from 0 to 9 and "10 or more" are compared by chi-squared.
+pub fn gap<R: RngCore + ?Sized>(rng: &mut R, gaps: usize) -> f64 {
+    const T: usize = 10;
+    let mut counts = [0u64; T + 1];
+    for _ in 0..gaps {
+        let mut len = 0;
+        while rng.next_f64() >= 0.5 {
+            len += 1;
+        }
+        counts[len.min(T)] += 1;
+    }
+    let n = gaps as f64;
+    let stat: f64 = counts
+        .iter()
+        .enumerate()
+        .map(|(r, &c)| {
+            // P(len = r) = 2^-(r+1); P(len >= T) = 2^-T.
+            let p = if r < T {
+                0.5f64.powi(r as i32 + 1)
+            } else {
+                0.5f64.powi(T as i32)
+            };
+            (c as f64 - n * p).powi(2) / (n * p)
+        })
+        .sum();
+    chi_squared_sf(stat, T as f64)
+}
+
+/// Every test above with `n` samples each (words, uniforms or gaps), by name.
+pub fn run_all<R: RngCore + ?Sized>(rng: &mut R, n: usize) -> [(&'static str, f64); 5] {
+    [
+        ("monobit", monobit(rng, n)),
+        ("runs", runs(rng, n)),
+        ("serial_correlation", serial_correlation(rng, n)),
+        ("byte_chi_squared", byte_chi_squared(rng, n)),
+        ("gap", gap(rng, n)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::mock::ScriptedRng;
+    use crate::rng::{Pcg32, SplitMix64};
+
+    #[test]
+    fn good_generators_pass() {
+        for (name, p) in run_all(&mut SplitMix64::seed_from_u64(1), 20_000)
+            .into_iter()
+            .chain(run_all(&mut Pcg32::seed_from_u64(1), 20_000))
+        {
+            assert!(p > 1e-4, "{name}: p = {p}");
+        }
+    }
+
+    #[test]
+    fn defects_are_caught() {
+        // Balanced bits but strictly alternating: passes monobit, fails runs.
+        let mut alt = ScriptedRng::new(&[0x5555_5555_5555_5555]);
+        assert!(monobit(&mut alt, 1000) > 0.5);
+        assert!(runs(&mut alt, 1000) < 1e-10);
+        // A counter: low-bit structure and only a few byte values.
+        let mut counter = ScriptedRng::new(&(0..4096).collect::<Vec<u64>>());
+        assert!(monobit(&mut counter, 4096) < 1e-10);
+        assert!(byte_chi_squared(&mut counter, 4096) < 1e-10);
+        // Uniforms that creep upward are serially correlated.
+        let ramp: Vec<f64> = (0..1000).map(|i| i as f64 / 1000.0).collect();
+        let mut ramp = ScriptedRng::from_f64(&ramp);
+        assert!(serial_correlation(&mut ramp, 1000) < 1e-10);
+    }
+}
@@ -0,0 +1,214 @@
+//! Bounded integers without modulo bias, and float ranges.
+//!
+//! `next_u64() % n` favours small residues whenever `n` does not divide 2^64. These helpers
+//! use Lemire's multiply-shift method instead: the high word of `x * n` is the result, and
+//! the rare `x` whose low word falls in the biased zone are rejected and redrawn.
+
+use super::RngCore;
+
+/// Uniform values in a range, for every [`RngCore`].
+///
+/// ```
+/// use probability_rs::rng::{RngExt, SplitMix64};
+///
+/// let mut rng = SplitMix64::seed_from_u64(1);
+/// let die = rng.gen_range_u64(1, 7);
+/// assert!((1..7).contains(&die));
+/// let items = ["a", "b", "c"];
+/// let _pick = items[rng.gen_index(items.len())];
+/// ```
+pub trait RngExt: RngCore {
+    /// Uniform over all of `u64`; the same as `next_u64`.
+    #[inline]
+    fn gen_u64(&mut self) -> u64 {
+        self.next_u64()
+    }
+
+    /// Uniform over all of `i64`.
+    #[inline]
+    fn gen_i64(&mut self) -> i64 {
+        self.next_u64() as i64
+    }
+
+    /// `n` random bits in the low end of the result (the top `n` of one `next_u64`).
+    /// Panics if `n > 64`.
+    #[inline]
+    fn next_bits(&mut self, n: u32) -> u64 {
+        assert!(n <= 64, "next_bits: {n} bits requested from a u64");
+        self.next_u64().checked_shr(64 - n).unwrap_or(0)
+    }
+
+    /// Uniform in `[lo, hi)`. Panics if `lo >= hi`.
+    #[inline]
+    fn gen_range_u64(&mut self, lo: u64, hi: u64) -> u64 {
+        assert!(lo < hi, "gen_range_u64: empty range {lo}..{hi}");
+        lo + below_u64(self, hi - lo)
+    }
+
+    /// Uniform in `[lo, hi)`. Panics if `lo >= hi`.
+    #[inline]
+    fn gen_range_i64(&mut self, lo: i64, hi: i64) -> i64 {
+        assert!(lo < hi, "gen_range_i64: empty range {lo}..{hi}");
+        let span = hi.wrapping_sub(lo) as u64;
+        lo.wrapping_add(below_u64(self, span) as i64)
+    }
+
+    /// Uniform `f64` in `[lo, hi)`. Draws where `lo + (hi - lo) * u` rounds up to `hi` are
+    /// redrawn. Panics unless `lo < hi` with a finite width.
+    ///
+    /// For other interval ends use the unit draws directly, e.g.
+    /// [`next_f64_open_open`](RngCore::next_f64_open_open) before taking a logarithm.
+    fn gen_range_f64(&mut self, lo: f64, hi: f64) -> f64 {
+        let width = hi - lo;
+        assert!(
+            lo < hi && width.is_finite(),
+            "gen_range_f64: invalid range {lo}..{hi}"
+        );
+        loop {
+            let x = lo + width * self.next_f64();
+            if x < hi {
+                return x;
+            }
+        }
+    }
+
+    /// `true` with probability `p`, from one `next_u64` compared against `p * 2^64`.
+    /// Panics unless `0 <= p <= 1`.
+    #[inline]
+    fn gen_bool(&mut self, p: f64) -> bool {
+        assert!((0.0..=1.0).contains(&p), "gen_bool: p = {p} outside [0, 1]");
+        if p == 1.0 {
+            // 2^64 does not fit; skip the draw rather than saturate to u64::MAX.
+            return true;
+        }
+        const SCALE: f64 = 2.0 * (1u64 << 63) as f64;
+        self.next_u64() < (p * SCALE) as u64
+    }
+
+    /// `true` with probability exactly `numerator / denominator`.
+    /// Panics if `denominator == 0` or `numerator > denominator`.
+    #[inline]
+    fn gen_ratio(&mut self, numerator: u32, denominator: u32) -> bool {
+        assert!(
+            denominator > 0 && numerator <= denominator,
+            "gen_ratio: invalid ratio {numerator}/{denominator}"
+        );
+        below_u32(self, denominator) < numerator
+    }
+
+    /// Uniform index into a slice of length `len`; one `next_u32` when `len` fits in 32 bits.
+    /// Panics if `len == 0`.
+    #[inline]
+    fn gen_index(&mut self, len: usize) -> usize {
+        assert!(len > 0, "gen_index: empty slice");
+        match u32::try_from(len) {
+            Ok(n) => below_u32(self, n) as usize,
+            Err(_) => below_u64(self, len as u64) as usize,
+        }
+    }
+}
+
+impl<R: RngCore + ?Sized> RngExt for R {}
+
+/// Uniform in `[0, n)` for `n > 0`.
+#[inline]
+fn below_u64<R: RngCore + ?Sized>(rng: &mut R, n: u64) -> u64 {
+    let mut m = rng.next_u64() as u128 * n as u128;
+    if (m as u64) < n {
+        // 2^64 mod n: the number of low words that would over-represent some results.
+        let threshold = n.wrapping_neg() % n;
+        while (m as u64) < threshold {
+            m = rng.next_u64() as u128 * n as u128;
+        }
+    }
+    (m >> 64) as u64
+}
+
+/// 32-bit variant of [`below_u64`].
+#[inline]
+fn below_u32<R: RngCore + ?Sized>(rng: &mut R, n: u32) -> u32 {
+    let mut m = rng.next_u32() as u64 * n as u64;
+    if (m as u32) < n {
+        let threshold = n.wrapping_neg() % n;
+        while (m as u32) < threshold {
+            m = rng.next_u32() as u64 * n as u64;
+        }
+    }
+    (m >> 32) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+    use crate::rng::mock::{Exhausted, ScriptedRng};
+
+    #[test]
+    fn rejects_the_biased_zone() {
+        // 2^64 mod 3 == 1, so x == 0 is the single rejected draw.
+        let mut rng = ScriptedRng::with_mode(&[0, u64::MAX], Exhausted::Panic);
+        assert_eq!(rng.gen_range_u64(0, 3), 2);
+        assert_eq!(rng.drawn(), 2);
+        let mut rng = ScriptedRng::with_mode(&[1 << 63], Exhausted::Panic);
+        assert_eq!(rng.gen_range_u64(10, 13), 11);
+    }
+
+    #[test]
+    fn wide_integers_and_bits() {
+        let mut rng = ScriptedRng::new(&[u64::MAX, 5]);
+        assert_eq!(rng.next_u128(), (u64::MAX as u128) << 64 | 5);
+        assert_eq!(rng.gen_i64(), -1);
+        assert_eq!(rng.next_bits(3), 0);
+        assert_eq!(rng.next_bits(3), 7);
+        assert_eq!(rng.next_bits(0), 0);
+        assert_eq!(rng.next_bits(64), u64::MAX);
+    }
+
+    #[test]
+    fn signed_ranges_cover_both_ends() {
+        let mut rng = ScriptedRng::new(&[1, u64::MAX]);
+        assert_eq!(rng.gen_range_i64(i64::MIN, i64::MAX), i64::MIN);
+        assert_eq!(rng.gen_range_i64(i64::MIN, i64::MAX), i64::MAX - 1);
+        assert_eq!(rng.gen_range_i64(-3, -2), -3);
+    }
+
+    #[test]
+    fn float_range_never_returns_hi() {
+        // With a one-ulp width the largest unit draw rounds onto `hi` and is redrawn.
+        let hi = 1.0 + f64::EPSILON;
+        let mut rng = ScriptedRng::with_mode(&[u64::MAX, 0], Exhausted::Panic);
+        assert_eq!(rng.gen_range_f64(1.0, hi), 1.0);
+        assert_eq!(rng.drawn(), 2);
+        let mut rng = SplitMix64::seed_from_u64(2);
+        for _ in 0..1000 {
+            let x = rng.gen_range_f64(-2.5, 4.0);
+            assert!((-2.5..4.0).contains(&x));
+        }
+    }
+
+    #[test]
+    fn bool_thresholds() {
+        let mut rng = ScriptedRng::new(&[(1 << 63) - 1, 1 << 63]);
+        assert!(rng.gen_bool(0.5));
+        assert!(!rng.gen_bool(0.5));
+        let mut none = ScriptedRng::with_mode(&[], Exhausted::Panic);
+        assert!(none.gen_bool(1.0));
+        let mut rng = SplitMix64::seed_from_u64(8);
+        assert!(!rng.gen_bool(0.0));
+        let hits = (0..30_000).filter(|_| rng.gen_ratio(1, 3)).count();
+        assert!((hits as f64 - 10_000.0).abs() < 300.0, "{hits}");
+    }
+
+    #[test]
+    fn index_counts_are_uniform() {
+        let mut rng = SplitMix64::seed_from_u64(12);
+        let mut counts = [0u32; 6];
+        let n = 60_000;
+        for _ in 0..n {
+            counts[rng.gen_index(6)] += 1;
+        }
+        for &c in &counts {
+            assert!((c as f64 - 10_000.0).abs() < 400.0, "{counts:?}");
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Scripted RNG for deterministic unit tests of sampling code.
+//!
+//! ```
+//! use probability_rs::rng::RngCore;
+//! use probability_rs::rng::mock::ScriptedRng;
+//!
+//! let mut rng = ScriptedRng::from_f64(&[0.25, 0.5]);
+//! assert_eq!(rng.next_f64(), 0.25);
+//! assert_eq!(rng.next_f64(), 0.5);
+//! assert_eq!(rng.next_f64(), 0.25); // repeats by default
+//! ```
+
+use super::RngCore;
+
+/// What a [`ScriptedRng`] does once every scripted value has been returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exhausted {
+    /// Start again from the first value.
+    Repeat,
+    /// Panic, so a test notices a sampler drawing more than expected.
+    Panic,
+}
+
+/// Replays a fixed sequence of `u64` outputs.
+///
+/// `0` maps to `next_f64() == 0.0` and `u64::MAX` to the largest value below 1.
+#[derive(Debug, Clone)]
+pub struct ScriptedRng {
+    values: Vec<u64>,
+    pos: usize,
+    drawn: usize,
+    on_exhausted: Exhausted,
+}
+
+impl ScriptedRng {
+    /// Replays `values`, repeating them when exhausted.
+    pub fn new(values: &[u64]) -> Self {
+        Self::with_mode(values, Exhausted::Repeat)
+    }
+
+    /// Replays `values`, then behaves according to `on_exhausted`.
+    ///
+    /// An empty script is allowed with [`Exhausted::Panic`] and asserts that nothing is drawn.
+    pub fn with_mode(values: &[u64], on_exhausted: Exhausted) -> Self {
+        assert!(
+            !values.is_empty() || on_exhausted == Exhausted::Panic,
+            "ScriptedRng needs at least one value to repeat"
+        );
+        Self {
+            values: values.to_vec(),
+            pos: 0,
+            drawn: 0,
+            on_exhausted,
+        }
+    }
+
+    /// Replays uniforms: each `u` in `[0,1)` is returned exactly by
+    /// [`next_f64`](RngCore::next_f64) when it is a multiple of 2^-53 (otherwise rounded down).
+    pub fn from_f64(values: &[f64]) -> Self {
+        Self::from_f64_with_mode(values, Exhausted::Repeat)
+    }
+
+    /// [`from_f64`](Self::from_f64) with an explicit exhaustion policy.
+    pub fn from_f64_with_mode(values: &[f64], on_exhausted: Exhausted) -> Self {
+        let bits: Vec<u64> = values.iter().map(|&u| f64_to_bits(u)).collect();
+        Self::with_mode(&bits, on_exhausted)
+    }
+
+    /// Number of `u64`s drawn so far.
+    #[inline]
+    pub fn drawn(&self) -> usize {
+        self.drawn
+    }
+}
+
+fn f64_to_bits(u: f64) -> u64 {
+    assert!(
+        (0.0..1.0).contains(&u),
+        "scripted uniform must lie in [0,1), got {u}"
+    );
+    ((u * (1u64 << 53) as f64) as u64) << 11
+}
+
+impl RngCore for ScriptedRng {
+    fn next_u64(&mut self) -> u64 {
+        if self.pos == self.values.len() {
+            match self.on_exhausted {
+                Exhausted::Repeat => self.pos = 0,
+                Exhausted::Panic => {
+                    panic!("ScriptedRng exhausted after {} draws", self.values.len())
+                }
+            }
+        }
+        let v = self.values[self.pos];
+        self.pos += 1;
+        self.drawn += 1;
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn f64_values_round_trip() {
+        let mut rng = ScriptedRng::from_f64(&[0.0, 0.75, 1.0 - f64::EPSILON / 2.0]);
+        assert_eq!(rng.next_f64(), 0.0);
+        assert_eq!(rng.next_f64(), 0.75);
+        assert_eq!(rng.next_f64(), 1.0 - f64::EPSILON / 2.0);
+        assert_eq!(rng.drawn(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "exhausted")]
+    fn panics_when_exhausted() {
+        let mut rng = ScriptedRng::with_mode(&[1, 2], Exhausted::Panic);
+        rng.next_u64();
+        rng.next_u64();
+        rng.next_u64();
+    }
+}
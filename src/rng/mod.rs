@@ -2,22 +2,421 @@
 //! This module declares the core RNG trait and exposes concrete RNGs as submodules.
 
 /// Minimal trait for random number generation.
+///
+/// Uniform floats come in several interval flavours:
+/// - [`next_f64`](RngCore::next_f64) / [`next_f64_closed_open`](RngCore::next_f64_closed_open): `[0,1)`, 53 bits;
+/// - [`next_f64_open_open`](RngCore::next_f64_open_open): `(0,1)`, safe for `ln`, logit and probit;
+/// - [`next_f64_open_closed`](RngCore::next_f64_open_closed): `(0,1]`, 53 bits, safe for `ln`;
+/// - [`next_f64_dense`](RngCore::next_f64_dense): `(0,1)` with full precision near 0, down to subnormals.
+///
+/// The single-word mappings, plus `[0,1]` and a one-word high-resolution variant, are
+/// available as plain functions in [`convert`].
 pub trait RngCore {
     fn next_u64(&mut self) -> u64;
+    /// 32 random bits; defaults to the high half of `next_u64`. Generators with a native
+    /// 32-bit output override this so callers needing only 32 bits pay for one step.
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+    /// 128 random bits from two `next_u64` calls, the first in the high half.
+    #[inline]
+    fn next_u128(&mut self) -> u128 {
+        let hi = self.next_u64() as u128;
+        (hi << 64) | self.next_u64() as u128
+    }
+    /// Fills `dest` with successive `next_u64` outputs. Wrappers and block generators
+    /// override this to produce a whole block per call.
+    fn fill_u64(&mut self, dest: &mut [u64]) {
+        for w in dest {
+            *w = self.next_u64();
+        }
+    }
+    /// Fills `dest` with random bytes: successive `next_u64` outputs in little-endian
+    /// order, the last one truncated.
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut chunks = dest.chunks_exact_mut(8);
+        for chunk in &mut chunks {
+            chunk.copy_from_slice(&self.next_u64().to_le_bytes());
+        }
+        let rest = chunks.into_remainder();
+        if !rest.is_empty() {
+            let bytes = self.next_u64().to_le_bytes();
+            rest.copy_from_slice(&bytes[..rest.len()]);
+        }
+    }
+    /// Uniform in `[0,1)` with 53 bits: one `next_u64`, i.e. exactly two steps of a 32-bit
+    /// generator and no more.
     #[inline]
     fn next_f64(&mut self) -> f64 {
-        // Use 53 bits of precision for f64 in [0,1).
-        const DEN: f64 = (1u64 << 53) as f64;
-        ((self.next_u64() >> 11) as f64) / DEN
+        convert::closed_open(self.next_u64())
+    }
+    /// Uniform `f32` in `[0,1)` with 24 bits of precision, from one `next_u32`.
+    #[inline]
+    fn next_f32(&mut self) -> f32 {
+        const DEN: f32 = (1u32 << 24) as f32;
+        ((self.next_u32() >> 8) as f32) / DEN
+    }
+    /// Uniform in `[0,1)` at the precision of `F`: [`next_f32`](RngCore::next_f32) or
+    /// [`next_f64`](RngCore::next_f64) for the built-in float types.
+    #[inline]
+    fn next_float<F: crate::num::Float>(&mut self) -> F
+    where
+        Self: Sized,
+    {
+        if F::PRECISION <= 32 {
+            F::unit_from_bits((self.next_u32() as u64) << 32)
+        } else {
+            F::unit_from_bits(self.next_u64())
+        }
+    }
+    /// Uniform in `[0,1)`; identical to [`next_f64`](RngCore::next_f64), named for explicitness.
+    #[inline]
+    fn next_f64_closed_open(&mut self) -> f64 {
+        self.next_f64()
+    }
+    /// Uniform in `(0,1)`: 52 random bits offset by half a step, so neither 0 nor 1 can occur.
+    #[inline]
+    fn next_f64_open_open(&mut self) -> f64 {
+        convert::open_open(self.next_u64())
+    }
+    /// Uniform in `(0,1]`: the `[0,1)` grid shifted up one step, so `1.0` occurs but `0.0` cannot.
+    #[inline]
+    fn next_f64_open_closed(&mut self) -> f64 {
+        convert::open_closed(self.next_u64())
+    }
+    /// Uniform in `(0,1)` where every representable magnitude is reachable.
+    ///
+    /// The binary exponent is drawn geometrically (leading zeros of further draws) and the
+    /// 52 mantissa bits separately, so values below 2^-53 keep full relative precision,
+    /// reaching into the subnormal range. Consumes at least two `u64`s.
+    fn next_f64_dense(&mut self) -> f64 {
+        const DEN: f64 = (1u64 << 52) as f64;
+        let mantissa = 1.0 + ((self.next_u64() >> 12) as f64) / DEN;
+        let mut exp: i32 = -1;
+        loop {
+            let bits = self.next_u64();
+            if bits != 0 {
+                exp -= bits.leading_zeros() as i32;
+                break;
+            }
+            exp -= 64;
+            if exp < -1074 {
+                return f64::MIN_POSITIVE * f64::EPSILON;
+            }
+        }
+        mantissa * 2f64.powi(exp)
+    }
+    /// Whether every bit of `next_u64` is independently uniform. Samplers that split one
+    /// word into several fields (the ziggurat takes its strip from the low byte) fall back
+    /// to `next_f64`-based methods when this is `false`, as it is for quasi-random sources
+    /// such as [`Halton`](halton::Halton) whose words are a scaled coordinate.
+    #[inline]
+    fn uniform_bits(&self) -> bool {
+        true
     }
 }
 
+/// Construction from a fixed-size byte seed, implemented by every built-in generator.
+///
+/// `seed_from_u64` matches each generator's inherent `seed_from_u64`. Seeds are read
+/// as little-endian words; states a generator cannot use (e.g. all-zero xoshiro) fall
+/// back to `seed_from_u64(0)`. Any byte source works, e.g. a hash of an experiment name:
+///
+/// ```
+/// use probability_rs::rng::{RngCore, SeedableRng, xoshiro256::Xoshiro256StarStar};
+///
+/// let mut seed = <Xoshiro256StarStar as SeedableRng>::Seed::default();
+/// for (b, &x) in seed.iter_mut().zip(b"experiment-42".iter().cycle()) {
+///     *b = x;
+/// }
+/// let mut rng = <Xoshiro256StarStar as SeedableRng>::from_seed(seed);
+/// let _ = rng.next_u64();
+/// ```
+pub trait SeedableRng: Sized {
+    type Seed: AsMut<[u8]> + Default;
+    fn from_seed(seed: Self::Seed) -> Self;
+    /// Defaults to filling the seed with SplitMix64 output.
+    fn seed_from_u64(state: u64) -> Self {
+        let mut sm = SplitMix64::seed_from_u64(state);
+        let mut seed = Self::Seed::default();
+        for chunk in seed.as_mut().chunks_mut(8) {
+            let bytes = sm.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+        Self::from_seed(seed)
+    }
+}
+
+/// Non-overlapping substreams for parallel work: worker `i` draws from `rng.substream(i)`.
+///
+/// `substream(0)` is a copy of `self`; substream `i` starts where the generator's native
+/// split mechanism puts it:
+/// - xoshiro256 / Xoroshiro128++: `i` [`jump`](xoshiro256::Xoshiro256StarStar::jump)s
+///   (2^128 / 2^64 draws apart), costing O(i);
+/// - PCG32: re-seeded on stream selector + `i`;
+/// - Philox4x32: high 64 counter bits advanced by `i` (2^65 `u64`s apart), O(1);
+/// - SplitMix64 / WyRand: `i · ⌊2^64/φ⌋` draws ahead on their single Weyl cycle (φ the
+///   golden ratio), so every `i` gets its own start and the first `n` substreams are at
+///   least `2^64 / (3n)` draws apart.
+///
+/// Mt19937_64 has no cheap jump and does not implement it.
+///
+/// ```
+/// use probability_rs::rng::{RngCore, Streams, xoshiro256::Xoshiro256StarStar};
+///
+/// fn spawn_all<R: Streams + RngCore>(base: &R, workers: u64) -> Vec<R> {
+///     (0..workers).map(|i| base.substream(i)).collect()
+/// }
+/// let rngs = spawn_all(&Xoshiro256StarStar::seed_from_u64(7), 4);
+/// assert_eq!(rngs.len(), 4);
+/// ```
+pub trait Streams: Sized {
+    fn substream(&self, i: u64) -> Self;
+}
+
+/// Little-endian `u64` words of `bytes`.
+pub(crate) fn le_words<const N: usize>(bytes: &[u8]) -> [u64; N] {
+    let mut out = [0u64; N];
+    for (w, chunk) in out.iter_mut().zip(bytes.chunks_exact(8)) {
+        *w = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    out
+}
+
+pub mod alias;
+pub mod antithetic;
+pub mod buffered;
+pub mod convert;
+pub mod diagnostics;
+pub mod ext;
+pub mod halton;
+pub mod lhs;
+pub mod mock;
+pub mod mt19937;
 pub mod pcg32;
+pub mod philox;
+#[cfg(feature = "rand-compat")]
+pub mod rand_compat;
+pub mod reference;
+pub mod seq;
+#[cfg(feature = "serde")]
+mod serde_impl;
 pub mod splitmix64;
+pub mod stratified;
+pub mod sync;
+pub mod thread;
+pub mod wyrand;
 pub mod xoroshiro128;
 pub mod xoshiro256;
 
 // Re-export commonly used RNGs for ergonomic access: rng::SplitMix64
+pub use ext::RngExt;
+pub use mt19937::Mt19937_64;
 pub use pcg32::Pcg32;
+pub use philox::Philox4x32;
+#[cfg(feature = "rand-compat")]
+pub use rand_compat::RandCompat;
 pub use splitmix64::SplitMix64;
+pub use thread::{ThreadRng, sample, thread_rng};
+pub use wyrand::WyRand;
 pub use xoroshiro128::Xoroshiro128PlusPlus;
+
+impl<R: RngCore + ?Sized> RngCore for &mut R {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        (**self).next_u64()
+    }
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        (**self).next_u32()
+    }
+    #[inline]
+    fn next_u128(&mut self) -> u128 {
+        (**self).next_u128()
+    }
+    #[inline]
+    fn fill_u64(&mut self, dest: &mut [u64]) {
+        (**self).fill_u64(dest)
+    }
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        (**self).fill_bytes(dest)
+    }
+    #[inline]
+    fn next_f64(&mut self) -> f64 {
+        (**self).next_f64()
+    }
+    #[inline]
+    fn next_f32(&mut self) -> f32 {
+        (**self).next_f32()
+    }
+    #[inline]
+    fn next_f64_closed_open(&mut self) -> f64 {
+        (**self).next_f64_closed_open()
+    }
+    #[inline]
+    fn next_f64_open_open(&mut self) -> f64 {
+        (**self).next_f64_open_open()
+    }
+    #[inline]
+    fn next_f64_open_closed(&mut self) -> f64 {
+        (**self).next_f64_open_closed()
+    }
+    #[inline]
+    fn next_f64_dense(&mut self) -> f64 {
+        (**self).next_f64_dense()
+    }
+    #[inline]
+    fn uniform_bits(&self) -> bool {
+        (**self).uniform_bits()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixed(u64);
+    impl RngCore for Fixed {
+        fn next_u64(&mut self) -> u64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn open_open_excludes_endpoints() {
+        let lo = Fixed(0).next_f64_open_open();
+        let hi = Fixed(u64::MAX).next_f64_open_open();
+        assert!(lo > 0.0 && hi < 1.0);
+        assert_eq!(Fixed(0).next_f64_closed_open(), 0.0);
+        assert_eq!(Fixed(u64::MAX).next_f64_open_closed(), 1.0);
+        assert_eq!(Fixed(0).next_f64_open_closed(), 2f64.powi(-53));
+    }
+
+    #[test]
+    fn dense_reaches_below_2_pow_53() {
+        // Mantissa draw 0, exponent draw with 60 leading zeros -> 2^-61.
+        let mut r = Fixed(1 << 3);
+        let x = r.next_f64_dense();
+        assert!(x > 0.0 && x < 2f64.powi(-53));
+        let mut r = SplitMix64::seed_from_u64(3);
+        for _ in 0..1000 {
+            let x = r.next_f64_dense();
+            assert!(x > 0.0 && x < 1.0);
+        }
+    }
+
+    #[test]
+    fn generic_float_matches_typed() {
+        let mut a = crate::rng::SplitMix64::seed_from_u64(3);
+        let mut b = crate::rng::SplitMix64::seed_from_u64(3);
+        assert_eq!(a.next_float::<f32>(), b.next_f32());
+        assert_eq!(a.next_float::<f64>(), b.next_f64());
+        assert!(Fixed(u64::MAX).next_f32() < 1.0);
+    }
+
+    #[test]
+    fn state_round_trip_continues_stream() {
+        let mut x = xoshiro256::Xoshiro256StarStar::seed_from_u64(5);
+        x.next_u64();
+        let mut y = xoshiro256::Xoshiro256StarStar::from_state(x.state()).unwrap();
+        assert_eq!(x.next_u64(), y.next_u64());
+        let mut s = SplitMix64::seed_from_u64(9);
+        let mut t = SplitMix64::from_state(s.state());
+        assert_eq!(s.next_u64(), t.next_u64());
+        assert!(Xoroshiro128PlusPlus::from_state([0, 0]).is_none());
+        assert!(Pcg32::from_state([3, 4]).is_none());
+    }
+
+    #[test]
+    fn seedable_trait_matches_inherent_seeding() {
+        fn via_trait<R: SeedableRng + RngCore>(seed: u64) -> u64 {
+            <R as SeedableRng>::seed_from_u64(seed).next_u64()
+        }
+        assert_eq!(via_trait::<Pcg32>(8), Pcg32::seed_from_u64(8).next_u64());
+        assert_eq!(
+            via_trait::<Mt19937_64>(8),
+            Mt19937_64::seed_from_u64(8).next_u64()
+        );
+        let zero = <xoshiro256::Xoshiro256Plus as SeedableRng>::from_seed([0; 32]);
+        assert_eq!(zero, xoshiro256::Xoshiro256Plus::seed_from_u64(0));
+
+        // The provided seed_from_u64 fills the seed with SplitMix64 words.
+        struct Bytes([u8; 12]);
+        impl SeedableRng for Bytes {
+            type Seed = [u8; 12];
+            fn from_seed(seed: [u8; 12]) -> Self {
+                Bytes(seed)
+            }
+        }
+        let mut sm = SplitMix64::seed_from_u64(1);
+        let w0 = sm.next_u64().to_le_bytes();
+        let w1 = sm.next_u64().to_le_bytes();
+        let b = Bytes::seed_from_u64(1).0;
+        assert_eq!(b[..8], w0);
+        assert_eq!(b[8..], w1[..4]);
+    }
+
+    #[test]
+    fn substreams_start_apart() {
+        let base = xoshiro256::Xoshiro256PlusPlus::seed_from_u64(3);
+        let mut jumped = base.clone();
+        jumped.jump();
+        jumped.jump();
+        assert_eq!(base.substream(0), base);
+        assert_eq!(base.substream(2), jumped);
+
+        fn heads<R: Streams + RngCore>(base: &R) -> Vec<u64> {
+            (0..4).map(|i| base.substream(i).next_u64()).collect()
+        }
+        for h in [
+            heads(&Pcg32::seed_from_u64(3)),
+            heads(&Philox4x32::seed_from_u64(3)),
+            heads(&SplitMix64::seed_from_u64(3)),
+            heads(&WyRand::seed_from_u64(3)),
+            heads(&Xoroshiro128PlusPlus::seed_from_u64(3)),
+        ] {
+            for i in 0..h.len() {
+                assert!(!h[i + 1..].contains(&h[i]), "{h:?}");
+            }
+        }
+        let mut s = SplitMix64::seed_from_u64(3);
+        let t = s.substream(0);
+        assert_eq!(s.next_u64(), t.clone().next_u64());
+    }
+
+    #[test]
+    fn fill_bytes_is_little_endian_words() {
+        let mut a = SplitMix64::seed_from_u64(4);
+        let mut b = a.clone();
+        let mut buf = [0u8; 11];
+        a.fill_bytes(&mut buf);
+        let w0 = b.next_u64().to_le_bytes();
+        let w1 = b.next_u64().to_le_bytes();
+        assert_eq!(buf[..8], w0);
+        assert_eq!(buf[8..], w1[..3]);
+        // The 32-bit generators spend a single step on a short tail.
+        let mut p = Pcg32::seed_from_u64(4);
+        let mut q = p.clone();
+        let mut tail = [0u8; 3];
+        p.fill_bytes(&mut tail);
+        assert_eq!(tail, q.next_u32().to_le_bytes()[..3]);
+        assert_eq!(p, q);
+    }
+
+    #[test]
+    fn next_u32_is_native_for_32_bit_generators() {
+        let mut a = SplitMix64::seed_from_u64(6);
+        let mut b = a.clone();
+        assert_eq!(RngCore::next_u32(&mut a), (b.next_u64() >> 32) as u32);
+        // One PCG step per u32 and per f32; the f32 is the top 24 bits of that word.
+        let mut p = Pcg32::seed_from_u64(6);
+        let mut q = p.clone();
+        let x = p.next_f32();
+        assert_eq!(x, (q.next_u32() >> 8) as f32 / (1u32 << 24) as f32);
+        assert_eq!(p, q);
+        assert_eq!(p.next_float::<f32>(), q.next_f32());
+        assert_eq!(p, q);
+    }
+}
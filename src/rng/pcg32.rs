@@ -1,9 +1,9 @@
 //! PCG32 (PCG XSH RR 64/32) by Melissa O'Neill.
 //! Small-state, high-quality 32-bit output PRNG. Not cryptographic.
 
-use super::RngCore;
+use super::{RngCore, SeedableRng, Streams};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Pcg32 {
     state: u64,
     inc: u64, // must be odd
@@ -16,12 +16,31 @@ impl Pcg32 {
             state: 0,
             inc: (stream << 1) | 1,
         };
-        pcg.next_u64(); // advance once with inc set
+        // pcg32_srandom_r: one step with inc set, add the seed, one more step.
+        pcg.step();
         pcg.state = pcg.state.wrapping_add(seed);
-        pcg.next_u64();
+        pcg.step();
         pcg
     }
 
+    /// Internal `[state, increment]`; [`from_state`](Self::from_state) resumes the
+    /// stream exactly.
+    #[inline]
+    pub fn state(&self) -> [u64; 2] {
+        [self.state, self.inc]
+    }
+
+    /// Restores a generator from [`state`](Self::state); `None` if the increment is even.
+    pub fn from_state(state: [u64; 2]) -> Option<Self> {
+        if state[1] & 1 == 0 {
+            return None;
+        }
+        Some(Self {
+            state: state[0],
+            inc: state[1],
+        })
+    }
+
     /// Seed from a single seed using SplitMix64 to generate both state and stream.
     pub fn seed_from_u64(seed: u64) -> Self {
         let mut sm = super::SplitMix64::seed_from_u64(seed);
@@ -55,6 +74,29 @@ impl Pcg32 {
     }
 }
 
+impl SeedableRng for Pcg32 {
+    type Seed = [u8; 16];
+    /// Seed and stream, as for [`Pcg32::from_seed_and_stream`].
+    fn from_seed(seed: [u8; 16]) -> Self {
+        let [s, stream] = super::le_words(&seed);
+        Pcg32::from_seed_and_stream(s, stream)
+    }
+    fn seed_from_u64(state: u64) -> Self {
+        Pcg32::seed_from_u64(state)
+    }
+}
+
+impl Streams for Pcg32 {
+    /// Re-seeded from the current state on stream selector + `i`. Merely changing the
+    /// increment would repeat the next output, which depends on the state alone.
+    fn substream(&self, i: u64) -> Self {
+        if i == 0 {
+            return self.clone();
+        }
+        Pcg32::from_seed_and_stream(self.state, (self.inc >> 1).wrapping_add(i))
+    }
+}
+
 impl RngCore for Pcg32 {
     #[inline]
     fn next_u64(&mut self) -> u64 {
@@ -63,6 +105,22 @@ impl RngCore for Pcg32 {
         let lo = self.next_u32() as u64;
         (hi << 32) | lo
     }
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        Pcg32::next_u32(self)
+    }
+    /// Little-endian `next_u32` words, so a tail of up to 4 bytes costs one step.
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut chunks = dest.chunks_exact_mut(4);
+        for chunk in &mut chunks {
+            chunk.copy_from_slice(&self.next_u32().to_le_bytes());
+        }
+        let rest = chunks.into_remainder();
+        if !rest.is_empty() {
+            let bytes = self.next_u32().to_le_bytes();
+            rest.copy_from_slice(&bytes[..rest.len()]);
+        }
+    }
 }
 
 #[cfg(test)]
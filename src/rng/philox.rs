@@ -0,0 +1,216 @@
+//! Philox4x32-10 (Salmon et al., "Parallel random numbers: as easy as 1, 2, 3", SC'11).
+//! Counter-based: each 128-bit block is a pure function of a 64-bit key and a 128-bit
+//! counter, so any position of any stream can be reached directly. Not cryptographic.
+
+use super::{RngCore, SeedableRng, Streams};
+
+const M0: u32 = 0xD251_1F53;
+const M1: u32 = 0xCD9E_8D57;
+const W0: u32 = 0x9E37_79B9;
+const W1: u32 = 0xBB67_AE85;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Philox4x32 {
+    key: [u32; 2],
+    counter: [u32; 4], // block holding the next output
+    buf: [u32; 4],     // block(key, counter)
+    idx: usize,        // next word of `buf`, 0..4
+}
+
+#[inline]
+fn mulhilo(a: u32, b: u32) -> (u32, u32) {
+    let p = a as u64 * b as u64;
+    ((p >> 32) as u32, p as u32)
+}
+
+impl Philox4x32 {
+    /// Stream `key`, starting at counter zero.
+    pub fn new(key: [u32; 2]) -> Self {
+        Self::from_key_and_counter(key, [0; 4])
+    }
+
+    /// Stream `key`, starting at block `counter`.
+    pub fn from_key_and_counter(key: [u32; 2], counter: [u32; 4]) -> Self {
+        Self {
+            key,
+            counter,
+            buf: Self::block(key, counter),
+            idx: 0,
+        }
+    }
+
+    /// Uses the seed directly as the key (low word first), so distinct seeds are
+    /// distinct, non-overlapping streams.
+    pub fn seed_from_u64(seed: u64) -> Self {
+        Self::new([seed as u32, (seed >> 32) as u32])
+    }
+
+    /// The four output words for `(key, counter)`.
+    pub fn block(key: [u32; 2], counter: [u32; 4]) -> [u32; 4] {
+        let (mut c, mut k) = (counter, key);
+        for round in 0..10 {
+            if round > 0 {
+                k = [k[0].wrapping_add(W0), k[1].wrapping_add(W1)];
+            }
+            let (hi0, lo0) = mulhilo(M0, c[0]);
+            let (hi1, lo1) = mulhilo(M1, c[2]);
+            c = [hi1 ^ c[1] ^ k[0], lo1, hi0 ^ c[3] ^ k[1], lo0];
+        }
+        c
+    }
+
+    #[inline]
+    pub fn key(&self) -> [u32; 2] {
+        self.key
+    }
+
+    /// Counter of the block that holds the next output.
+    #[inline]
+    pub fn counter(&self) -> [u32; 4] {
+        self.counter
+    }
+
+    /// Jumps to the start of block `counter`. Each block yields four `u32`s (two `u64`s).
+    pub fn set_counter(&mut self, counter: [u32; 4]) {
+        self.counter = counter;
+        self.buf = Self::block(self.key, counter);
+        self.idx = 0;
+    }
+
+    /// `[key, counter low, counter high, word index]`; [`from_state`](Self::from_state)
+    /// resumes the stream exactly.
+    pub fn state(&self) -> [u64; 4] {
+        let c = self.counter;
+        [
+            self.key[0] as u64 | (self.key[1] as u64) << 32,
+            c[0] as u64 | (c[1] as u64) << 32,
+            c[2] as u64 | (c[3] as u64) << 32,
+            self.idx as u64,
+        ]
+    }
+
+    /// Restores a generator from [`state`](Self::state); `None` if the word index is not
+    /// below 4.
+    pub fn from_state(state: [u64; 4]) -> Option<Self> {
+        if state[3] >= 4 {
+            return None;
+        }
+        let split = |x: u64| [x as u32, (x >> 32) as u32];
+        let [c0, c1] = split(state[1]);
+        let [c2, c3] = split(state[2]);
+        let mut rng = Self::from_key_and_counter(split(state[0]), [c0, c1, c2, c3]);
+        rng.idx = state[3] as usize;
+        Some(rng)
+    }
+
+    #[inline]
+    pub fn next_u32(&mut self) -> u32 {
+        let x = self.buf[self.idx];
+        self.idx += 1;
+        if self.idx == 4 {
+            // 128-bit little-endian increment
+            for w in &mut self.counter {
+                *w = w.wrapping_add(1);
+                if *w != 0 {
+                    break;
+                }
+            }
+            self.buf = Self::block(self.key, self.counter);
+            self.idx = 0;
+        }
+        x
+    }
+}
+
+impl SeedableRng for Philox4x32 {
+    type Seed = [u8; 8];
+    /// The 64-bit key; the counter starts at zero.
+    fn from_seed(seed: [u8; 8]) -> Self {
+        Philox4x32::seed_from_u64(u64::from_le_bytes(seed))
+    }
+    fn seed_from_u64(state: u64) -> Self {
+        Philox4x32::seed_from_u64(state)
+    }
+}
+
+impl Streams for Philox4x32 {
+    /// Same key, high 64 counter bits advanced by `i`.
+    fn substream(&self, i: u64) -> Self {
+        let mut s = self.state();
+        s[2] = s[2].wrapping_add(i);
+        Self::from_state(s).unwrap()
+    }
+}
+
+impl RngCore for Philox4x32 {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let hi = self.next_u32() as u64;
+        let lo = self.next_u32() as u64;
+        (hi << 32) | lo
+    }
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        Philox4x32::next_u32(self)
+    }
+    /// Little-endian `next_u32` words, so a tail of up to 4 bytes costs one step.
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut chunks = dest.chunks_exact_mut(4);
+        for chunk in &mut chunks {
+            chunk.copy_from_slice(&self.next_u32().to_le_bytes());
+        }
+        let rest = chunks.into_remainder();
+        if !rest.is_empty() {
+            let bytes = self.next_u32().to_le_bytes();
+            rest.copy_from_slice(&bytes[..rest.len()]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_answer_vectors() {
+        // Random123 kat_vectors, philox4x32 10 rounds
+        assert_eq!(
+            Philox4x32::block([0, 0], [0; 4]),
+            [0x6627e8d5, 0xe169c58d, 0xbc57ac4c, 0x9b00dbd8]
+        );
+        assert_eq!(
+            Philox4x32::block([u32::MAX; 2], [u32::MAX; 4]),
+            [0x408f276d, 0x41c83b0e, 0xa20bc7c6, 0x6d5451fd]
+        );
+        assert_eq!(
+            Philox4x32::block(
+                [0xa4093822, 0x299f31d0],
+                [0x243f6a88, 0x85a308d3, 0x13198a2e, 0x03707344]
+            ),
+            [0xd16cfe09, 0x94fdcceb, 0x5001e420, 0x24126ea1]
+        );
+    }
+
+    #[test]
+    fn set_counter_gives_random_access() {
+        let mut seq = Philox4x32::seed_from_u64(11);
+        let draws: Vec<u64> = (0..10).map(|_| seq.next_u64()).collect();
+        let mut jump = Philox4x32::seed_from_u64(11);
+        jump.set_counter([3, 0, 0, 0]);
+        assert_eq!(jump.next_u64(), draws[6]);
+        // Carry into the next counter word.
+        let mut r = Philox4x32::from_key_and_counter([1, 2], [u32::MAX, 0, 0, 0]);
+        r.next_u64();
+        r.next_u64();
+        assert_eq!(r.counter(), [0, 1, 0, 0]);
+    }
+
+    #[test]
+    fn state_round_trip() {
+        let mut a = Philox4x32::seed_from_u64(5);
+        a.next_u32();
+        let mut b = Philox4x32::from_state(a.state()).unwrap();
+        assert_eq!(a.next_u64(), b.next_u64());
+        assert!(Philox4x32::from_state([0, 0, 0, 4]).is_none());
+    }
+}
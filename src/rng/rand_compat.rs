@@ -0,0 +1,127 @@
+//! Interoperability with `rand_core` (feature `rand-compat`).
+//!
+//! The built-in generators implement [`rand_core::RngCore`] and [`rand_core::SeedableRng`],
+//! and [`RandCompat`] lets any `rand_core` generator drive this crate's samplers:
+//!
+//! ```
+//! use probability_rs::rng::RandCompat;
+//! use probability_rs::{Distribution, dist::normal::Normal};
+//! # use probability_rs::rng::SplitMix64 as SomeRandRng;
+//!
+//! let rand_rng: SomeRandRng = rand_core::SeedableRng::seed_from_u64(7);
+//! let mut rng = RandCompat(rand_rng);
+//! let _x = Normal::standard().sample(&mut rng);
+//! ```
+//!
+//! Both `RngCore` traits name their method `next_u64`; with both in scope, call it as
+//! `probability_rs::rng::RngCore::next_u64(&mut rng)`.
+
+use super::{
+    Mt19937_64, Pcg32, Philox4x32, SplitMix64, WyRand, Xoroshiro128PlusPlus,
+    xoshiro256::{Xoshiro256Plus, Xoshiro256PlusPlus, Xoshiro256StarStar},
+};
+
+/// Adapts a [`rand_core::RngCore`] to this crate's [`RngCore`](super::RngCore).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RandCompat<R>(pub R);
+
+impl<R: rand_core::RngCore> super::RngCore for RandCompat<R> {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+}
+
+macro_rules! impl_rand_core {
+    ($($ty:ty),*) => {$(
+        impl rand_core::RngCore for $ty {
+            #[inline]
+            fn next_u32(&mut self) -> u32 {
+                super::RngCore::next_u32(self)
+            }
+            #[inline]
+            fn next_u64(&mut self) -> u64 {
+                super::RngCore::next_u64(self)
+            }
+            fn fill_bytes(&mut self, dst: &mut [u8]) {
+                super::RngCore::fill_bytes(self, dst)
+            }
+        }
+    )*};
+}
+
+impl_rand_core!(
+    Mt19937_64,
+    Pcg32,
+    Philox4x32,
+    SplitMix64,
+    Xoroshiro128PlusPlus,
+    Xoshiro256StarStar,
+    Xoshiro256PlusPlus,
+    Xoshiro256Plus,
+    WyRand
+);
+
+// Same seeds and streams as this crate's own `SeedableRng`.
+macro_rules! impl_seedable {
+    ($($ty:ty),*) => {$(
+        impl rand_core::SeedableRng for $ty {
+            type Seed = <$ty as super::SeedableRng>::Seed;
+            fn from_seed(seed: Self::Seed) -> Self {
+                <$ty as super::SeedableRng>::from_seed(seed)
+            }
+            fn seed_from_u64(state: u64) -> Self {
+                <$ty as super::SeedableRng>::seed_from_u64(state)
+            }
+        }
+    )*};
+}
+
+impl_seedable!(
+    Mt19937_64,
+    Pcg32,
+    Philox4x32,
+    SplitMix64,
+    WyRand,
+    Xoroshiro128PlusPlus,
+    Xoshiro256StarStar,
+    Xoshiro256PlusPlus,
+    Xoshiro256Plus
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::SeedableRng;
+
+    #[test]
+    fn seeding_matches_inherent_constructors() {
+        let mut a = <Xoshiro256StarStar as SeedableRng>::seed_from_u64(4);
+        let mut b = Xoshiro256StarStar::seed_from_u64(4);
+        assert_eq!(
+            rand_core::RngCore::next_u64(&mut a),
+            super::super::RngCore::next_u64(&mut b)
+        );
+        let z = Xoroshiro128PlusPlus::from_seed([0; 16]);
+        assert_eq!(z, Xoroshiro128PlusPlus::seed_from_u64(0));
+    }
+
+    #[test]
+    fn bridge_drives_samplers() {
+        use crate::{Distribution, dist::poisson::Poisson};
+        let p = Poisson::new(4.0).unwrap();
+        let mut ours = SplitMix64::seed_from_u64(10);
+        let mut bridged = RandCompat(SplitMix64::seed_from_u64(10));
+        assert_eq!(p.sample(&mut ours), p.sample(&mut bridged));
+        let mut bytes = [0u8; 5];
+        rand_core::RngCore::fill_bytes(&mut bridged.0, &mut bytes);
+    }
+}
@@ -0,0 +1,209 @@
+//! Known-answer vectors: the first outputs of each generator for a fixed seeding, as
+//! printed by the algorithm authors' reference C code (Random123's `kat_vectors` for
+//! Philox, `mt19937-64.out.txt` for the Mersenne Twister).
+//!
+//! Porting a simulation to another language? Seed the other implementation the way
+//! [`source`](ReferenceVector::source) says and compare against
+//! [`outputs`](ReferenceVector::outputs).
+//!
+//! ```
+//! use probability_rs::rng::reference::reference_vector;
+//!
+//! let v = reference_vector("Pcg32").unwrap();
+//! assert_eq!(v.outputs[0], 0xa15c02b7);
+//! assert!(v.verify(&mut *v.generator()));
+//! ```
+
+use super::xoshiro256::{Xoshiro256Plus, Xoshiro256PlusPlus, Xoshiro256StarStar};
+use super::{Mt19937_64, Pcg32, Philox4x32, RngCore, SplitMix64, WyRand, Xoroshiro128PlusPlus};
+
+/// Reference outputs of one generator.
+#[derive(Debug)]
+pub struct ReferenceVector {
+    /// Type name of the generator in this crate.
+    pub name: &'static str,
+    /// How the reference implementation is seeded.
+    pub source: &'static str,
+    /// Native output width: 32 (compare `next_u32`) or 64 (compare `next_u64`).
+    pub bits: u32,
+    pub outputs: &'static [u64],
+    build: fn() -> Box<dyn RngCore>,
+}
+
+impl ReferenceVector {
+    /// This crate's generator, seeded as described by [`source`](Self::source).
+    pub fn generator(&self) -> Box<dyn RngCore> {
+        (self.build)()
+    }
+
+    /// Whether the next native outputs of `rng` equal [`outputs`](Self::outputs).
+    pub fn verify(&self, rng: &mut dyn RngCore) -> bool {
+        self.outputs.iter().all(|&want| {
+            let got = match self.bits {
+                32 => rng.next_u32() as u64,
+                _ => rng.next_u64(),
+            };
+            got == want
+        })
+    }
+}
+
+/// All built-in vectors, one per generator.
+pub fn reference_vectors() -> &'static [ReferenceVector] {
+    &VECTORS
+}
+
+/// The vector for the generator type called `name` (e.g. `"Xoshiro256StarStar"`).
+pub fn reference_vector(name: &str) -> Option<&'static ReferenceVector> {
+    VECTORS.iter().find(|v| v.name == name)
+}
+
+static VECTORS: [ReferenceVector; 9] = [
+    ReferenceVector {
+        name: "SplitMix64",
+        source: "splitmix64.c, x = 1234567",
+        bits: 64,
+        outputs: &[
+            6457827717110365317,
+            3203168211198807973,
+            9817491932198370423,
+            4593380528125082431,
+            16408922859458223821,
+            7804594928223864054,
+            10895525637215051397,
+            5078158048327840177,
+        ],
+        build: || Box::new(SplitMix64::from_state(1234567)),
+    },
+    ReferenceVector {
+        name: "Xoshiro256StarStar",
+        source: "xoshiro256starstar.c, s = {1, 2, 3, 4}",
+        bits: 64,
+        outputs: &[
+            11520,
+            0,
+            1509978240,
+            1215971899390074240,
+            1216172134540287360,
+            607988272756665600,
+            16172922978634559625,
+            8476171486693032832,
+        ],
+        build: || Box::new(Xoshiro256StarStar::from_state([1, 2, 3, 4]).unwrap()),
+    },
+    ReferenceVector {
+        name: "Xoshiro256PlusPlus",
+        source: "xoshiro256plusplus.c, s = {1, 2, 3, 4}",
+        bits: 64,
+        outputs: &[
+            41943041,
+            58720359,
+            3588806011781223,
+            3591011842654386,
+            9228616714210784205,
+            9973669472204895162,
+            14011001112246962877,
+            12406186145184390807,
+        ],
+        build: || Box::new(Xoshiro256PlusPlus::from_state([1, 2, 3, 4]).unwrap()),
+    },
+    ReferenceVector {
+        name: "Xoshiro256Plus",
+        source: "xoshiro256plus.c, s = {1, 2, 3, 4}",
+        bits: 64,
+        outputs: &[
+            5,
+            211106232532999,
+            211106635186183,
+            9223759065350669058,
+            9250833439874351877,
+            13862484359527728515,
+            2346507365006083650,
+            1168864526675804870,
+        ],
+        build: || Box::new(Xoshiro256Plus::from_state([1, 2, 3, 4]).unwrap()),
+    },
+    ReferenceVector {
+        name: "Xoroshiro128PlusPlus",
+        source: "xoroshiro128plusplus.c, s = {1, 2}",
+        bits: 64,
+        outputs: &[
+            393217,
+            669327710093319,
+            1732421326133921491,
+            11394790081659126983,
+            9555452776773192676,
+            3586421180005889563,
+            1691397964866707553,
+            10735626796753111697,
+        ],
+        build: || Box::new(Xoroshiro128PlusPlus::from_state([1, 2]).unwrap()),
+    },
+    ReferenceVector {
+        name: "WyRand",
+        source: "wyhash.h wyrand, seed = 1234567",
+        bits: 64,
+        outputs: &[
+            1039220411907061708,
+            7824934774743837796,
+            15045726798686391609,
+            11897712078968232033,
+            16181740736517372213,
+            7038416820417128368,
+            15876699215728328229,
+            5328484693322799149,
+        ],
+        build: || Box::new(WyRand::from_state(1234567)),
+    },
+    ReferenceVector {
+        name: "Pcg32",
+        source: "pcg-c-basic pcg32_srandom_r(42, 54)",
+        bits: 32,
+        outputs: &[
+            0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e, 0xbfc6a3ad,
+            0x812fff6d,
+        ],
+        build: || Box::new(Pcg32::from_seed_and_stream(42, 54)),
+    },
+    ReferenceVector {
+        name: "Philox4x32",
+        source: "Random123 kat_vectors philox4x32_10, key = {0xa4093822, 0x299f31d0}, \
+                 ctr = {0x243f6a88, 0x85a308d3, 0x13198a2e, 0x03707344}",
+        bits: 32,
+        outputs: &[0xd16cfe09, 0x94fdcceb, 0x5001e420, 0x24126ea1],
+        build: || {
+            Box::new(Philox4x32::from_key_and_counter(
+                [0xa4093822, 0x299f31d0],
+                [0x243f6a88, 0x85a308d3, 0x13198a2e, 0x03707344],
+            ))
+        },
+    },
+    ReferenceVector {
+        name: "Mt19937_64",
+        source: "mt19937-64.c init_by_array64({0x12345, 0x23456, 0x34567, 0x45678})",
+        bits: 64,
+        outputs: &[
+            7266447313870364031,
+            4946485549665804864,
+            16945909448695747420,
+            16394063075524226720,
+            4873882236456199058,
+        ],
+        build: || Box::new(Mt19937_64::from_key(&[0x12345, 0x23456, 0x34567, 0x45678])),
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_generator_matches_its_reference() {
+        for v in reference_vectors() {
+            assert!(v.verify(&mut *v.generator()), "{} diverges", v.name);
+            assert!(!v.verify(&mut SplitMix64::from_state(0)), "{}", v.name);
+        }
+        assert!(reference_vector("Mt19937_64").is_some());
+        assert!(reference_vector("Mt19937").is_none());
+    }
+}
@@ -0,0 +1,351 @@
+//! Shuffles, permutations and sampling without replacement, built on the unbiased
+//! [`gen_index`](RngExt::gen_index).
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+
+use super::{RngCore, RngExt};
+
+/// Shuffles `slice` in place; every permutation is equally likely.
+pub fn shuffle<T, R: RngCore + ?Sized>(slice: &mut [T], rng: &mut R) {
+    partial_shuffle(slice, slice.len(), rng);
+}
+
+/// Moves a uniformly random `k`-subset, in uniformly random order, to the front of `slice`
+/// and returns it. `k` is clamped to `slice.len()`; costs `k` index draws.
+pub fn partial_shuffle<'a, T, R: RngCore + ?Sized>(
+    slice: &'a mut [T],
+    k: usize,
+    rng: &mut R,
+) -> &'a mut [T] {
+    let n = slice.len();
+    let k = k.min(n);
+    for i in 0..k {
+        let j = i + rng.gen_index(n - i);
+        slice.swap(i, j);
+    }
+    &mut slice[..k]
+}
+
+/// A uniformly random permutation of `0..n`.
+pub fn random_permutation<R: RngCore + ?Sized>(n: usize, rng: &mut R) -> Vec<usize> {
+    let mut p: Vec<usize> = (0..n).collect();
+    shuffle(&mut p, rng);
+    p
+}
+
+/// A uniformly chosen element, or `None` if `slice` is empty.
+pub fn choose<'a, T, R: RngCore + ?Sized>(slice: &'a [T], rng: &mut R) -> Option<&'a T> {
+    if slice.is_empty() {
+        None
+    } else {
+        Some(&slice[rng.gen_index(slice.len())])
+    }
+}
+
+/// `k` distinct elements (a uniform `k`-subset by position), in the order of
+/// [`sample_indices`]. `k` is clamped to `slice.len()`.
+pub fn choose_multiple<'a, T, R: RngCore + ?Sized>(
+    slice: &'a [T],
+    k: usize,
+    rng: &mut R,
+) -> Vec<&'a T> {
+    sample_indices(slice.len(), k, rng)
+        .into_iter()
+        .map(|i| &slice[i])
+        .collect()
+}
+
+/// `k` distinct indices from `0..n`, every subset equally likely, using Floyd's algorithm
+/// (exactly `k` draws). The order is not uniformly random; [`shuffle`] it if that matters.
+/// `k` is clamped to `n`.
+pub fn sample_indices<R: RngCore + ?Sized>(n: usize, k: usize, rng: &mut R) -> Vec<usize> {
+    let k = k.min(n);
+    let mut picked = Vec::with_capacity(k);
+    let mut seen = HashSet::with_capacity(k);
+    for j in n - k..n {
+        let t = rng.gen_index(j + 1);
+        // j itself cannot have been picked yet, so it replaces a repeated t.
+        let x = if seen.contains(&t) { j } else { t };
+        seen.insert(x);
+        picked.push(x);
+    }
+    picked
+}
+
+/// Uniform `k`-sample from a stream of unknown length, in O(k) memory.
+///
+/// Uses Li's Algorithm L: after the reservoir fills, the number of items to skip before
+/// the next replacement is drawn directly, so only O(k log(n/k)) items cost random draws.
+///
+/// ```
+/// use probability_rs::rng::{SplitMix64, seq::ReservoirSampler};
+///
+/// let mut rng = SplitMix64::seed_from_u64(1);
+/// let mut r = ReservoirSampler::new(3);
+/// r.extend(0..1_000_000, &mut rng);
+/// assert_eq!(r.into_sample().len(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReservoirSampler<T> {
+    k: usize,
+    reservoir: Vec<T>,
+    seen: u64,
+    next: u64,
+    w: f64,
+}
+
+impl<T> ReservoirSampler<T> {
+    /// An empty reservoir keeping `k` items.
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            reservoir: Vec::with_capacity(k),
+            seen: 0,
+            next: 0,
+            w: 1.0,
+        }
+    }
+
+    /// Offers the next stream item.
+    pub fn push<R: RngCore + ?Sized>(&mut self, item: T, rng: &mut R) {
+        let i = self.seen;
+        self.seen += 1;
+        if self.reservoir.len() < self.k {
+            self.reservoir.push(item);
+            if self.reservoir.len() == self.k {
+                self.next = i;
+                self.advance(rng);
+            }
+        } else if i == self.next && self.k > 0 {
+            let slot = rng.gen_index(self.k);
+            self.reservoir[slot] = item;
+            self.advance(rng);
+        }
+    }
+
+    /// Offers every item of `iter`.
+    pub fn extend<I: IntoIterator<Item = T>, R: RngCore + ?Sized>(&mut self, iter: I, rng: &mut R) {
+        for item in iter {
+            self.push(item, rng);
+        }
+    }
+
+    /// Shrinks the acceptance weight and schedules the next replacement.
+    fn advance<R: RngCore + ?Sized>(&mut self, rng: &mut R) {
+        let k = self.k as f64;
+        self.w *= (rng.next_f64_open_open().ln() / k).exp();
+        let skip = (rng.next_f64_open_open().ln() / (-self.w).ln_1p()).floor();
+        // Saturates for astronomically long skips; such streams never reach `next`.
+        self.next = self.next.saturating_add(skip as u64).saturating_add(1);
+    }
+
+    /// Number of items offered so far.
+    #[inline]
+    pub fn seen(&self) -> u64 {
+        self.seen
+    }
+
+    /// Current sample, fewer than `k` items while the stream is shorter than `k`.
+    #[inline]
+    pub fn sample(&self) -> &[T] {
+        &self.reservoir
+    }
+
+    pub fn into_sample(self) -> Vec<T> {
+        self.reservoir
+    }
+}
+
+/// `k` distinct items drawn with probability proportional to `weights`, one at a time
+/// without replacement, via Efraimidis–Spirakis exponential keys. Items come back in
+/// draw order; zero-weight items are never chosen, so fewer than `k` may be returned.
+///
+/// Panics if the lengths differ or a weight is negative or not finite.
+pub fn choose_multiple_weighted<'a, T, R: RngCore + ?Sized>(
+    items: &'a [T],
+    weights: &[f64],
+    k: usize,
+    rng: &mut R,
+) -> Vec<&'a T> {
+    assert_eq!(items.len(), weights.len(), "one weight per item");
+    let mut r = WeightedReservoirSampler::new(k);
+    for (item, &w) in items.iter().zip(weights) {
+        r.push(item, w, rng);
+    }
+    r.into_sample()
+}
+
+/// Streaming counterpart of [`choose_multiple_weighted`] (A-Res): keeps the `k` items
+/// with the largest keys `ln(u) / w` in a heap, O(log k) per item.
+#[derive(Debug, Clone)]
+pub struct WeightedReservoirSampler<T> {
+    k: usize,
+    heap: BinaryHeap<Keyed<T>>,
+}
+
+/// Heap entry ordered so the smallest key sits on top, ready to be evicted.
+#[derive(Debug, Clone)]
+struct Keyed<T> {
+    key: f64,
+    item: T,
+}
+
+impl<T> PartialEq for Keyed<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T> Eq for Keyed<T> {}
+
+impl<T> PartialOrd for Keyed<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Keyed<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.key.total_cmp(&self.key)
+    }
+}
+
+impl<T> WeightedReservoirSampler<T> {
+    /// An empty reservoir keeping `k` items.
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            heap: BinaryHeap::with_capacity(k),
+        }
+    }
+
+    /// Offers `item` with `weight`. Panics if the weight is negative or not finite.
+    pub fn push<R: RngCore + ?Sized>(&mut self, item: T, weight: f64, rng: &mut R) {
+        assert!(
+            weight.is_finite() && weight >= 0.0,
+            "weight must be finite and non-negative, got {weight}"
+        );
+        if weight == 0.0 || self.k == 0 {
+            return;
+        }
+        let key = rng.next_f64_open_open().ln() / weight;
+        if self.heap.len() < self.k {
+            self.heap.push(Keyed { key, item });
+        } else if self.heap.peek().is_some_and(|min| key > min.key) {
+            self.heap.pop();
+            self.heap.push(Keyed { key, item });
+        }
+    }
+
+    /// The kept items, in draw order (largest key first).
+    pub fn into_sample(self) -> Vec<T> {
+        // `Ord` is reversed, so ascending order is descending key.
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|e| e.item)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+
+    #[test]
+    fn permutations_are_uniform() {
+        // All 6 orderings of 3 elements, each ~1/6.
+        let mut rng = SplitMix64::seed_from_u64(21);
+        let mut counts = std::collections::HashMap::new();
+        for _ in 0..60_000 {
+            *counts
+                .entry(random_permutation(3, &mut rng))
+                .or_insert(0u32) += 1;
+        }
+        assert_eq!(counts.len(), 6);
+        for &c in counts.values() {
+            assert!((c as f64 - 10_000.0).abs() < 400.0, "{counts:?}");
+        }
+    }
+
+    #[test]
+    fn partial_shuffle_keeps_elements() {
+        let mut rng = SplitMix64::seed_from_u64(2);
+        let mut v: Vec<u32> = (0..10).collect();
+        assert_eq!(partial_shuffle(&mut v, 3, &mut rng).len(), 3);
+        assert_eq!(partial_shuffle(&mut v, 99, &mut rng).len(), 10);
+        v.sort_unstable();
+        assert_eq!(v, (0..10).collect::<Vec<_>>());
+        shuffle(&mut [] as &mut [u8], &mut rng);
+    }
+
+    #[test]
+    fn floyd_subsets_are_uniform() {
+        // C(5, 2) = 10 subsets, each ~1/10.
+        let mut rng = SplitMix64::seed_from_u64(5);
+        let mut counts = std::collections::HashMap::new();
+        for _ in 0..50_000 {
+            let mut s = sample_indices(5, 2, &mut rng);
+            assert_ne!(s[0], s[1]);
+            s.sort_unstable();
+            *counts.entry(s).or_insert(0u32) += 1;
+        }
+        assert_eq!(counts.len(), 10);
+        for &c in counts.values() {
+            assert!((c as f64 - 5_000.0).abs() < 300.0, "{counts:?}");
+        }
+        let items = ["a", "b", "c"];
+        assert_eq!(choose_multiple(&items, 5, &mut rng).len(), 3);
+        assert!(items.contains(choose(&items, &mut rng).unwrap()));
+        assert!(choose(&[] as &[u8], &mut rng).is_none());
+    }
+
+    #[test]
+    fn reservoir_inclusion_is_uniform() {
+        // Each of 50 items lands in a 5-sample with probability 1/10.
+        let mut rng = SplitMix64::seed_from_u64(17);
+        let mut counts = [0u32; 50];
+        for _ in 0..20_000 {
+            let mut r = ReservoirSampler::new(5);
+            r.extend(0..50usize, &mut rng);
+            assert_eq!(r.seen(), 50);
+            for &i in r.sample() {
+                counts[i] += 1;
+            }
+        }
+        for &c in &counts {
+            assert!((c as f64 - 2_000.0).abs() < 200.0, "{counts:?}");
+        }
+        let mut short = ReservoirSampler::new(10);
+        short.extend(0..4, &mut rng);
+        assert_eq!(short.into_sample(), vec![0, 1, 2, 3]);
+        let mut none = ReservoirSampler::new(0);
+        none.extend(0..4, &mut rng);
+        assert!(none.sample().is_empty());
+    }
+
+    #[test]
+    fn weighted_first_draw_is_proportional() {
+        // The first pick of a weighted draw without replacement has P(i) = w_i / sum(w).
+        let mut rng = SplitMix64::seed_from_u64(30);
+        let items = [0usize, 1, 2, 3];
+        let weights = [1.0, 2.0, 3.0, 0.0];
+        let mut first = [0u32; 4];
+        for _ in 0..30_000 {
+            let pick = choose_multiple_weighted(&items, &weights, 2, &mut rng);
+            assert_eq!(pick.len(), 2);
+            assert_ne!(pick[0], pick[1]);
+            first[*pick[0]] += 1;
+        }
+        assert_eq!(first[3], 0);
+        for (i, want) in [5_000.0, 10_000.0, 15_000.0].into_iter().enumerate() {
+            assert!((first[i] as f64 - want).abs() < 400.0, "{first:?}");
+        }
+        assert_eq!(
+            choose_multiple_weighted(&items, &weights, 9, &mut rng).len(),
+            3
+        );
+    }
+}
@@ -0,0 +1,97 @@
+//! `serde` support for the generators (feature `serde`): each serializes as its
+//! [`state`](super::SplitMix64::state) and deserializes through `from_state`, rejecting
+//! invalid states.
+
+use super::{
+    Mt19937_64, Pcg32, Philox4x32, SplitMix64, WyRand, Xoroshiro128PlusPlus,
+    xoshiro256::{Xoshiro256Plus, Xoshiro256PlusPlus, Xoshiro256StarStar},
+};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// Generators for which every `u64` is a valid state.
+macro_rules! serde_via_u64 {
+    ($($ty:ident),*) => {$(
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                self.state().serialize(s)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+                Ok($ty::from_state(u64::deserialize(d)?))
+            }
+        }
+    )*};
+}
+
+serde_via_u64!(SplitMix64, WyRand);
+
+// 312 words exceed serde's fixed-size array impls: (words as a sequence, index).
+impl Serialize for Mt19937_64 {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let (mt, index) = self.state();
+        (&mt[..], index).serialize(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for Mt19937_64 {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let (words, index) = <(Vec<u64>, usize)>::deserialize(d)?;
+        let mt = words
+            .try_into()
+            .map_err(|w: Vec<u64>| D::Error::invalid_length(w.len(), &"312 words"))?;
+        Mt19937_64::from_state(mt, index)
+            .ok_or_else(|| D::Error::custom("invalid mt19937-64 state"))
+    }
+}
+
+macro_rules! serde_via_state {
+    ($($ty:ty: [u64; $n:literal], $invalid:literal;)*) => {$(
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                self.state().serialize(s)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+                let state = <[u64; $n]>::deserialize(d)?;
+                <$ty>::from_state(state).ok_or_else(|| D::Error::custom($invalid))
+            }
+        }
+    )*};
+}
+
+serde_via_state! {
+    Xoroshiro128PlusPlus: [u64; 2], "all-zero xoroshiro128++ state";
+    Xoshiro256StarStar: [u64; 4], "all-zero xoshiro256** state";
+    Xoshiro256PlusPlus: [u64; 4], "all-zero xoshiro256++ state";
+    Xoshiro256Plus: [u64; 4], "all-zero xoshiro256+ state";
+    Pcg32: [u64; 2], "pcg32 increment must be odd";
+    Philox4x32: [u64; 4], "philox word index must be below 4";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::RngCore;
+
+    #[test]
+    fn checkpoint_resumes_stream() {
+        let mut rng = Pcg32::seed_from_u64(77);
+        rng.next_u64();
+        let json = serde_json::to_string(&rng).unwrap();
+        let mut restored: Pcg32 = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.next_u64(), rng.next_u64());
+        assert!(serde_json::from_str::<Pcg32>("[1,2]").is_err());
+        assert!(serde_json::from_str::<Xoshiro256StarStar>("[0,0,0,0]").is_err());
+        let mut mt = Mt19937_64::seed_from_u64(3);
+        mt.next_u64();
+        let json = serde_json::to_string(&mt).unwrap();
+        let mut restored: Mt19937_64 = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.next_u64(), mt.next_u64());
+        assert!(serde_json::from_str::<Mt19937_64>("[[1,2],0]").is_err());
+    }
+}
@@ -1,8 +1,8 @@
 //! SplitMix64 (Sebastiano Vigna): fast, good bit diffusion, non-cryptographic RNG.
 
-use super::RngCore;
+use super::{RngCore, SeedableRng, Streams};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SplitMix64 {
     pub(crate) state: u64,
 }
@@ -11,6 +11,38 @@ impl SplitMix64 {
     pub fn seed_from_u64(seed: u64) -> Self {
         Self { state: seed }
     }
+
+    /// Internal state; [`from_state`](Self::from_state) resumes the stream exactly.
+    #[inline]
+    pub fn state(&self) -> u64 {
+        self.state
+    }
+
+    /// Restores a generator from [`state`](Self::state). Every `u64` is a valid state.
+    #[inline]
+    pub fn from_state(state: u64) -> Self {
+        Self { state }
+    }
+}
+
+impl SeedableRng for SplitMix64 {
+    type Seed = [u8; 8];
+    fn from_seed(seed: [u8; 8]) -> Self {
+        SplitMix64::from_state(u64::from_le_bytes(seed))
+    }
+    fn seed_from_u64(state: u64) -> Self {
+        SplitMix64::seed_from_u64(state)
+    }
+}
+
+impl Streams for SplitMix64 {
+    /// `i · ⌊2^64/φ⌋` draws ahead. That multiplier is odd, so no two `i` share a start,
+    /// and the starts spread out like the golden-ratio sequence.
+    fn substream(&self, i: u64) -> Self {
+        // The state moves by the increment per draw.
+        let stride: u64 = 0x9E3779B97F4A7C15u64.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        Self::from_state(self.state.wrapping_add(i.wrapping_mul(stride)))
+    }
 }
 
 impl RngCore for SplitMix64 {
@@ -37,4 +69,21 @@ mod tests {
             assert_eq!(r1.next_u64(), r2.next_u64());
         }
     }
+
+    #[test]
+    fn substreams_start_apart() {
+        use crate::rng::Streams;
+        let base = SplitMix64::seed_from_u64(3);
+        assert_eq!(base.substream(0).state(), base.state());
+        // A stride of 2^48 draws brought substream 2^16 back onto substream 0.
+        let starts: Vec<u64> = [0, 1, 1 << 16, 1 << 32]
+            .map(|i| base.substream(i).state())
+            .to_vec();
+        assert!(
+            starts
+                .iter()
+                .enumerate()
+                .all(|(i, s)| !starts[..i].contains(s))
+        );
+    }
 }
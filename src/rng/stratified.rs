@@ -0,0 +1,59 @@
+//! Stratified uniforms: one jittered draw in each of `n` equal strata of `(0,1)`.
+//!
+//! Averages over stratified points have variance at most that of `n` iid draws, and far
+//! less for smooth integrands. The distribution-level form is
+//! [`Continuous::sample_stratified`](crate::Continuous::sample_stratified).
+
+use super::RngCore;
+
+/// `u[i]` uniform in `(i/n, (i+1)/n)`, in stratum order.
+///
+/// ```
+/// use probability_rs::rng::{SplitMix64, stratified::stratified_uniforms};
+///
+/// let u = stratified_uniforms(4, &mut SplitMix64::seed_from_u64(1));
+/// assert!(u.iter().enumerate().all(|(i, &x)| (x * 4.0) as usize == i));
+/// ```
+pub fn stratified_uniforms<R: RngCore + ?Sized>(n: usize, rng: &mut R) -> Vec<f64> {
+    let n_f = n as f64;
+    // n − 1 + u can round up to n; keep the top stratum below 1.
+    (0..n)
+        .map(|i| ((i as f64 + rng.next_f64_open_open()) / n_f).min(1.0 - f64::EPSILON / 2.0))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+    use crate::rng::mock::ScriptedRng;
+
+    #[test]
+    fn reduces_variance_of_the_mean() {
+        // Mean of u^2 over 64 points: stratified error is far below the iid 1/sqrt(n) scale.
+        let mut rng = SplitMix64::seed_from_u64(6);
+        let mut worst = 0.0f64;
+        for _ in 0..200 {
+            let m = stratified_uniforms(64, &mut rng)
+                .iter()
+                .map(|u| u * u)
+                .sum::<f64>()
+                / 64.0;
+            worst = worst.max((m - 1.0 / 3.0).abs());
+        }
+        assert!(worst < 5e-3, "{worst}");
+        assert!(stratified_uniforms(0, &mut rng).is_empty());
+    }
+
+    #[test]
+    fn top_stratum_stays_below_one() {
+        // The largest open-open draw, 1 − 2^-53, puts n − 1 + u within rounding of n.
+        let mut rng = ScriptedRng::new(&[u64::MAX]);
+        for n in [2, 3, 1000] {
+            assert!(
+                stratified_uniforms(n, &mut rng).iter().all(|&u| u < 1.0),
+                "{n}"
+            );
+        }
+    }
+}
@@ -0,0 +1,192 @@
+//! Generators shareable across threads through `&`.
+//!
+//! [`RngCore`] takes `&mut self`, so both wrappers implement it for `&Self` as well:
+//! give each thread a copy of the reference and draw through `&mut handle`.
+//!
+//! ```
+//! use probability_rs::Distribution;
+//! use probability_rs::dist::normal::Normal;
+//! use probability_rs::rng::{SplitMix64, sync::SharedRng};
+//!
+//! let shared = SharedRng::new(SplitMix64::seed_from_u64(3));
+//! let n = Normal::standard();
+//! std::thread::scope(|s| {
+//!     for _ in 0..4 {
+//!         s.spawn(|| {
+//!             let mut rng = &shared;
+//!             let _x = n.sample(&mut rng);
+//!         });
+//!     }
+//! });
+//! ```
+//!
+//! Which thread receives which draw depends on scheduling. [`SharedRng`] serializes
+//! every call on a mutex; [`SharedPhilox`] is lock-free and hands out positions of one
+//! Philox stream, so the set of values drawn is reproducible even though their
+//! assignment to threads is not. For per-thread reproducibility give each worker its own
+//! [`substream`](super::Streams::substream) instead.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+use super::{Philox4x32, RngCore};
+
+/// Any generator behind a mutex.
+#[derive(Debug, Default)]
+pub struct SharedRng<R> {
+    inner: Mutex<R>,
+}
+
+impl<R: RngCore> SharedRng<R> {
+    pub fn new(rng: R) -> Self {
+        Self {
+            inner: Mutex::new(rng),
+        }
+    }
+
+    /// Locks the generator for a batch of draws. A panic while holding the lock cannot
+    /// leave the state invalid, so poisoning is ignored.
+    pub fn lock(&self) -> MutexGuard<'_, R> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<R: RngCore> RngCore for &SharedRng<R> {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.lock().next_u64()
+    }
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.lock().next_u32()
+    }
+    fn fill_u64(&mut self, dest: &mut [u64]) {
+        self.lock().fill_u64(dest)
+    }
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.lock().fill_bytes(dest)
+    }
+    fn uniform_bits(&self) -> bool {
+        self.lock().uniform_bits()
+    }
+}
+
+impl<R: RngCore> RngCore for SharedRng<R> {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.inner
+            .get_mut()
+            .unwrap_or_else(|e| e.into_inner())
+            .next_u64()
+    }
+    fn uniform_bits(&self) -> bool {
+        self.lock().uniform_bits()
+    }
+}
+
+/// Lock-free Philox4x32 stream: an atomic counter assigns each `next_u64` a position,
+/// and the output is the one a sequential [`Philox4x32::new(key)`](Philox4x32::new) gives at
+/// that position.
+#[derive(Debug)]
+pub struct SharedPhilox {
+    key: [u32; 2],
+    position: AtomicU64,
+}
+
+impl SharedPhilox {
+    pub fn new(key: [u32; 2]) -> Self {
+        Self {
+            key,
+            position: AtomicU64::new(0),
+        }
+    }
+
+    /// Same key as [`Philox4x32::seed_from_u64`].
+    pub fn seed_from_u64(seed: u64) -> Self {
+        Self::new([seed as u32, (seed >> 32) as u32])
+    }
+
+    /// Number of `u64`s handed out so far.
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.position.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    fn at(&self, pos: u64) -> u64 {
+        let block = pos / 2;
+        let ctr = [block as u32, (block >> 32) as u32, 0, 0];
+        let w = Philox4x32::block(self.key, ctr);
+        let i = 2 * (pos % 2) as usize;
+        (w[i] as u64) << 32 | w[i + 1] as u64
+    }
+}
+
+impl RngCore for &SharedPhilox {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.at(self.position.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl RngCore for SharedPhilox {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let pos = *self.position.get_mut();
+        *self.position.get_mut() = pos + 1;
+        self.at(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+
+    fn draw_in_threads<R>(shared: &R) -> Vec<u64>
+    where
+        for<'a> &'a R: RngCore,
+        R: Sync,
+    {
+        let mut all: Vec<u64> = std::thread::scope(|s| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| {
+                    s.spawn(move || {
+                        let mut rng = shared;
+                        (0..250).map(|_| rng.next_u64()).collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().unwrap())
+                .collect()
+        });
+        all.sort_unstable();
+        all
+    }
+
+    #[test]
+    fn threads_share_one_stream() {
+        let mut seq = SplitMix64::seed_from_u64(5);
+        let mut want: Vec<u64> = (0..1000).map(|_| seq.next_u64()).collect();
+        want.sort_unstable();
+        let shared = SharedRng::new(SplitMix64::seed_from_u64(5));
+        assert_eq!(draw_in_threads(&shared), want);
+        assert_eq!(shared.into_inner(), seq);
+
+        let mut seq = Philox4x32::seed_from_u64(5);
+        let mut want: Vec<u64> = (0..1000).map(|_| seq.next_u64()).collect();
+        let mut direct = SharedPhilox::seed_from_u64(5);
+        assert_eq!(direct.next_u64(), want[0]);
+        assert_eq!(direct.next_u64(), want[1]);
+        want.sort_unstable();
+        let shared = SharedPhilox::seed_from_u64(5);
+        assert_eq!(draw_in_threads(&shared), want);
+        assert_eq!(shared.position(), 1000);
+    }
+}
@@ -0,0 +1,92 @@
+//! Per-thread generator seeded from process entropy, for code that does not need
+//! reproducibility. Seed an explicit generator whenever results must be replayable.
+
+use std::cell::RefCell;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::RngCore;
+use super::xoshiro256::Xoshiro256StarStar;
+use crate::dist::Distribution;
+
+thread_local! {
+    static THREAD_RNG: RefCell<Xoshiro256StarStar> =
+        RefCell::new(Xoshiro256StarStar::seed_from_u64(entropy_seed()));
+}
+
+/// Mixes the std hasher's random keys (OS-seeded), the clock, the thread id and a
+/// process-wide counter, so threads started together still get distinct seeds.
+fn entropy_seed() -> u64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut h = RandomState::new().build_hasher();
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    nanos.hash(&mut h);
+    std::thread::current().id().hash(&mut h);
+    COUNTER.fetch_add(1, Ordering::Relaxed).hash(&mut h);
+    h.finish()
+}
+
+/// Handle to the calling thread's generator (Xoshiro256**). Handles are cheap, share one
+/// stream per thread, and cannot be sent to other threads.
+#[derive(Clone, Debug, Default)]
+pub struct ThreadRng {
+    _not_send: PhantomData<*const ()>,
+}
+
+/// The calling thread's generator, seeded from entropy on first use.
+///
+/// ```
+/// use probability_rs::dist::normal::Normal;
+/// use probability_rs::rng::{self, RngCore};
+///
+/// let u = rng::thread_rng().next_f64();
+/// assert!((0.0..1.0).contains(&u));
+/// let x = rng::sample(&Normal::standard());
+/// assert!(x.is_finite());
+/// ```
+#[inline]
+pub fn thread_rng() -> ThreadRng {
+    ThreadRng::default()
+}
+
+/// One draw from `dist` using [`thread_rng`].
+#[inline]
+pub fn sample<D: Distribution>(dist: &D) -> D::Value {
+    dist.sample(&mut thread_rng())
+}
+
+impl RngCore for ThreadRng {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        THREAD_RNG.with(|r| r.borrow_mut().next_u64())
+    }
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        THREAD_RNG.with(|r| r.borrow_mut().fill_bytes(dest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_share_the_thread_stream() {
+        let mut a = thread_rng();
+        let mut b = a.clone();
+        let x = a.next_u64();
+        let y = b.next_u64();
+        assert_ne!(x, y);
+        let other = std::thread::spawn(|| thread_rng().next_u64())
+            .join()
+            .unwrap();
+        assert_ne!(other, x);
+        assert_ne!(other, y);
+        let b = sample(&crate::dist::bernoulli::Bernoulli::new(1.0).unwrap());
+        assert_eq!(b, 1);
+    }
+}
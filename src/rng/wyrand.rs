@@ -0,0 +1,87 @@
+//! WyRand (Wang Yi, from wyhash): a Weyl sequence finalized by one 64x64→128 multiply.
+//! 64-bit state and a single multiply per draw, for workloads where generator speed
+//! dominates. Period 2^64; passes BigCrush and PractRand, but prefer xoshiro256 when
+//! statistical margin matters. Not cryptographic.
+
+use super::{RngCore, SeedableRng, Streams};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WyRand {
+    state: u64,
+}
+
+impl WyRand {
+    pub fn seed_from_u64(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Internal state; [`from_state`](Self::from_state) resumes the stream exactly.
+    #[inline]
+    pub fn state(&self) -> u64 {
+        self.state
+    }
+
+    /// Restores a generator from [`state`](Self::state). Every `u64` is a valid state.
+    #[inline]
+    pub fn from_state(state: u64) -> Self {
+        Self { state }
+    }
+}
+
+impl SeedableRng for WyRand {
+    type Seed = [u8; 8];
+    fn from_seed(seed: [u8; 8]) -> Self {
+        WyRand::from_state(u64::from_le_bytes(seed))
+    }
+    fn seed_from_u64(state: u64) -> Self {
+        WyRand::seed_from_u64(state)
+    }
+}
+
+impl Streams for WyRand {
+    /// `i · ⌊2^64/φ⌋` draws ahead. That multiplier is odd, so no two `i` share a start,
+    /// and the starts spread out like the golden-ratio sequence.
+    fn substream(&self, i: u64) -> Self {
+        // The state moves by the increment per draw.
+        let stride: u64 = 0xa076_1d64_78bd_642fu64.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        Self::from_state(self.state.wrapping_add(i.wrapping_mul(stride)))
+    }
+}
+
+impl RngCore for WyRand {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0xa076_1d64_78bd_642f);
+        let t = (self.state as u128) * ((self.state ^ 0xe703_7ed1_a0b4_28db) as u128);
+        ((t >> 64) as u64) ^ (t as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_sequence() {
+        let mut r = WyRand::seed_from_u64(42);
+        assert_eq!(r.next_u64(), 12558987674375533620);
+        assert_eq!(r.next_u64(), 16846851108956068306);
+        assert_eq!(r.next_u64(), 14652274819296609082);
+    }
+
+    #[test]
+    fn substreams_start_apart() {
+        use crate::rng::Streams;
+        let base = WyRand::seed_from_u64(3);
+        assert_eq!(base.substream(0).state(), base.state());
+        let starts: Vec<u64> = [0, 1, 1 << 16, 1 << 32]
+            .map(|i| base.substream(i).state())
+            .to_vec();
+        assert!(
+            starts
+                .iter()
+                .enumerate()
+                .all(|(i, s)| !starts[..i].contains(s))
+        );
+    }
+}
@@ -1,9 +1,9 @@
 //! Xoroshiro128++ (Blackman & Vigna): fast, small-state RNG (128-bit).
 //! Not suitable for cryptographic use. Period: 2^128 - 1.
 
-use super::RngCore;
+use super::{RngCore, SeedableRng, Streams};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Xoroshiro128PlusPlus {
     s0: u64,
     s1: u64,
@@ -21,9 +21,26 @@ impl Xoroshiro128PlusPlus {
         Self { s0, s1 }
     }
 
+    /// Internal state `[s0, s1]`; [`from_state`](Self::from_state) resumes the stream exactly.
+    #[inline]
+    pub fn state(&self) -> [u64; 2] {
+        [self.s0, self.s1]
+    }
+
+    /// Restores a generator from [`state`](Self::state); `None` for the invalid all-zero state.
+    pub fn from_state(state: [u64; 2]) -> Option<Self> {
+        if state == [0, 0] {
+            return None;
+        }
+        Some(Self {
+            s0: state[0],
+            s1: state[1],
+        })
+    }
+
     #[inline]
     fn rotl(x: u64, k: u32) -> u64 {
-        (x << k) | (x >> (64 - k))
+        x.rotate_left(k)
     }
 
     /// Jump equivalent to 2^64 calls; can be used to generate non-overlapping sequences.
@@ -67,6 +84,28 @@ impl Xoroshiro128PlusPlus {
     }
 }
 
+impl SeedableRng for Xoroshiro128PlusPlus {
+    type Seed = [u8; 16];
+    fn from_seed(seed: [u8; 16]) -> Self {
+        Xoroshiro128PlusPlus::from_state(super::le_words(&seed))
+            .unwrap_or_else(|| Xoroshiro128PlusPlus::seed_from_u64(0))
+    }
+    fn seed_from_u64(state: u64) -> Self {
+        Xoroshiro128PlusPlus::seed_from_u64(state)
+    }
+}
+
+impl Streams for Xoroshiro128PlusPlus {
+    /// `i` [`jump`](Self::jump)s ahead.
+    fn substream(&self, i: u64) -> Self {
+        let mut rng = self.clone();
+        for _ in 0..i {
+            rng.jump();
+        }
+        rng
+    }
+}
+
 impl RngCore for Xoroshiro128PlusPlus {
     #[inline]
     fn next_u64(&mut self) -> u64 {
@@ -1,105 +1,159 @@
-//! Xoshiro256** (Blackman & Vigna): fast, high-quality, non-cryptographic RNG.
-//! State: 256-bit (4 x u64). Period: 2^256 - 1. Excellent statistical properties.
+//! The xoshiro256 family (Blackman & Vigna): fast, high-quality, non-cryptographic RNGs.
+//! State: 256-bit (4 x u64). Period: 2^256 - 1. The variants share the state transition
+//! and jumps and differ only in the output scrambler:
+//! - [`Xoshiro256StarStar`] (`**`): all-purpose, excellent statistical properties;
+//! - [`Xoshiro256PlusPlus`] (`++`): all-purpose, equally strong;
+//! - [`Xoshiro256Plus`] (`+`): fastest, for floats (its lowest bits are weak).
+//!
 //! Not suitable for cryptographic use.
 
-use super::RngCore;
+use super::{RngCore, SeedableRng, Streams};
 
-#[derive(Clone, Debug)]
-pub struct Xoshiro256StarStar {
-    s: [u64; 4],
+const JUMP: [u64; 4] = [
+    0x180ec6d33cfd0aba,
+    0xd5a61266f0c9392c,
+    0xa9582618e03fc9aa,
+    0x39abdc4529b1661c,
+];
+const LONG_JUMP: [u64; 4] = [
+    0x76e15d3efefdcbbf,
+    0xc5004e441c522fb3,
+    0x77710069854ee241,
+    0x39109bb02acbe635,
+];
+
+#[inline]
+fn advance(s: &mut [u64; 4]) {
+    let t = s[1] << 17;
+
+    s[2] ^= s[0];
+    s[3] ^= s[1];
+    s[1] ^= s[2];
+    s[0] ^= s[3];
+
+    s[2] ^= t;
+    s[3] = s[3].rotate_left(45);
 }
 
-impl Xoshiro256StarStar {
-    /// Seed from a 64-bit value using SplitMix64 to expand into 256 bits.
-    pub fn seed_from_u64(seed: u64) -> Self {
-        let mut sm = super::SplitMix64::seed_from_u64(seed);
-        let mut s = [0u64; 4];
-        for slot in &mut s {
-            *slot = sm.next_u64();
-        }
-        // All-zero state is invalid; perturb if detected (extremely unlikely).
-        if s == [0, 0, 0, 0] {
-            s[0] = 1;
+fn jump_with(s: &mut [u64; 4], poly: &[u64; 4]) {
+    let mut t = [0u64; 4];
+    for &jump in poly {
+        let mut b = jump;
+        while b != 0 {
+            if (b & 1) != 0 {
+                t[0] ^= s[0];
+                t[1] ^= s[1];
+                t[2] ^= s[2];
+                t[3] ^= s[3];
+            }
+            advance(s);
+            b >>= 1;
         }
-        Self { s }
     }
+    *s = t;
+}
 
-    #[inline]
-    fn rotl(x: u64, k: u32) -> u64 {
-        (x << k) | (x >> (64 - k))
-    }
+macro_rules! xoshiro256 {
+    ($(#[$doc:meta])* $name:ident, |$s:ident| $output:expr) => {
+        $(#[$doc])*
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub struct $name {
+            s: [u64; 4],
+        }
 
-    /// Jump equivalent to 2^128 calls; provides 2^128 non-overlapping subsequences.
-    /// Useful for parallel streams.
-    pub fn jump(&mut self) {
-        const JUMP: [u64; 4] = [
-            0x180ec6d33cfd0aba,
-            0xd5a61266f0c9392c,
-            0xa9582618e03fc9aa,
-            0x39abdc4529b1661c,
-        ];
-        let mut t = [0u64; 4];
-        for &jump in &JUMP {
-            let mut b = jump;
-            while b != 0 {
-                if (b & 1) != 0 {
-                    t[0] ^= self.s[0];
-                    t[1] ^= self.s[1];
-                    t[2] ^= self.s[2];
-                    t[3] ^= self.s[3];
+        impl $name {
+            /// Seed from a 64-bit value using SplitMix64 to expand into 256 bits.
+            pub fn seed_from_u64(seed: u64) -> Self {
+                let mut sm = super::SplitMix64::seed_from_u64(seed);
+                let mut s = [0u64; 4];
+                for slot in &mut s {
+                    *slot = sm.next_u64();
                 }
-                let _ = self.next_u64();
-                b >>= 1;
+                // All-zero state is invalid; perturb if detected (extremely unlikely).
+                if s == [0, 0, 0, 0] {
+                    s[0] = 1;
+                }
+                Self { s }
+            }
+
+            /// Internal state; [`from_state`](Self::from_state) resumes the stream exactly.
+            #[inline]
+            pub fn state(&self) -> [u64; 4] {
+                self.s
             }
-        }
-        self.s = t;
-    }
 
-    /// Long jump equivalent to 2^192 calls.
-    pub fn long_jump(&mut self) {
-        const LJUMP: [u64; 4] = [
-            0x76e15d3efefdcbbf,
-            0xc5004e441c522fb3,
-            0x77710069854ee241,
-            0x39109bb02acbe635,
-        ];
-        let mut t = [0u64; 4];
-        for &jump in &LJUMP {
-            let mut b = jump;
-            while b != 0 {
-                if (b & 1) != 0 {
-                    t[0] ^= self.s[0];
-                    t[1] ^= self.s[1];
-                    t[2] ^= self.s[2];
-                    t[3] ^= self.s[3];
+            /// Restores a generator from [`state`](Self::state); `None` for the invalid
+            /// all-zero state.
+            pub fn from_state(s: [u64; 4]) -> Option<Self> {
+                if s == [0; 4] {
+                    return None;
                 }
-                let _ = self.next_u64();
-                b >>= 1;
+                Some(Self { s })
             }
-        }
-        self.s = t;
-    }
-}
 
-impl RngCore for Xoshiro256StarStar {
-    #[inline]
-    fn next_u64(&mut self) -> u64 {
-        let result = Self::rotl(self.s[1].wrapping_mul(5), 7).wrapping_mul(9);
+            /// Jump equivalent to 2^128 calls; provides 2^128 non-overlapping subsequences.
+            /// Useful for parallel streams.
+            pub fn jump(&mut self) {
+                jump_with(&mut self.s, &JUMP);
+            }
 
-        let t = self.s[1] << 17;
+            /// Long jump equivalent to 2^192 calls.
+            pub fn long_jump(&mut self) {
+                jump_with(&mut self.s, &LONG_JUMP);
+            }
+        }
 
-        self.s[2] ^= self.s[0];
-        self.s[3] ^= self.s[1];
-        self.s[1] ^= self.s[2];
-        self.s[0] ^= self.s[3];
+        impl SeedableRng for $name {
+            type Seed = [u8; 32];
+            fn from_seed(seed: [u8; 32]) -> Self {
+                Self::from_state(super::le_words(&seed)).unwrap_or_else(|| Self::seed_from_u64(0))
+            }
+            fn seed_from_u64(state: u64) -> Self {
+                $name::seed_from_u64(state)
+            }
+        }
 
-        self.s[2] ^= t;
-        self.s[3] = Self::rotl(self.s[3], 45);
+        impl Streams for $name {
+            /// `i` [`jump`](Self::jump)s ahead.
+            fn substream(&self, i: u64) -> Self {
+                let mut rng = self.clone();
+                for _ in 0..i {
+                    rng.jump();
+                }
+                rng
+            }
+        }
 
-        result
-    }
+        impl RngCore for $name {
+            #[inline]
+            fn next_u64(&mut self) -> u64 {
+                let $s = &self.s;
+                let result = $output;
+                advance(&mut self.s);
+                result
+            }
+        }
+    };
 }
 
+xoshiro256!(
+    /// Xoshiro256**: `rotl(s1 * 5, 7) * 9`.
+    Xoshiro256StarStar,
+    |s| s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9)
+);
+
+xoshiro256!(
+    /// Xoshiro256++: `rotl(s0 + s3, 23) + s0`.
+    Xoshiro256PlusPlus,
+    |s| s[0].wrapping_add(s[3]).rotate_left(23).wrapping_add(s[0])
+);
+
+xoshiro256!(
+    /// Xoshiro256+: `s0 + s3`. Use the upper bits (as [`RngCore::next_f64`] does).
+    Xoshiro256Plus,
+    |s| s[0].wrapping_add(s[3])
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,4 +175,34 @@ mod tests {
             assert!((0.0..1.0).contains(&x));
         }
     }
+
+    #[test]
+    fn reference_outputs_from_state_1234() {
+        // Reference C implementations seeded with s = {1, 2, 3, 4}
+        let take = |r: &mut dyn RngCore| [r.next_u64(), r.next_u64(), r.next_u64(), r.next_u64()];
+        let mut ss = Xoshiro256StarStar::from_state([1, 2, 3, 4]).unwrap();
+        assert_eq!(take(&mut ss), [11520, 0, 1509978240, 1215971899390074240]);
+        let mut pp = Xoshiro256PlusPlus::from_state([1, 2, 3, 4]).unwrap();
+        assert_eq!(
+            take(&mut pp),
+            [41943041, 58720359, 3588806011781223, 3591011842654386]
+        );
+        let mut p = Xoshiro256Plus::from_state([1, 2, 3, 4]).unwrap();
+        assert_eq!(
+            take(&mut p),
+            [5, 211106232532999, 211106635186183, 9223759065350669058]
+        );
+    }
+
+    #[test]
+    fn variants_share_jumps() {
+        let mut a = Xoshiro256Plus::from_state([1, 2, 3, 4]).unwrap();
+        let mut b = Xoshiro256PlusPlus::from_state([1, 2, 3, 4]).unwrap();
+        a.jump();
+        b.jump();
+        assert_eq!(a.state(), b.state());
+        a.long_jump();
+        b.long_jump();
+        assert_eq!(a.state(), b.state());
+    }
 }
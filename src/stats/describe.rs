@@ -0,0 +1,97 @@
+//! One-call summary of a sample.
+
+use super::OnlineMoments;
+use super::quantile::{QuantileMethod, sorted_quantile};
+
+/// Summary statistics of a sample, from [`describe`].
+///
+/// `variance` and `std_dev` are the unbiased sample forms; `skewness` and `kurtosis`
+/// (excess) the moment estimators of [`OnlineMoments`]. Quartiles and median interpolate
+/// linearly between order statistics ([`QuantileMethod::Linear`], the R and NumPy default).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub variance: f64,
+    pub std_dev: f64,
+    pub q1: f64,
+    pub median: f64,
+    pub q3: f64,
+    pub skewness: f64,
+    pub kurtosis: f64,
+}
+
+/// Summarizes `data`: one pass for the extremes and moments, then a sorted copy for the
+/// quartiles. Fields are NaN where undefined (all of them for an empty slice, the
+/// variance for a single value); a NaN in `data` propagates to the moments.
+///
+/// ```
+/// use probability_rs::stats::describe;
+///
+/// let s = describe(&[3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0]);
+/// assert_eq!((s.count, s.min, s.max), (8, 1.0, 9.0));
+/// assert_eq!((s.q1, s.median, s.q3), (1.75, 3.5, 5.25));
+/// assert_eq!(s.mean, 3.875);
+/// ```
+pub fn describe(data: &[f64]) -> Summary {
+    let mut m = OnlineMoments::new();
+    let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+    for &x in data {
+        m.push(x);
+        min = min.min(x);
+        max = max.max(x);
+    }
+    let mut sorted = data.to_vec();
+    sorted.sort_unstable_by(f64::total_cmp);
+    if data.is_empty() {
+        (min, max) = (f64::NAN, f64::NAN);
+    }
+    Summary {
+        count: data.len(),
+        min,
+        max,
+        mean: m.mean(),
+        variance: m.variance(),
+        std_dev: m.std_dev(),
+        q1: sorted_quantile(&sorted, 0.25, QuantileMethod::Linear),
+        median: sorted_quantile(&sorted, 0.5, QuantileMethod::Linear),
+        q3: sorted_quantile(&sorted, 0.75, QuantileMethod::Linear),
+        skewness: m.skewness(),
+        kurtosis: m.kurtosis(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_reference_values() {
+        // mpmath: type 7 quartiles, unbiased variance, moment skewness and excess kurtosis
+        let x = [2.5, -1.0, 7.25, 3.0, 0.5, 11.0, 4.0];
+        let s = describe(&x);
+        assert_eq!(s.count, 7);
+        assert_eq!((s.min, s.max), (-1.0, 11.0));
+        assert_eq!((s.q1, s.median, s.q3), (1.5, 3.0, 5.625));
+        assert!((s.mean - 3.892857142857143).abs() < 1e-15);
+        assert!((s.variance - 16.663690476190476).abs() < 1e-13);
+        assert!((s.std_dev - s.variance.sqrt()).abs() < 1e-15);
+        assert!((s.skewness - 0.6278034716895451).abs() < 1e-13);
+        assert!((s.kurtosis - -0.6272227285502881).abs() < 1e-13);
+    }
+
+    #[test]
+    fn empty_and_single_samples() {
+        let e = describe(&[]);
+        assert_eq!(e.count, 0);
+        assert!(e.min.is_nan() && e.mean.is_nan() && e.median.is_nan());
+        let one = describe(&[4.0]);
+        assert_eq!(
+            (one.min, one.median, one.q3, one.mean),
+            (4.0, 4.0, 4.0, 4.0)
+        );
+        assert!(one.variance.is_nan());
+    }
+}
@@ -0,0 +1,264 @@
+//! Binned counts of a sample or stream.
+
+use super::quantile::{QuantileMethod, quantiles};
+use crate::dist::{DistError, check_finite};
+
+/// How [`Histogram::auto`] chooses the number of bins.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BinRule {
+    /// Width `2·IQR/n^⅓`, robust to heavy tails; falls back to Sturges when the IQR is 0.
+    #[default]
+    FreedmanDiaconis,
+    /// `⌈log₂ n⌉ + 1` bins, adequate for small roughly normal samples.
+    Sturges,
+}
+
+/// Counts of values in contiguous bins `[e₀, e₁), [e₁, e₂), …, [eₖ₋₁, eₖ]` (the last bin
+/// is closed, as in NumPy), plus tallies of values below and above the edges. NaN is
+/// ignored.
+///
+/// [`edges`](Self::edges) with [`densities`](Self::densities) is the piecewise-constant
+/// density estimate of the data; the counts feed a chi-squared goodness-of-fit test.
+///
+/// ```
+/// use probability_rs::stats::Histogram;
+///
+/// let mut h = Histogram::new(0.0, 4.0, 4)?;
+/// h.extend([0.5, 1.0, 1.5, 3.9, 4.0, 7.0]);
+/// assert_eq!(h.counts(), &[1, 2, 0, 2]);
+/// assert_eq!((h.total(), h.overflow()), (5, 1));
+/// assert_eq!(h.densities()[1], 0.4);
+/// # Ok::<(), probability_rs::dist::DistError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    edges: Vec<f64>,
+    counts: Vec<u64>,
+    underflow: u64,
+    overflow: u64,
+    // Set for equal widths, so `push` can compute the bin instead of searching.
+    uniform: bool,
+}
+
+impl Histogram {
+    /// `bins` equal-width bins over `[low, high]`. Errors unless `low < high` are finite and
+    /// `bins >= 1`.
+    pub fn new(low: f64, high: f64, bins: usize) -> Result<Self, DistError> {
+        check_finite("low", low)?;
+        check_finite("high", high)?;
+        if low >= high {
+            return Err(DistError::InvalidRange { low, high });
+        }
+        if bins == 0 {
+            return Err(DistError::DomainError);
+        }
+        // Halved so that `high − low` cannot overflow; scaling by ½ and 2 is exact, so the
+        // edges are `low + i·width` whenever that width is finite.
+        let half_width = (0.5 * high - 0.5 * low) / bins as f64;
+        let mut edges: Vec<f64> = (0..bins)
+            .map(|i| 2.0 * (0.5 * low + i as f64 * half_width))
+            .collect();
+        edges.push(high);
+        Ok(Self::with_edges(edges, true))
+    }
+
+    /// Bins between consecutive `edges`. Errors unless there are at least two, all finite
+    /// and strictly increasing.
+    pub fn from_edges(edges: &[f64]) -> Result<Self, DistError> {
+        for &e in edges {
+            check_finite("edges", e)?;
+        }
+        if edges.len() < 2 {
+            return Err(DistError::DomainError);
+        }
+        if let Some(w) = edges.windows(2).find(|w| w[0] >= w[1]) {
+            return Err(DistError::InvalidRange {
+                low: w[0],
+                high: w[1],
+            });
+        }
+        Ok(Self::with_edges(edges.to_vec(), false))
+    }
+
+    /// Equal-width bins spanning the finite values of `data`, counted per `rule` (at most
+    /// one bin per value), with `data` already pushed. A constant sample gets the range
+    /// `x ± 0.5`, and an empty one a single bin on `[0, 1]`.
+    pub fn auto(data: &[f64], rule: BinRule) -> Self {
+        let mut finite: Vec<f64> = data.iter().copied().filter(|x| x.is_finite()).collect();
+        let n = finite.len();
+        let (lo, hi) = finite
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &x| {
+                (lo.min(x), hi.max(x))
+            });
+        let (lo, hi) = match n {
+            0 => (0.0, 1.0),
+            _ if lo == hi => (lo - 0.5, hi + 0.5),
+            _ => (lo, hi),
+        };
+        let sturges = || (n.max(1) as f64).log2().ceil() as usize + 1;
+        let bins = match rule {
+            BinRule::Sturges => sturges(),
+            BinRule::FreedmanDiaconis => {
+                let q = quantiles(&mut finite, &[0.25, 0.75], QuantileMethod::Linear);
+                let iqr = q[1] - q[0];
+                if n > 0 && iqr > 0.0 {
+                    let width = 2.0 * iqr / (n as f64).cbrt();
+                    ((hi - lo) / width).ceil() as usize
+                } else {
+                    sturges()
+                }
+            }
+        };
+        let mut h = Self::new(lo, hi, bins.clamp(1, n.max(1)))
+            .expect("finite increasing range and at least one bin");
+        h.extend(data.iter().copied());
+        h
+    }
+
+    fn with_edges(edges: Vec<f64>, uniform: bool) -> Self {
+        Self {
+            counts: vec![0; edges.len() - 1],
+            edges,
+            underflow: 0,
+            overflow: 0,
+            uniform,
+        }
+    }
+
+    /// Counts one value.
+    pub fn push(&mut self, x: f64) {
+        if x.is_nan() {
+            return;
+        }
+        let (lo, hi) = (self.edges[0], self.edges[self.edges.len() - 1]);
+        if x < lo {
+            self.underflow += 1;
+        } else if x > hi {
+            self.overflow += 1;
+        } else {
+            let k = self.counts.len();
+            let i = if self.uniform {
+                // Rounding can land one bin off near an edge; the edges decide.
+                let t = (0.5 * x - 0.5 * lo) / (0.5 * hi - 0.5 * lo);
+                let mut i = ((t * k as f64) as usize).min(k - 1);
+                if x < self.edges[i] {
+                    i -= 1;
+                } else if i + 1 < k && x >= self.edges[i + 1] {
+                    i += 1;
+                }
+                i
+            } else {
+                self.edges.partition_point(|&e| e <= x).min(k) - 1
+            };
+            self.counts[i] += 1;
+        }
+    }
+
+    /// The `bins() + 1` bin edges, increasing.
+    #[inline]
+    pub fn edges(&self) -> &[f64] {
+        &self.edges
+    }
+
+    /// The count in each bin.
+    #[inline]
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    #[inline]
+    pub fn bins(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Values counted in some bin (excluding under- and overflow).
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Values below the first edge.
+    #[inline]
+    pub fn underflow(&self) -> u64 {
+        self.underflow
+    }
+
+    /// Values above the last edge.
+    #[inline]
+    pub fn overflow(&self) -> u64 {
+        self.overflow
+    }
+
+    /// `count / (total · width)` per bin, so the bars integrate to 1 over the edges; NaN
+    /// when nothing fell in a bin.
+    pub fn densities(&self) -> Vec<f64> {
+        let total = self.total() as f64;
+        self.counts
+            .iter()
+            .zip(self.edges.windows(2))
+            .map(|(&c, w)| c as f64 / (total * (w[1] - w[0])))
+            .collect()
+    }
+}
+
+impl Extend<f64> for Histogram {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|x| self.push(x));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_and_custom_edges() {
+        let mut h = Histogram::new(-1.0, 1.0, 10).unwrap();
+        // Every value on an interior edge belongs to the bin above it.
+        h.extend((0..=10).map(|i| -1.0 + 0.2 * i as f64));
+        h.extend([-1.5, 2.0, f64::NAN]);
+        assert_eq!(h.counts(), &[1, 1, 1, 1, 1, 1, 1, 1, 1, 2]);
+        assert_eq!((h.underflow(), h.overflow(), h.total()), (1, 1, 11));
+        let area: f64 = h.densities().iter().map(|d| d * 0.2).sum();
+        assert!((area - 1.0).abs() < 1e-15);
+
+        let mut c = Histogram::from_edges(&[0.0, 1.0, 10.0, 100.0]).unwrap();
+        c.extend([0.0, 0.5, 1.0, 9.99, 10.0, 100.0, 100.1]);
+        assert_eq!(c.counts(), &[2, 2, 2]);
+        assert_eq!(c.overflow(), 1);
+        assert_eq!(c.densities(), [1.0 / 3.0, 1.0 / 27.0, 1.0 / 270.0]);
+
+        assert!(Histogram::new(1.0, 1.0, 3).is_err());
+        assert!(Histogram::new(0.0, 1.0, 0).is_err());
+        assert!(Histogram::from_edges(&[0.0, 2.0, 1.0]).is_err());
+        assert!(Histogram::from_edges(&[0.0]).is_err());
+    }
+
+    #[test]
+    fn range_wider_than_the_largest_float() {
+        let m = f64::MAX;
+        let mut h = Histogram::new(-m, m, 4).unwrap();
+        assert!(h.edges().windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(h.edges()[2], 0.0);
+        h.extend([-m, -1.0, 0.0, 0.75 * m, m]);
+        assert_eq!(h.counts(), &[1, 1, 1, 2]);
+        assert_eq!(Histogram::auto(&[-m, 0.0, m], BinRule::Sturges).total(), 3);
+    }
+
+    #[test]
+    fn automatic_binning_matches_numpy() {
+        // numpy.histogram_bin_edges(numpy.arange(1, 101), "fd" / "sturges")
+        let xs: Vec<f64> = (1..=100).map(f64::from).collect();
+        let fd = Histogram::auto(&xs, BinRule::FreedmanDiaconis);
+        let st = Histogram::auto(&xs, BinRule::Sturges);
+        assert_eq!((fd.bins(), st.bins()), (5, 8));
+        assert_eq!(fd.edges()[0], 1.0);
+        assert_eq!(*fd.edges().last().unwrap(), 100.0);
+        assert_eq!((fd.total(), st.total()), (100, 100));
+
+        let constant = Histogram::auto(&[2.0; 4], BinRule::FreedmanDiaconis);
+        assert_eq!(constant.edges()[0], 1.5);
+        assert_eq!(constant.total(), 4);
+        assert_eq!(Histogram::auto(&[], BinRule::Sturges).bins(), 1);
+    }
+}
@@ -0,0 +1,17 @@
+//! Descriptive statistics for samples and streams.
+//!
+//! Where [`dist`](crate::dist) describes a distribution from its parameters, this module
+//! summarizes observed data, e.g. the output of a sampler under test.
+
+pub mod describe;
+pub mod histogram;
+pub mod moments;
+pub mod p2;
+pub mod quantile;
+pub mod tests;
+
+pub use describe::{Summary, describe};
+pub use histogram::{BinRule, Histogram};
+pub use moments::OnlineMoments;
+pub use p2::{P2Quantile, P2Quantiles};
+pub use quantile::{QuantileMethod, quantile, quantiles};
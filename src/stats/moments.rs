@@ -0,0 +1,196 @@
+//! Streaming mean, variance, skewness and kurtosis.
+
+/// Running central moments of a stream, updated in O(1) per value without storing it.
+///
+/// Uses the Welford/Terriberry updates of the mean and the central sums `M2..M4`, so a
+/// large common offset (1e6 + noise) costs little precision, and [`merge`](Self::merge)
+/// combines accumulators (Chan et al., Pébay), so chunks can be reduced in parallel.
+///
+/// ```
+/// use probability_rs::stats::OnlineMoments;
+///
+/// let xs = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+/// let mut m = OnlineMoments::new();
+/// for x in xs {
+///     m.push(x);
+/// }
+/// assert_eq!(m.mean(), 5.0);
+/// assert!((m.population_variance() - 4.0).abs() < 1e-15);
+///
+/// let mut head: OnlineMoments = xs[..3].iter().copied().collect();
+/// head.merge(&xs[3..].iter().copied().collect());
+/// assert!((head.variance() - m.variance()).abs() < 1e-15);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct OnlineMoments {
+    n: u64,
+    mean: f64,
+    m2: f64,
+    m3: f64,
+    m4: f64,
+}
+
+impl OnlineMoments {
+    pub const fn new() -> Self {
+        Self {
+            n: 0,
+            mean: 0.0,
+            m2: 0.0,
+            m3: 0.0,
+            m4: 0.0,
+        }
+    }
+
+    /// Adds one value.
+    pub fn push(&mut self, x: f64) {
+        let n1 = self.n as f64;
+        self.n += 1;
+        let n = self.n as f64;
+        let delta = x - self.mean;
+        let dn = delta / n;
+        let dn2 = dn * dn;
+        let term = delta * dn * n1;
+        self.mean += dn;
+        self.m4 += term * dn2 * (n * n - 3.0 * n + 3.0) + 6.0 * dn2 * self.m2 - 4.0 * dn * self.m3;
+        self.m3 += term * dn * (n - 2.0) - 3.0 * dn * self.m2;
+        self.m2 += term;
+    }
+
+    /// Folds `other` in, as if its values had been pushed here.
+    pub fn merge(&mut self, other: &Self) {
+        if other.n == 0 {
+            return;
+        }
+        if self.n == 0 {
+            *self = *other;
+            return;
+        }
+        let (na, nb) = (self.n as f64, other.n as f64);
+        let n = na + nb;
+        let d = other.mean - self.mean;
+        let d2 = d * d;
+        let (a, b) = (*self, other);
+        self.n += other.n;
+        self.mean = a.mean + d * nb / n;
+        self.m2 = a.m2 + b.m2 + d2 * na * nb / n;
+        self.m3 = a.m3
+            + b.m3
+            + d2 * d * na * nb * (na - nb) / (n * n)
+            + 3.0 * d * (na * b.m2 - nb * a.m2) / n;
+        self.m4 = a.m4
+            + b.m4
+            + d2 * d2 * na * nb * (na * na - na * nb + nb * nb) / (n * n * n)
+            + 6.0 * d2 * (na * na * b.m2 + nb * nb * a.m2) / (n * n)
+            + 4.0 * d * (na * b.m3 - nb * a.m3) / n;
+    }
+
+    /// Number of values pushed.
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.n
+    }
+
+    /// Sample mean; NaN when empty.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        if self.n == 0 { f64::NAN } else { self.mean }
+    }
+
+    /// Unbiased sample variance `M2 / (n − 1)`; NaN below two values.
+    pub fn variance(&self) -> f64 {
+        if self.n < 2 {
+            return f64::NAN;
+        }
+        self.m2 / (self.n - 1) as f64
+    }
+
+    /// Population variance `M2 / n`; NaN when empty.
+    pub fn population_variance(&self) -> f64 {
+        self.m2 / self.n as f64
+    }
+
+    /// Square root of [`variance`](Self::variance).
+    pub fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// Sample skewness `g1 = √n M3 / M2^(3/2)` (the moment estimator, as SciPy's default).
+    pub fn skewness(&self) -> f64 {
+        (self.n as f64).sqrt() * self.m3 / self.m2.powf(1.5)
+    }
+
+    /// Sample excess kurtosis `g2 = n M4 / M2² − 3`, matching [`Moments::kurtosis`](crate::dist::Moments::kurtosis).
+    pub fn kurtosis(&self) -> f64 {
+        self.n as f64 * self.m4 / (self.m2 * self.m2) - 3.0
+    }
+}
+
+impl Extend<f64> for OnlineMoments {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|x| self.push(x));
+    }
+}
+
+impl FromIterator<f64> for OnlineMoments {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut m = Self::new();
+        m.extend(iter);
+        m
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Distribution;
+    use crate::dist::Moments;
+    use crate::dist::gamma::Gamma;
+    use crate::rng::SplitMix64;
+
+    fn two_pass(xs: &[f64]) -> (f64, f64, f64, f64) {
+        let n = xs.len() as f64;
+        let mean = xs.iter().sum::<f64>() / n;
+        let c = |k: i32| xs.iter().map(|x| (x - mean).powi(k)).sum::<f64>() / n;
+        let (m2, m3, m4) = (c(2), c(3), c(4));
+        (mean, m2, m3 / m2.powf(1.5), m4 / (m2 * m2) - 3.0)
+    }
+
+    #[test]
+    fn matches_two_pass_and_merges() {
+        let mut rng = SplitMix64::seed_from_u64(4);
+        let d = Gamma::new(2.0, 1.0).unwrap();
+        // A large offset would wreck the naive Σx² formulas.
+        let xs: Vec<f64> = (0..10_000).map(|_| 1e6 + d.sample(&mut rng)).collect();
+        let all: OnlineMoments = xs.iter().copied().collect();
+        let (mean, var, skew, kurt) = two_pass(&xs);
+        assert_eq!(all.count(), 10_000);
+        assert!((all.mean() / mean - 1.0).abs() < 1e-14);
+        assert!((all.population_variance() / var - 1.0).abs() < 1e-9);
+        assert!((all.skewness() / skew - 1.0).abs() < 1e-6);
+        assert!((all.kurtosis() / kurt - 1.0).abs() < 1e-6);
+        // Gamma(2): skewness √2, excess kurtosis 3.
+        assert!((all.skewness() - d.skewness()).abs() < 0.15);
+        assert!((all.kurtosis() - d.kurtosis()).abs() < 1.0);
+
+        let mut merged = OnlineMoments::new();
+        for chunk in xs.chunks(777) {
+            merged.merge(&chunk.iter().copied().collect());
+        }
+        assert_eq!(merged.count(), all.count());
+        assert!((merged.mean() / all.mean() - 1.0).abs() < 1e-15);
+        assert!((merged.variance() / all.variance() - 1.0).abs() < 1e-9);
+        assert!((merged.skewness() / all.skewness() - 1.0).abs() < 1e-6);
+        assert!((merged.kurtosis() / all.kurtosis() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn empty_and_single_values() {
+        let mut m = OnlineMoments::new();
+        assert!(m.mean().is_nan() && m.variance().is_nan());
+        m.push(3.0);
+        assert_eq!((m.mean(), m.population_variance()), (3.0, 0.0));
+        assert!(m.variance().is_nan());
+        m.merge(&OnlineMoments::new());
+        assert_eq!(m.count(), 1);
+    }
+}
@@ -0,0 +1,248 @@
+//! Streaming quantile estimation with the P² algorithm.
+
+use super::quantile::{QuantileMethod, sorted_quantile};
+
+/// Several quantiles of a stream in O(1) memory, by the extended P² algorithm
+/// (Jain & Chlamtac 1985; Raatikainen 1987): `2m + 3` markers track the minimum, the
+/// maximum, each requested quantile and the midpoints between them, and are nudged along
+/// a piecewise-parabolic fit of the empirical CDF as values arrive.
+///
+/// Until there are enough values to place every marker the estimates are exact
+/// ([`QuantileMethod::Linear`]); after that they are approximations whose error shrinks as
+/// the stream grows, typically to a small fraction of the local spread for smooth data.
+///
+/// ```
+/// use probability_rs::stats::P2Quantiles;
+///
+/// let mut q = P2Quantiles::new(&[0.5, 0.9, 0.99]);
+/// for i in 0..10_000 {
+///     q.push(((i * 7919) % 10_000) as f64);
+/// }
+/// let est = q.estimates();
+/// assert!((est[0] - 5_000.0).abs() < 50.0);
+/// assert!((est[2] - 9_900.0).abs() < 50.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct P2Quantiles {
+    probs: Vec<f64>,
+    // Marker heights, actual and desired positions (0-based), and desired increments.
+    heights: Vec<f64>,
+    pos: Vec<f64>,
+    desired: Vec<f64>,
+    increments: Vec<f64>,
+    n: u64,
+}
+
+impl P2Quantiles {
+    /// Tracks the quantiles at `probs`. Panics unless `probs` is non-empty and strictly
+    /// increasing within `(0, 1)`.
+    pub fn new(probs: &[f64]) -> Self {
+        assert!(
+            !probs.is_empty()
+                && probs.iter().all(|p| *p > 0.0 && *p < 1.0)
+                && probs.windows(2).all(|w| w[0] < w[1]),
+            "probabilities must be strictly increasing in (0,1)"
+        );
+        let mut increments = vec![0.0];
+        let mut prev = 0.0;
+        for &p in probs {
+            increments.extend([0.5 * (prev + p), p]);
+            prev = p;
+        }
+        increments.extend([0.5 * (prev + 1.0), 1.0]);
+        let k = increments.len();
+        Self {
+            probs: probs.to_vec(),
+            heights: Vec::with_capacity(k),
+            pos: (0..k).map(|i| i as f64).collect(),
+            desired: increments.iter().map(|d| (k - 1) as f64 * d).collect(),
+            increments,
+            n: 0,
+        }
+    }
+
+    /// Adds one value. NaN is ignored.
+    pub fn push(&mut self, x: f64) {
+        if x.is_nan() {
+            return;
+        }
+        self.n += 1;
+        let k = self.increments.len();
+        if self.heights.len() < k {
+            self.heights.push(x);
+            if self.heights.len() == k {
+                self.heights.sort_unstable_by(f64::total_cmp);
+            }
+            return;
+        }
+
+        let q = &mut self.heights;
+        let cell = if x < q[0] {
+            q[0] = x;
+            0
+        } else if x >= q[k - 1] {
+            q[k - 1] = x;
+            k - 2
+        } else {
+            q.partition_point(|&h| h <= x) - 1
+        };
+        for n in &mut self.pos[cell + 1..] {
+            *n += 1.0;
+        }
+        for (d, inc) in self.desired.iter_mut().zip(&self.increments) {
+            *d += inc;
+        }
+
+        for i in 1..k - 1 {
+            let d = self.desired[i] - self.pos[i];
+            let n = &self.pos;
+            if (d >= 1.0 && n[i + 1] - n[i] > 1.0) || (d <= -1.0 && n[i - 1] - n[i] < -1.0) {
+                let s = d.signum();
+                let q = &self.heights;
+                let parabolic = q[i]
+                    + s / (n[i + 1] - n[i - 1])
+                        * ((n[i] - n[i - 1] + s) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                            + (n[i + 1] - n[i] - s) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]));
+                let new = if q[i - 1] < parabolic && parabolic < q[i + 1] {
+                    parabolic
+                } else {
+                    let j = if s > 0.0 { i + 1 } else { i - 1 };
+                    q[i] + s * (q[j] - q[i]) / (n[j] - n[i])
+                };
+                self.heights[i] = new;
+                self.pos[i] += s;
+            }
+        }
+    }
+
+    /// Number of values pushed.
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.n
+    }
+
+    /// The probabilities being tracked.
+    #[inline]
+    pub fn probabilities(&self) -> &[f64] {
+        &self.probs
+    }
+
+    /// The current estimate for each tracked probability; NaN before any value.
+    pub fn estimates(&self) -> Vec<f64> {
+        if self.heights.len() < self.increments.len() {
+            let mut seen = self.heights.clone();
+            seen.sort_unstable_by(f64::total_cmp);
+            return self
+                .probs
+                .iter()
+                .map(|&p| sorted_quantile(&seen, p, QuantileMethod::Linear))
+                .collect();
+        }
+        (0..self.probs.len())
+            .map(|j| self.heights[2 * j + 2])
+            .collect()
+    }
+}
+
+impl Extend<f64> for P2Quantiles {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|x| self.push(x));
+    }
+}
+
+/// A single quantile of a stream in O(1) memory: five P² markers (Jain & Chlamtac 1985).
+/// See [`P2Quantiles`] for several at once.
+///
+/// ```
+/// use probability_rs::stats::P2Quantile;
+///
+/// let mut median = P2Quantile::new(0.5);
+/// median.extend((1..=1001).map(f64::from));
+/// assert!((median.estimate() - 501.0).abs() < 1.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct P2Quantile(P2Quantiles);
+
+impl P2Quantile {
+    /// Tracks the `p`-quantile. Panics unless `p` is in `(0, 1)`.
+    pub fn new(p: f64) -> Self {
+        assert!(p > 0.0 && p < 1.0, "p must be in (0,1)");
+        Self(P2Quantiles::new(&[p]))
+    }
+
+    /// Adds one value. NaN is ignored.
+    #[inline]
+    pub fn push(&mut self, x: f64) {
+        self.0.push(x);
+    }
+
+    /// The current estimate; NaN before any value.
+    pub fn estimate(&self) -> f64 {
+        self.0.estimates()[0]
+    }
+
+    /// Number of values pushed.
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.0.count()
+    }
+
+    /// The probability being tracked.
+    #[inline]
+    pub fn p(&self) -> f64 {
+        self.0.probs[0]
+    }
+}
+
+impl Extend<f64> for P2Quantile {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        self.0.extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Distribution;
+    use crate::dist::exponential::Exponential;
+    use crate::rng::SplitMix64;
+    use crate::stats::quantiles;
+
+    #[test]
+    fn reproduces_the_jain_chlamtac_example() {
+        // The worked example of the 1985 paper: marker heights after 20 values.
+        let xs = [
+            0.02, 0.15, 0.74, 3.39, 0.83, 22.37, 10.15, 15.43, 38.62, 15.92, 34.60, 10.28, 1.47,
+            0.40, 0.05, 11.39, 0.27, 0.42, 0.09, 11.37,
+        ];
+        let mut q = P2Quantile::new(0.5);
+        q.extend(xs);
+        let want = [0.02, 0.49, 4.44, 17.20, 38.62];
+        for (h, w) in q.0.heights.iter().zip(want) {
+            assert!((h - w).abs() < 5e-3, "{:?}", q.0.heights);
+        }
+        assert_eq!(q.count(), 20);
+    }
+
+    #[test]
+    fn tracks_a_skewed_stream() {
+        let probs = [0.1, 0.5, 0.9, 0.99];
+        let mut q = P2Quantiles::new(&probs);
+        let mut rng = SplitMix64::seed_from_u64(7);
+        let d = Exponential::new(1.0).unwrap();
+        let mut xs: Vec<f64> = (0..100_000).map(|_| d.sample(&mut rng)).collect();
+        q.extend(xs.iter().copied());
+        let exact = quantiles(&mut xs, &probs, QuantileMethod::Linear);
+        for (est, want) in q.estimates().into_iter().zip(exact) {
+            assert!((est - want).abs() < 0.01 * want, "{est} vs {want}");
+        }
+    }
+
+    #[test]
+    fn exact_before_the_markers_fill() {
+        let mut q = P2Quantile::new(0.5);
+        assert!(q.estimate().is_nan());
+        q.extend([3.0, f64::NAN, 1.0, 2.0]);
+        assert_eq!((q.count(), q.estimate(), q.p()), (3, 2.0, 0.5));
+    }
+}
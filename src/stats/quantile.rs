@@ -0,0 +1,203 @@
+//! Sample quantiles with the Hyndman–Fan interpolation conventions.
+
+/// How a sample quantile picks or interpolates order statistics: the nine definitions of
+/// Hyndman & Fan (1996), numbered as R's `quantile(type = …)` and named as NumPy's
+/// `quantile(method = …)`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuantileMethod {
+    /// Type 1: the inverse of the empirical CDF.
+    InvertedCdf,
+    /// Type 2: as type 1, averaging at the jumps.
+    AveragedInvertedCdf,
+    /// Type 3: the nearest order statistic, ties to the even one (SAS).
+    ClosestObservation,
+    /// Type 4: linear interpolation of the empirical CDF.
+    InterpolatedInvertedCdf,
+    /// Type 5: piecewise linear with knots at `(k − ½)/n`.
+    Hazen,
+    /// Type 6: knots at `k/(n + 1)` (Minitab, SPSS).
+    Weibull,
+    /// Type 7: knots at `(k − 1)/(n − 1)`; the R and NumPy default.
+    #[default]
+    Linear,
+    /// Type 8: approximately median-unbiased whatever the distribution; Hyndman & Fan's pick.
+    MedianUnbiased,
+    /// Type 9: approximately unbiased for normal data.
+    NormalUnbiased,
+}
+
+impl QuantileMethod {
+    /// The 0-based index of the lower order statistic and the weight of the next one.
+    fn position(self, n: usize, p: f64) -> (usize, f64) {
+        use QuantileMethod::*;
+        let nf = n as f64;
+        let np = nf * p;
+        // As R, the discontinuous types treat n·p within a few ulps of an integer as that
+        // integer, so 100 × 0.07 (which rounds to 7.000000000000001) picks x₇.
+        let fuzz = 4.0 * f64::EPSILON * np.max(1.0);
+        // 1-based index j with weight g on x[j + 1], clamped to the sample.
+        let (j, g) = match self {
+            InvertedCdf | AveragedInvertedCdf => {
+                let j = (np + fuzz).floor();
+                if np > j + fuzz {
+                    (j + 1.0, 0.0)
+                } else if self == AveragedInvertedCdf {
+                    (j, 0.5)
+                } else {
+                    (j, 0.0)
+                }
+            }
+            ClosestObservation => {
+                let h = np - 0.5;
+                let j = (h + fuzz).floor();
+                let even = j % 2.0 == 0.0;
+                if (h - j).abs() <= fuzz && even {
+                    (j, 0.0)
+                } else {
+                    (j + 1.0, 0.0)
+                }
+            }
+            _ => {
+                let m = match self {
+                    InterpolatedInvertedCdf => 0.0,
+                    Hazen => 0.5,
+                    Weibull => p,
+                    Linear => 1.0 - p,
+                    MedianUnbiased => (p + 1.0) / 3.0,
+                    _ => 0.25 * p + 0.375,
+                };
+                let h = np + m;
+                (h.floor(), h - h.floor())
+            }
+        };
+        if j < 1.0 {
+            (0, 0.0)
+        } else if j >= nf {
+            (n - 1, 0.0)
+        } else {
+            (j as usize - 1, g)
+        }
+    }
+}
+
+// x[lo] + w (x[lo + 1] − x[lo]), without touching x[lo + 1] when w = 0.
+fn interpolate(lo: f64, hi: impl FnOnce() -> f64, w: f64) -> f64 {
+    if w == 0.0 { lo } else { lo + w * (hi() - lo) }
+}
+
+/// The `p`-quantile of already sorted data; NaN when it is empty.
+pub(crate) fn sorted_quantile(sorted: &[f64], p: f64, method: QuantileMethod) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let (lo, w) = method.position(sorted.len(), p);
+    interpolate(sorted[lo], || sorted[lo + 1], w)
+}
+
+/// The `p`-quantile of `data` under `method`, in expected O(n): selects the needed order
+/// statistics in place, so `data` is left partially reordered. NaN for empty data; NaNs
+/// in `data` sort above +∞. Panics unless `p` is in `[0,1]`.
+///
+/// ```
+/// use probability_rs::stats::{QuantileMethod, quantile};
+///
+/// let mut x = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+/// // R: quantile(1:10, 0.1, type = 7) and type = 8
+/// assert_eq!(quantile(&mut x, 0.1, QuantileMethod::Linear), 1.9);
+/// assert!((quantile(&mut x, 0.1, QuantileMethod::MedianUnbiased) - 4.1 / 3.0).abs() < 1e-15);
+/// ```
+pub fn quantile(data: &mut [f64], p: f64, method: QuantileMethod) -> f64 {
+    assert!((0.0..=1.0).contains(&p), "p must be in [0,1]");
+    if data.is_empty() {
+        return f64::NAN;
+    }
+    let (lo, w) = method.position(data.len(), p);
+    let (_, &mut x_lo, above) = data.select_nth_unstable_by(lo, f64::total_cmp);
+    let above = &*above;
+    interpolate(
+        x_lo,
+        || above.iter().copied().min_by(f64::total_cmp).unwrap_or(x_lo),
+        w,
+    )
+}
+
+/// Quantiles at each of `ps`, sharing one sort of `data` (which is left sorted). Panics
+/// unless every p is in `[0,1]`.
+pub fn quantiles(data: &mut [f64], ps: &[f64], method: QuantileMethod) -> Vec<f64> {
+    assert!(
+        ps.iter().all(|p| (0.0..=1.0).contains(p)),
+        "p must be in [0,1]"
+    );
+    data.sort_unstable_by(f64::total_cmp);
+    ps.iter()
+        .map(|&p| sorted_quantile(data, p, method))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use QuantileMethod::*;
+
+    const ALL: [QuantileMethod; 9] = [
+        InvertedCdf,
+        AveragedInvertedCdf,
+        ClosestObservation,
+        InterpolatedInvertedCdf,
+        Hazen,
+        Weibull,
+        Linear,
+        MedianUnbiased,
+        NormalUnbiased,
+    ];
+
+    #[test]
+    fn hyndman_fan_types_match_r() {
+        // R: sapply(1:9, function(t) quantile(1:10, p, type = t))
+        let reference: [(f64, [f64; 9]); 3] = [
+            (0.1, [1.0, 1.5, 1.0, 1.0, 1.5, 1.1, 1.9, 4.1 / 3.0, 1.4]),
+            (0.5, [5.0, 5.5, 5.0, 5.0, 5.5, 5.5, 5.5, 5.5, 5.5]),
+            (0.95, [10.0, 10.0, 10.0, 9.5, 10.0, 10.0, 9.55, 10.0, 10.0]),
+        ];
+        let x: Vec<f64> = (1..=10).rev().map(f64::from).collect();
+        for (p, want) in reference {
+            for (method, want) in ALL.into_iter().zip(want) {
+                let mut data = x.clone();
+                let got = quantile(&mut data, p, method);
+                assert!((got - want).abs() < 1e-14, "{method:?} at {p}: {got}");
+                let mut data = x.clone();
+                assert_eq!(
+                    quantiles(&mut data, &[0.0, p, 1.0], method),
+                    [1.0, got, 10.0]
+                );
+            }
+        }
+        // Type 3 rounds np half to even: 2.5 picks x[2] and 3.5 picks x[4].
+        let mut y = [10.0, 20.0, 30.0, 40.0, 50.0];
+        assert_eq!(quantile(&mut y, 0.5, ClosestObservation), 20.0);
+        assert_eq!(quantile(&mut y, 0.7, ClosestObservation), 40.0);
+        // R's fuzz: n·p = 7.000000000000001 and 28.999999999999996 sit on the jumps, and
+        // n·p − ½ = 13.999999999999998 is a tie that goes to the even x₁₄.
+        let z: Vec<f64> = (1..=100).map(f64::from).collect();
+        assert_eq!(quantile(&mut z.clone(), 0.07, InvertedCdf), 7.0);
+        assert_eq!(quantile(&mut z.clone(), 0.29, AveragedInvertedCdf), 29.5);
+        assert_eq!(quantile(&mut z.clone(), 0.145, ClosestObservation), 14.0);
+    }
+
+    #[test]
+    fn degenerate_samples() {
+        assert!(quantile(&mut [], 0.5, Linear).is_nan());
+        for method in ALL {
+            assert_eq!(quantile(&mut [3.0], 0.3, method), 3.0);
+        }
+        let mut with_inf = [1.0, f64::INFINITY];
+        assert_eq!(quantile(&mut with_inf, 0.0, Linear), 1.0);
+        assert_eq!(quantile(&mut with_inf, 1.0, Linear), f64::INFINITY);
+    }
+
+    #[test]
+    #[should_panic(expected = "p must be in [0,1]")]
+    fn rejects_p_outside_unit_interval() {
+        quantile(&mut [1.0, 2.0], 1.5, Linear);
+    }
+}
@@ -0,0 +1,473 @@
+//! Hypothesis tests: goodness of fit of samples to distributions, and independence in
+//! contingency tables.
+
+use crate::dist::{Continuous, ContinuousDyn, DiscreteDyn, DistError};
+use crate::num::gamma_q;
+
+/// Outcome of a [`ks_test`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KsResult {
+    /// `Dₙ = supₓ |Fₙ(x) − F(x)|`.
+    pub statistic: f64,
+    /// `P(Dₙ ≥ statistic)` if the data were drawn from the distribution.
+    pub p_value: f64,
+}
+
+// Largest sample for the exact p-value; beyond it the asymptotic form is accurate to about
+// 1e-3 and the exact matrix power gets expensive. R's `ks.test` switches at the same size.
+const KS_EXACT_MAX_N: usize = 100;
+
+/// One-sample two-sided Kolmogorov–Smirnov test of `data` against `dist`.
+///
+/// The p-value is exact for `n <= 100` (Marsaglia, Tsang & Wang 2003) and from the
+/// asymptotic Kolmogorov distribution of `√n·Dₙ` above, as R's `ks.test` does by default.
+/// Ties in `data` or a discontinuous `cdf` invalidate the p-value. NaN for empty data.
+///
+/// ```
+/// use probability_rs::{Distribution, dist::normal::Normal, rng::SplitMix64};
+/// use probability_rs::stats::tests::ks_test;
+///
+/// let n = Normal::standard();
+/// let mut rng = SplitMix64::seed_from_u64(1);
+/// let xs: Vec<f64> = (0..500).map(|_| n.sample(&mut rng)).collect();
+/// assert!(ks_test(&xs, &n).p_value > 0.01);
+/// assert!(ks_test(&xs, &Normal::new(0.3, 1.0)?).p_value < 1e-6);
+/// # Ok::<(), probability_rs::dist::DistError>(())
+/// ```
+pub fn ks_test<D: Continuous + ?Sized>(data: &[f64], dist: &D) -> KsResult {
+    let n = data.len();
+    if n == 0 {
+        return KsResult {
+            statistic: f64::NAN,
+            p_value: f64::NAN,
+        };
+    }
+    let mut sorted = data.to_vec();
+    sorted.sort_unstable_by(f64::total_cmp);
+    let nf = n as f64;
+    let statistic = sorted
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| {
+            let f = dist.cdf(x);
+            (f - i as f64 / nf).max((i + 1) as f64 / nf - f)
+        })
+        .fold(0.0, f64::max);
+    let p_value = if n <= KS_EXACT_MAX_N {
+        1.0 - kolmogorov_cdf_exact(n, statistic)
+    } else {
+        kolmogorov_sf(nf.sqrt() * statistic)
+    };
+    KsResult {
+        statistic,
+        p_value: p_value.clamp(0.0, 1.0),
+    }
+}
+
+/// `P(Dₙ < d)` by the matrix method of Marsaglia, Tsang & Wang (2003): entry `(k, k)` of
+/// `Hⁿ·n!/nⁿ` for a `(2k − 1)²` matrix `H`, `k = ⌊nd⌋ + 1`, with the power kept in range
+/// by a decimal exponent.
+fn kolmogorov_cdf_exact(n: usize, d: f64) -> f64 {
+    let nf = n as f64;
+    if d <= 0.5 / nf {
+        return 0.0;
+    }
+    if d >= 1.0 {
+        return 1.0;
+    }
+    let k = (nf * d) as usize + 1;
+    let m = 2 * k - 1;
+    let h = k as f64 - nf * d;
+    let mut hm = vec![0.0; m * m];
+    for i in 0..m {
+        for j in 0..m {
+            if i + 1 >= j {
+                hm[i * m + j] = 1.0;
+            }
+        }
+    }
+    for i in 0..m {
+        hm[i * m] -= h.powi(i as i32 + 1);
+        hm[(m - 1) * m + i] -= h.powi((m - i) as i32);
+    }
+    if 2.0 * h > 1.0 {
+        hm[(m - 1) * m] += (2.0 * h - 1.0).powi(m as i32);
+    }
+    for i in 0..m {
+        for j in 0..=(i + 1).min(m - 1) {
+            for g in 1..=(i + 1 - j) {
+                hm[i * m + j] /= g as f64;
+            }
+        }
+    }
+
+    let (q, mut e) = matrix_power(&hm, m, n);
+    let mut s = q[(k - 1) * m + k - 1];
+    for i in 1..=n {
+        s *= i as f64 / nf;
+        if s < 1e-140 {
+            s *= 1e140;
+            e -= 140;
+        }
+    }
+    s * 10f64.powi(e)
+}
+
+fn matrix_product(a: &[f64], b: &[f64], m: usize) -> Vec<f64> {
+    let mut c = vec![0.0; m * m];
+    for i in 0..m {
+        for l in 0..m {
+            let a_il = a[i * m + l];
+            if a_il != 0.0 {
+                for j in 0..m {
+                    c[i * m + j] += a_il * b[l * m + j];
+                }
+            }
+        }
+    }
+    c
+}
+
+// `a^n` as a matrix and a power of ten, rescaled whenever the centre entry passes 1e140.
+fn matrix_power(a: &[f64], m: usize, n: usize) -> (Vec<f64>, i32) {
+    if n == 1 {
+        return (a.to_vec(), 0);
+    }
+    let (half, e_half) = matrix_power(a, m, n / 2);
+    let mut q = matrix_product(&half, &half, m);
+    let mut e = 2 * e_half;
+    if n % 2 == 1 {
+        q = matrix_product(a, &q, m);
+    }
+    if q[(m / 2) * m + m / 2] > 1e140 {
+        q.iter_mut().for_each(|v| *v *= 1e-140);
+        e += 140;
+    }
+    (q, e)
+}
+
+/// `P(K > x)` for the Kolmogorov distribution, the limit of `√n·Dₙ`.
+fn kolmogorov_sf(x: f64) -> f64 {
+    use std::f64::consts::PI;
+    if x <= 0.0 {
+        return 1.0;
+    }
+    if x < 1.0 {
+        // Jacobi-transformed series, fast for small x: P(K ≤ x) = √(2π)/x Σ e^{−(2k−1)²π²/8x²}.
+        let t = -PI * PI / (8.0 * x * x);
+        let cdf: f64 = (1..=6)
+            .map(|k| ((2 * k - 1) as f64).powi(2) * t)
+            .map(f64::exp)
+            .sum::<f64>()
+            * (2.0 * PI).sqrt()
+            / x;
+        1.0 - cdf
+    } else {
+        // 2 Σ (−1)^{k−1} e^{−2k²x²}, alternating and fast for x ≥ 1.
+        2.0 * (1..=6)
+            .map(|k| {
+                let sign = if k % 2 == 1 { 1.0 } else { -1.0 };
+                sign * (-2.0 * (k * k) as f64 * x * x).exp()
+            })
+            .sum::<f64>()
+    }
+}
+
+/// Outcome of a chi-squared test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Chi2Result {
+    /// `Σ (O − E)²/E`.
+    pub statistic: f64,
+    /// Degrees of freedom of the reference chi-squared law.
+    pub dof: usize,
+    /// `P(χ²_dof ≥ statistic)`.
+    pub p_value: f64,
+}
+
+impl Chi2Result {
+    fn new(statistic: f64, dof: usize) -> Self {
+        // Upper tail of `ChiSquared::cdf` computed directly, so small p-values keep their
+        // relative accuracy instead of cancelling in 1 − cdf.
+        let p_value = gamma_q(0.5 * dof as f64, 0.5 * statistic);
+        Self {
+            statistic,
+            dof,
+            p_value,
+        }
+    }
+}
+
+/// What [`chi2_gof`] compares the observed counts with. Expectations are rescaled to the
+/// observed total, so only their proportions matter.
+#[derive(Clone, Copy)]
+pub enum Expected<'a> {
+    /// Expected frequency of each bin, in any units.
+    Counts(&'a [f64]),
+    /// A discrete law over one bin per value from `first` on: the first bin also collects
+    /// everything below `first` and the last everything above, so every bin holds mass.
+    Discrete {
+        dist: &'a dyn DiscreteDyn,
+        first: i64,
+    },
+    /// A continuous law binned by `edges` (one more than the bins, e.g.
+    /// [`Histogram::edges`](super::Histogram::edges)), conditioned on landing inside them.
+    Continuous {
+        dist: &'a dyn ContinuousDyn,
+        edges: &'a [f64],
+    },
+}
+
+/// Pearson's chi-squared goodness-of-fit test of binned `observed` counts against
+/// `expected`, with `k − 1 − ddof` degrees of freedom for `k` bins (`ddof` = parameters
+/// estimated from the same data). Errors with [`DistError::DomainError`] when the bin
+/// counts disagree, no value was observed, an expectation is negative or not finite, or
+/// no degree of freedom is left.
+///
+/// The chi-squared approximation wants every expected count to be about 5 or more; merge
+/// sparse tail bins (e.g. with a later `first`) when it is not.
+///
+/// ```
+/// use probability_rs::{Distribution, dist::poisson::Poisson, rng::SplitMix64};
+/// use probability_rs::stats::tests::{Expected, chi2_gof};
+///
+/// let d = Poisson::new(3.0)?;
+/// let mut rng = SplitMix64::seed_from_u64(5);
+/// let mut observed = [0u64; 9]; // ≤ 0, 1, …, 7, ≥ 8
+/// for _ in 0..5000 {
+///     observed[d.sample(&mut rng).min(8) as usize] += 1;
+/// }
+/// let r = chi2_gof(&observed, Expected::Discrete { dist: &d, first: 0 }, 0)?;
+/// assert_eq!(r.dof, 8);
+/// assert!(r.p_value > 0.01);
+/// # Ok::<(), probability_rs::dist::DistError>(())
+/// ```
+pub fn chi2_gof(
+    observed: &[u64],
+    expected: Expected<'_>,
+    ddof: usize,
+) -> Result<Chi2Result, DistError> {
+    let k = observed.len();
+    let weights: Vec<f64> = match expected {
+        Expected::Counts(e) => e.to_vec(),
+        Expected::Discrete { dist, first } => {
+            let mut prev = 0.0;
+            (0..k as i64)
+                .map(|i| {
+                    if i + 1 == k as i64 {
+                        dist.sf_dyn(first + i - 1)
+                    } else {
+                        let f = dist.cdf_dyn(first + i);
+                        let w = f - prev;
+                        prev = f;
+                        w
+                    }
+                })
+                .collect()
+        }
+        Expected::Continuous { dist, edges } => {
+            if edges.len() != k + 1 {
+                return Err(DistError::DomainError);
+            }
+            edges
+                .windows(2)
+                .map(|w| dist.cdf_dyn(w[1]) - dist.cdf_dyn(w[0]))
+                .collect()
+        }
+    };
+    let total: u64 = observed.iter().sum();
+    let mass: f64 = weights.iter().sum();
+    if weights.len() != k
+        || total == 0
+        || k < ddof + 2
+        || weights.iter().any(|w| !(w.is_finite() && *w >= 0.0))
+        || mass <= 0.0
+    {
+        return Err(DistError::DomainError);
+    }
+    let scale = total as f64 / mass;
+    let statistic = observed
+        .iter()
+        .zip(&weights)
+        .map(|(&o, &w)| {
+            let e = w * scale;
+            let d = o as f64 - e;
+            if o == 0 && e == 0.0 { 0.0 } else { d * d / e }
+        })
+        .sum();
+    Ok(Chi2Result::new(statistic, k - 1 - ddof))
+}
+
+/// Pearson's chi-squared test of independence of the rows and columns of a contingency
+/// `table`, with `(r − 1)(c − 1)` degrees of freedom and no continuity correction (R's
+/// `chisq.test(correct = FALSE)`). Errors with [`DistError::DomainError`] unless the table
+/// has at least two rows and two columns, all of one length, and no empty row or column.
+///
+/// ```
+/// use probability_rs::stats::tests::chi2_independence;
+///
+/// let r = chi2_independence(&[[10, 10, 20], [20, 20, 20]])?;
+/// assert_eq!(r.dof, 2);
+/// assert!((r.statistic - 25.0 / 9.0).abs() < 1e-14);
+/// # Ok::<(), probability_rs::dist::DistError>(())
+/// ```
+pub fn chi2_independence<R: AsRef<[u64]>>(table: &[R]) -> Result<Chi2Result, DistError> {
+    let cols = table.first().map_or(0, |r| r.as_ref().len());
+    if table.len() < 2 || cols < 2 || table.iter().any(|r| r.as_ref().len() != cols) {
+        return Err(DistError::DomainError);
+    }
+    let row_sums: Vec<u64> = table.iter().map(|r| r.as_ref().iter().sum()).collect();
+    let col_sums: Vec<u64> = (0..cols)
+        .map(|j| table.iter().map(|r| r.as_ref()[j]).sum())
+        .collect();
+    if row_sums.contains(&0) || col_sums.contains(&0) {
+        return Err(DistError::DomainError);
+    }
+    let n = row_sums.iter().sum::<u64>() as f64;
+    let mut statistic = 0.0;
+    for (row, &rs) in table.iter().zip(&row_sums) {
+        for (&o, &cs) in row.as_ref().iter().zip(&col_sums) {
+            let e = rs as f64 * cs as f64 / n;
+            let d = o as f64 - e;
+            statistic += d * d / e;
+        }
+    }
+    Ok(Chi2Result::new(statistic, (table.len() - 1) * (cols - 1)))
+}
+
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod tests {
+    use super::*;
+    use crate::Distribution;
+    use crate::dist::binomial::Binomial;
+    use crate::dist::exponential::Exponential;
+    use crate::dist::normal::Normal;
+    use crate::dist::uniform::Uniform;
+    use crate::rng::SplitMix64;
+    use crate::stats::Histogram;
+
+    #[test]
+    fn exact_distribution_of_dn() {
+        // Marsaglia, Tsang & Wang (2003): K(10, 0.274).
+        assert!((kolmogorov_cdf_exact(10, 0.274) - 0.6284796154565043).abs() < 1e-13);
+        // n!(2d − 1/n)ⁿ on (1/2n, 1/n].
+        let (n, d) = (5usize, 0.15);
+        let want = 120.0 * (2.0 * d - 0.2f64).powi(5);
+        assert!((kolmogorov_cdf_exact(n, d) - want).abs() < 1e-14);
+        // For d ≥ 1/2 the tail is twice the one-sided one; mpmath, Birnbaum–Tucker:
+        // 2*d*fsum(binomial(n,j)*(1-d-j/n)**(n-j)*(d+j/n)**(j-1) for j in range(floor(n*(1-d))+1))
+        for (n, d, tail) in [
+            (3usize, 0.6, 0.144),
+            (20, 0.55, 3.2861970662280172e-6),
+            (100, 0.5, 1.2131434371817858e-23),
+        ] {
+            let got = 1.0 - kolmogorov_cdf_exact(n, d);
+            assert!((got - tail).abs() < 1e-13, "n = {n}: {got}");
+        }
+        // Large n approaches the limit law.
+        let exact = 1.0 - kolmogorov_cdf_exact(100, 0.12);
+        assert!((exact - kolmogorov_sf(1.2)).abs() < 1e-2);
+    }
+
+    #[test]
+    fn asymptotic_tail_against_mpmath() {
+        // mpmath: 2*nsum(lambda k: (-1)**(k-1)*exp(-2*k**2*x**2), [1, inf])
+        for (x, want) in [
+            (0.5, 0.963945243664875),
+            (1.0, 0.2699996716773545),
+            (1.36, 0.04948587675537788),
+            (2.0, 0.0006709252557796954),
+        ] {
+            let got = kolmogorov_sf(x);
+            assert!((got - want).abs() < 1e-15, "x = {x}: {got}");
+        }
+    }
+
+    #[test]
+    fn accepts_the_true_law_and_rejects_others() {
+        let mut rng = SplitMix64::seed_from_u64(11);
+        let e = Exponential::new(2.0).unwrap();
+        for n in [30, 1000] {
+            let xs: Vec<f64> = (0..n).map(|_| e.sample(&mut rng)).collect();
+            let fit = ks_test(&xs, &e);
+            assert!(fit.p_value > 0.01, "n = {n}: {fit:?}");
+            if n == 1000 {
+                let off = ks_test(&xs, &Exponential::new(1.5).unwrap());
+                assert!(
+                    off.statistic > fit.statistic && off.p_value < 1e-6,
+                    "{off:?}"
+                );
+            }
+        }
+        // One point at the median: D = 1/2, P(D₁ ≥ 1/2) = 1.
+        let one = ks_test(&[0.5], &Uniform::standard());
+        assert_eq!((one.statistic, one.p_value), (0.5, 1.0));
+        assert!(ks_test(&[], &Uniform::standard()).p_value.is_nan());
+    }
+
+    #[test]
+    fn chi2_gof_against_scipy_examples() {
+        // scipy.stats.chisquare docs; p-values from mpmath gammainc(k/2, x/2, inf, regularized=True)
+        let obs = [16, 18, 16, 14, 12, 12];
+        let r = chi2_gof(&obs, Expected::Counts(&[1.0; 6]), 0).unwrap();
+        assert_eq!((r.statistic, r.dof), (2.0, 5));
+        assert!((r.p_value - 0.84914503608460964).abs() < 1e-14);
+        let even = [16.0, 16.0, 16.0, 16.0, 16.0, 8.0];
+        let r = chi2_gof(&obs, Expected::Counts(&even), 0).unwrap();
+        assert!((r.statistic - 3.5).abs() < 1e-14);
+        assert!((r.p_value - 0.62338762774958203).abs() < 1e-14);
+        let r = chi2_gof(&obs, Expected::Counts(&[1.0; 6]), 1).unwrap();
+        assert!((r.p_value - 0.73575888234288464).abs() < 1e-14);
+
+        assert!(chi2_gof(&obs, Expected::Counts(&[1.0; 5]), 0).is_err());
+        assert!(chi2_gof(&[0, 0], Expected::Counts(&[1.0; 2]), 0).is_err());
+        assert!(chi2_gof(&obs, Expected::Counts(&[1.0; 6]), 5).is_err());
+        assert!(chi2_gof(&[3, 4], Expected::Counts(&[1.0, -1.0]), 0).is_err());
+    }
+
+    #[test]
+    fn chi2_gof_validates_samplers() {
+        let mut rng = SplitMix64::seed_from_u64(3);
+        let b = Binomial::new(20, 0.3).unwrap();
+        let mut observed = [0u64; 9]; // ≤ 2, 3, …, 9, ≥ 10
+        for _ in 0..20_000 {
+            observed[(b.sample(&mut rng).clamp(2, 10) - 2) as usize] += 1;
+        }
+        let fit = chi2_gof(&observed, Expected::Discrete { dist: &b, first: 2 }, 0).unwrap();
+        assert!(fit.p_value > 0.01, "{fit:?}");
+        let wrong = Binomial::new(20, 0.31).unwrap();
+        let off = chi2_gof(
+            &observed,
+            Expected::Discrete {
+                dist: &wrong,
+                first: 2,
+            },
+            0,
+        )
+        .unwrap();
+        assert!(off.p_value < 1e-6, "{off:?}");
+
+        let n = Normal::standard();
+        let mut h = Histogram::new(-2.5, 2.5, 10).unwrap();
+        h.extend((0..20_000).map(|_| n.sample(&mut rng)));
+        let edges = h.edges();
+        let fit = chi2_gof(h.counts(), Expected::Continuous { dist: &n, edges }, 0).unwrap();
+        assert!(fit.p_value > 0.01, "{fit:?}");
+        let wide = Normal::new(0.0, 1.05).unwrap();
+        let off = chi2_gof(h.counts(), Expected::Continuous { dist: &wide, edges }, 0).unwrap();
+        assert!(off.p_value < 1e-6, "{off:?}");
+    }
+
+    #[test]
+    fn chi2_independence_against_scipy_example() {
+        // scipy.stats.chi2_contingency docs (no correction applies beyond 2×2).
+        let r = chi2_independence(&[vec![10, 10, 20], vec![20, 20, 20]]).unwrap();
+        assert!((r.statistic - 25.0 / 9.0).abs() < 1e-14);
+        assert_eq!(r.dof, 2);
+        assert!((r.p_value - 0.2493522087772962).abs() < 1e-14);
+
+        assert!(chi2_independence(&[[1, 2]]).is_err());
+        assert!(chi2_independence(&[vec![1, 2], vec![3]]).is_err());
+        assert!(chi2_independence(&[[1, 0], [2, 0]]).is_err());
+    }
+}
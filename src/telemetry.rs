@@ -0,0 +1,135 @@
+//! Optional sampler telemetry: rejection counts and search-loop iterations.
+//!
+//! With the `instrumentation` feature enabled, samplers bump per-thread counters that can
+//! be read with [`snapshot`] and cleared with [`reset`]. Without the feature every call
+//! compiles to nothing and [`snapshot`] returns zeros.
+//!
+//! ```
+//! use probability_rs::{Distribution, dist::gamma::Gamma, rng::SplitMix64, telemetry};
+//! let g = Gamma::new(0.7, 1.0).unwrap();
+//! let mut rng = SplitMix64::seed_from_u64(1);
+//! telemetry::reset();
+//! for _ in 0..1000 {
+//!     g.sample(&mut rng);
+//! }
+//! let c = telemetry::snapshot();
+//! let per_draw = c.get(telemetry::Counter::GammaRejections) as f64 / 1000.0;
+//! # let _ = per_draw;
+//! ```
+
+/// Events reported by the samplers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Counter {
+    /// Marsaglia–Tsang proposals drawn (one per accepted or rejected candidate).
+    GammaProposals,
+    /// Marsaglia–Tsang proposals rejected (`v <= 0` or failed acceptance test).
+    GammaRejections,
+    /// Candidates accepted by the cheap squeeze, without evaluating logarithms.
+    GammaSqueezeAccepts,
+    /// Polar-method pairs rejected for falling outside the unit disc.
+    NormalPolarRejections,
+    /// Ziggurat draws that missed the rectangle fast path (wedge or tail tests).
+    NormalZigguratSlowPaths,
+    /// Exponential ziggurat draws that missed the rectangle fast path.
+    ExponentialZigguratSlowPaths,
+    /// Steps taken by the Poisson inversion search.
+    PoissonSearchSteps,
+    /// PTRS candidates rejected by the Poisson sampler.
+    PoissonPtrsRejections,
+    /// Steps taken by the binomial inversion search.
+    BinomialSearchSteps,
+    /// BTPE candidates rejected by the binomial sampler.
+    BinomialBtpeRejections,
+}
+
+impl Counter {
+    /// Every counter, in declaration order.
+    pub const ALL: [Counter; 10] = [
+        Counter::GammaProposals,
+        Counter::GammaRejections,
+        Counter::GammaSqueezeAccepts,
+        Counter::NormalPolarRejections,
+        Counter::NormalZigguratSlowPaths,
+        Counter::ExponentialZigguratSlowPaths,
+        Counter::PoissonSearchSteps,
+        Counter::PoissonPtrsRejections,
+        Counter::BinomialSearchSteps,
+        Counter::BinomialBtpeRejections,
+    ];
+}
+
+/// Counter values of the current thread at the time of [`snapshot`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Counters {
+    values: [u64; Counter::ALL.len()],
+}
+
+impl Counters {
+    #[inline]
+    pub fn get(&self, c: Counter) -> u64 {
+        self.values[c as usize]
+    }
+}
+
+/// Whether the crate was built with the `instrumentation` feature.
+pub const ENABLED: bool = cfg!(feature = "instrumentation");
+
+#[cfg(feature = "instrumentation")]
+thread_local! {
+    static COUNTS: std::cell::Cell<[u64; Counter::ALL.len()]> =
+        const { std::cell::Cell::new([0; Counter::ALL.len()]) };
+}
+
+/// Adds `n` to counter `c` on the current thread.
+#[inline(always)]
+pub(crate) fn record(c: Counter, n: u64) {
+    #[cfg(feature = "instrumentation")]
+    COUNTS.with(|cell| {
+        let mut v = cell.get();
+        v[c as usize] = v[c as usize].wrapping_add(n);
+        cell.set(v);
+    });
+    #[cfg(not(feature = "instrumentation"))]
+    let _ = (c, n);
+}
+
+/// Current counter values of this thread.
+pub fn snapshot() -> Counters {
+    #[cfg(feature = "instrumentation")]
+    return Counters {
+        values: COUNTS.with(|cell| cell.get()),
+    };
+    #[cfg(not(feature = "instrumentation"))]
+    Counters::default()
+}
+
+/// Clears all counters of this thread.
+pub fn reset() {
+    #[cfg(feature = "instrumentation")]
+    COUNTS.with(|cell| cell.set([0; Counter::ALL.len()]));
+}
+
+#[cfg(all(test, feature = "instrumentation"))]
+mod tests {
+    use super::*;
+    use crate::Distribution;
+    use crate::dist::gamma::Gamma;
+    use crate::rng::SplitMix64;
+
+    #[test]
+    fn gamma_counts_proposals() {
+        let g = Gamma::new(2.0, 1.0).unwrap();
+        let mut rng = SplitMix64::seed_from_u64(5);
+        reset();
+        for _ in 0..500 {
+            g.sample(&mut rng);
+        }
+        let c = snapshot();
+        let proposals = c.get(Counter::GammaProposals);
+        assert_eq!(proposals - c.get(Counter::GammaRejections), 500);
+        assert!(c.get(Counter::GammaSqueezeAccepts) <= 500);
+        reset();
+        assert_eq!(snapshot(), Counters::default());
+    }
+}